@@ -271,6 +271,8 @@ pub async fn new_wallet_context_from_cluster(
         keystore,
         client_type: ClientType::RPC(fullnode_url.into(), None),
         active_address: Some(address),
+        envs: vec![],
+        active_env: None,
     }
     .persisted(&wallet_config_path)
     .save()
@@ -279,10 +279,18 @@ impl<R: rand::RngCore + rand::CryptoRng> ConfigBuilder<R> {
                     admin_interface_port: utils::get_available_port(),
                     json_rpc_address: utils::available_local_socket_address(),
                     websocket_address: None,
+                    json_rpc_multi_get_max_batch_size:
+                        crate::node::DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE,
+                    json_rpc_rate_limit: None,
                     consensus_config: Some(consensus_config),
                     enable_event_processing: false,
+                    event_store_backend: Default::default(),
+                    event_store_retention: None,
+                    rebuild_event_store_on_startup: false,
                     enable_gossip: true,
                     enable_checkpoint: true,
+                    object_pruning: None,
+                    bootstrap_snapshot_path: None,
                     enable_reconfig: false,
                     genesis: crate::node::Genesis::new(genesis.clone()),
                     grpc_load_shed: initial_accounts_config.grpc_load_shed,
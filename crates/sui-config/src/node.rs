@@ -28,6 +28,9 @@ use sui_types::sui_serde::KeyPairBase64;
 // Default max number of concurrent requests served
 pub const DEFAULT_GRPC_CONCURRENCY_LIMIT: usize = 20000000000;
 
+// Default max number of items in one JSON-RPC multi-get request
+pub const DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE: usize = 50;
+
 #[serde_as]
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
@@ -53,6 +56,11 @@ pub struct NodeConfig {
     pub json_rpc_address: SocketAddr,
     #[serde(default = "default_websocket_address")]
     pub websocket_address: Option<SocketAddr>,
+    #[serde(default = "default_json_rpc_multi_get_max_batch_size")]
+    pub json_rpc_multi_get_max_batch_size: usize,
+    /// Per-client rate limiting of the JSON-RPC server, disabled if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_rpc_rate_limit: Option<JsonRpcRateLimitConfig>,
 
     #[serde(default = "default_metrics_address")]
     pub metrics_address: SocketAddr,
@@ -64,12 +72,29 @@ pub struct NodeConfig {
 
     #[serde(default)]
     pub enable_event_processing: bool,
+    #[serde(default)]
+    pub event_store_backend: EventStoreBackend,
+    /// Pruning of the event store, events are kept forever if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_store_retention: Option<EventStoreRetentionConfig>,
+    /// Replay the effects of the transactions missing from the event store on startup, before
+    /// serving requests.
+    #[serde(default)]
+    pub rebuild_event_store_on_startup: bool,
 
     #[serde(default)]
     pub enable_gossip: bool,
 
     #[serde(default = "bool_true")]
     pub enable_checkpoint: bool,
+    /// Pruning of the object versions superseded by old checkpoints, all versions are kept if
+    /// not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub object_pruning: Option<ObjectPruningConfig>,
+    /// State snapshot to bootstrap the database from, instead of genesis, when the database is
+    /// empty. See `sui-tool snapshot export`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bootstrap_snapshot_path: Option<PathBuf>,
 
     #[serde(default)]
     pub enable_reconfig: bool,
@@ -122,6 +147,10 @@ pub fn default_websocket_address() -> Option<SocketAddr> {
     Some(SocketAddr::new(IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0)), 9001))
 }
 
+pub fn default_json_rpc_multi_get_max_batch_size() -> usize {
+    DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE
+}
+
 pub fn default_concurrency_limit() -> Option<usize> {
     Some(DEFAULT_GRPC_CONCURRENCY_LIMIT)
 }
@@ -196,6 +225,147 @@ impl ConsensusConfig {
     }
 }
 
+/// Per-client-IP token bucket limits of the JSON-RPC HTTP server.
+/// Each client IP accrues `tokens-per-second` tokens, up to `burst-size`, and every call
+/// spends the cost of its method. Calls are rejected while a client has too few tokens.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct JsonRpcRateLimitConfig {
+    pub tokens_per_second: u32,
+    pub burst_size: u32,
+    /// Cost of a call to a method missing from `method-costs`.
+    #[serde(default = "default_json_rpc_method_cost")]
+    pub default_method_cost: u32,
+    /// Cost of a call by method name, e.g. `sui_getTransactionsInRange: 20`.
+    #[serde(default)]
+    pub method_costs: BTreeMap<String, u32>,
+}
+
+impl JsonRpcRateLimitConfig {
+    pub fn method_cost(&self, method: &str) -> u32 {
+        self.method_costs
+            .get(method)
+            .copied()
+            .unwrap_or(self.default_method_cost)
+    }
+
+    /// Check that a client with a full bucket can afford a call to every method, calls costing
+    /// more than `burst-size` would otherwise always be rejected.
+    pub fn validate(&self) -> Result<()> {
+        let costs = self
+            .method_costs
+            .iter()
+            .map(|(method, cost)| (method.as_str(), *cost))
+            .chain(std::iter::once(("default", self.default_method_cost)));
+        for (method, cost) in costs {
+            if cost > self.burst_size {
+                anyhow::bail!(
+                    "JSON-RPC rate limit burst size {} is below the cost {cost} of method {method}",
+                    self.burst_size
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_json_rpc_method_cost() -> u32 {
+    1
+}
+
+/// Retention policy of the event store. Events older than `max-age-secs`, older than the max
+/// age set for their event type, or beyond the newest `max-event-count` events are pruned by a
+/// background task. Events are pruned in sequence number order, so that the events left are
+/// always the newest ones.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct EventStoreRetentionConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age_secs: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_event_count: Option<u64>,
+    /// Max age of the events of some types, by event type name, e.g. `TransferObject: 86400`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub max_age_secs_by_event_type: BTreeMap<String, u64>,
+    #[serde(default = "default_event_store_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+    /// Interval between vacuums, which return the space freed by pruning to the file system.
+    /// SQLite stores are vacuumed incrementally, except for the first vacuum of stores created
+    /// before incremental vacuuming. The store is never vacuumed if not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vacuum_interval_secs: Option<u64>,
+}
+
+fn default_event_store_prune_interval_secs() -> u64 {
+    600
+}
+
+/// Storage engine of the event store.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum EventStoreBackend {
+    /// SQLite database, `events.db` in the node DB.
+    #[default]
+    Sqlite,
+    /// RocksDB database, `events` in the node DB. Writes don't block each other, at the cost of
+    /// slower filtered queries.
+    Rocksdb,
+}
+
+impl EventStoreBackend {
+    /// Default location of the event store in the node DB at `db_path`.
+    pub fn default_path(&self, db_path: &Path) -> PathBuf {
+        match self {
+            EventStoreBackend::Sqlite => db_path.join("events.db"),
+            EventStoreBackend::Rocksdb => db_path.join("events"),
+        }
+    }
+}
+
+impl std::str::FromStr for EventStoreBackend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sqlite" => Ok(EventStoreBackend::Sqlite),
+            "rocksdb" => Ok(EventStoreBackend::Rocksdb),
+            _ => Err(anyhow::anyhow!(
+                "Unknown event store backend {s}, expected sqlite or rocksdb"
+            )),
+        }
+    }
+}
+
+/// Pruning of superseded object versions. A background task deletes the object versions that
+/// the transactions of a checkpoint took as input and replaced, once the checkpoint is older than
+/// the newest `num-checkpoints-to-retain` certified checkpoints, or than the last
+/// `num-epochs-to-retain` epochs. Past object reads of pruned versions report them as pruned.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ObjectPruningConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_checkpoints_to_retain: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub num_epochs_to_retain: Option<u64>,
+    #[serde(default = "default_object_prune_interval_secs")]
+    pub prune_interval_secs: u64,
+    /// Checkpoints pruned at most per run, to bound the time a run takes.
+    #[serde(default = "default_max_checkpoints_per_prune")]
+    pub max_checkpoints_per_run: u64,
+    /// Whether the pruner runs from startup. It can be switched on and off at runtime through
+    /// the admin interface.
+    #[serde(default = "bool_true")]
+    pub enabled: bool,
+}
+
+fn default_object_prune_interval_secs() -> u64 {
+    60
+}
+
+fn default_max_checkpoints_per_prune() -> u64 {
+    100
+}
+
 /// Publicly known information about a validator
 /// TODO read most of this from on-chain
 #[serde_as]
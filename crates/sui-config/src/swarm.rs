@@ -118,10 +118,18 @@ impl NetworkConfig {
             } else {
                 None
             },
+            json_rpc_multi_get_max_batch_size:
+                crate::node::DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE,
+            json_rpc_rate_limit: None,
             consensus_config: None,
             enable_event_processing,
+            event_store_backend: Default::default(),
+            event_store_retention: None,
+            rebuild_event_store_on_startup: false,
             enable_gossip: true,
             enable_checkpoint: true,
+            object_pruning: None,
+            bootstrap_snapshot_path: None,
             enable_reconfig: false,
             genesis: validator_config.genesis.clone(),
             grpc_load_shed: None,
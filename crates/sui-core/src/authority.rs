@@ -5,6 +5,7 @@
 use std::hash::Hash;
 use std::ops::Deref;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::{
     collections::{HashMap, VecDeque},
     pin::Pin,
@@ -21,7 +22,12 @@ use chrono::prelude::*;
 use fastcrypto::traits::KeyPair;
 use futures::stream::{self, Stream};
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use move_core_types::{language_storage::ModuleId, resolver::ModuleResolver};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+    resolver::ModuleResolver,
+};
 use move_vm_runtime::{move_vm::MoveVM, native_functions::NativeFunctionTable};
 use parking_lot::Mutex;
 use prometheus::{
@@ -31,12 +37,13 @@ use prometheus::{
 use tap::TapFallible;
 use tokio::sync::{
     broadcast::{self, error::RecvError},
-    mpsc,
+    mpsc, RwLock, RwLockWriteGuard,
 };
 use tracing::Instrument;
-use tracing::{debug, error, instrument, warn};
+use tracing::{debug, error, info, instrument, warn};
 use typed_store::Map;
 
+use authority_store::ObjectKey;
 pub use authority_store::{
     AuthorityStore, GatewayStore, ResolverWrapper, SuiDataStore, UpdateType,
 };
@@ -47,22 +54,28 @@ use narwhal_config::{
 
 use sui_adapter::adapter;
 use sui_config::genesis::Genesis;
-use sui_json_rpc_types::{SuiEventEnvelope, SuiTransactionEffects};
+use sui_json_rpc_types::{
+    Balance, SuiDryRunTransactionResponse, SuiEventEnvelope, SuiTransactionEffects,
+};
 use sui_simulator::nondeterministic;
 use sui_storage::{
     event_store::{EventStore, EventStoreType, StoredEvent},
+    indexes::ObjectTypeIndexesBackfill,
     node_sync_store::NodeSyncStore,
     write_ahead_log::{DBTxGuard, TxGuard, WriteAheadLog},
     IndexStore,
 };
+use sui_types::coin::Coin;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{AuthorityKeyPair, NetworkKeyPair};
+use sui_types::event::EventID;
+use sui_types::filter::EventFilter;
 use sui_types::messages_checkpoint::{
-    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointRequest, CheckpointRequestType,
-    CheckpointResponse, CheckpointSequenceNumber,
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents, CheckpointRequest,
+    CheckpointRequestType, CheckpointResponse, CheckpointSequenceNumber,
 };
 use sui_types::object::{Owner, PastObjectRead};
-use sui_types::query::TransactionQuery;
+use sui_types::query::{EventQuery, TransactionQuery};
 use sui_types::sui_system_state::SuiSystemState;
 use sui_types::temporary_store::InnerTemporaryStore;
 pub use sui_types::temporary_store::TemporaryStore;
@@ -75,6 +88,7 @@ use sui_types::{
     fp_ensure,
     messages::*,
     object::{Object, ObjectFormatOptions, ObjectRead},
+    parse_sui_struct_tag,
     storage::{BackingPackageStore, DeleteKind},
     MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID,
 };
@@ -121,6 +135,8 @@ mod authority_store;
 
 pub const MAX_ITEMS_LIMIT: u64 = 1_000;
 const BROADCAST_CAPACITY: usize = 10_000;
+/// How many live objects are loaded at once to index them by type in the background.
+const OBJECT_TYPE_INDEXES_BACKFILL_CHUNK_SIZE: usize = 10_000;
 
 pub(crate) const MAX_TX_RECOVERY_RETRY: u32 = 3;
 type CertTxGuard<'a> = DBTxGuard<'a, CertifiedTransaction>;
@@ -486,6 +502,10 @@ pub struct AuthorityState {
 
     /// A channel to tell consensus to reconfigure.
     tx_reconfigure_consensus: mpsc::Sender<ReconfigConsensusMessage>,
+
+    /// Held shared by certificates from their tx guard to their commit, and exclusively by
+    /// [`Self::pause_execution`], so that the stores are never observed mid-transaction.
+    execution_lock: RwLock<()>,
 }
 
 /// The authority state encapsulates all state, drives execution, and ensures safety.
@@ -621,6 +641,7 @@ impl AuthorityState {
             }
         );
 
+        let _execution_guard = self.execution_lock.read().await;
         let tx_guard = self.database.acquire_tx_guard(certificate).await?;
 
         if certificate.contains_shared_object() {
@@ -698,6 +719,7 @@ impl AuthorityState {
             ?tx_digest,
             tx_kind = certificate.signed_data.data.kind_as_str()
         );
+        let _execution_guard = self.execution_lock.read().await;
         let tx_guard = self
             .database
             .acquire_tx_guard(certificate)
@@ -937,7 +959,7 @@ impl AuthorityState {
         &self,
         transaction: &Transaction,
         transaction_digest: TransactionDigest,
-    ) -> Result<SuiTransactionEffects, anyhow::Error> {
+    ) -> Result<SuiDryRunTransactionResponse, anyhow::Error> {
         transaction.verify()?;
         let (gas_status, input_objects) =
             transaction_input_checker::check_transaction_input(&self.database, transaction).await?;
@@ -946,7 +968,7 @@ impl AuthorityState {
         let transaction_dependencies = input_objects.transaction_dependencies();
         let temporary_store =
             TemporaryStore::new(self.database.clone(), input_objects, transaction_digest);
-        let (_inner_temp_store, effects, _execution_error) =
+        let (inner_temp_store, effects, _execution_error) =
             execution_engine::execute_transaction_to_effects(
                 shared_object_refs,
                 temporary_store,
@@ -958,7 +980,8 @@ impl AuthorityState {
                 gas_status,
                 self.epoch(),
             );
-        SuiTransactionEffects::try_from(effects, self.module_cache.as_ref())
+        let effects = SuiTransactionEffects::try_from(effects, self.module_cache.as_ref())?;
+        SuiDryRunTransactionResponse::new(effects, &inner_temp_store)
     }
 
     pub fn is_tx_already_executed(&self, digest: &TransactionDigest) -> SuiResult<bool> {
@@ -1006,9 +1029,116 @@ impl AuthorityState {
             seq,
             digest,
             timestamp_ms,
+        )?;
+
+        let written: Vec<ObjectRef> = effects
+            .effects
+            .all_mutated()
+            .map(|(obj_ref, _, _)| *obj_ref)
+            .collect();
+        indexes.index_object_types(
+            self.object_type_index_entries(&written)?.into_iter(),
+            effects
+                .effects
+                .deleted
+                .iter()
+                .chain(&effects.effects.wrapped)
+                .copied(),
         )
     }
 
+    /// The Move objects among the object versions `object_refs`, each with its struct type, to
+    /// index them by type. Packages, and versions that are no longer stored, are left out.
+    fn object_type_index_entries(
+        &self,
+        object_refs: &[ObjectRef],
+    ) -> SuiResult<Vec<(ObjectInfo, StructTag)>> {
+        let keys: Vec<ObjectKey> = object_refs.iter().map(ObjectKey::from).collect();
+        Ok(object_refs
+            .iter()
+            .zip(self.database.multi_get_object_by_key(&keys)?)
+            .filter_map(|(object_ref, object)| {
+                let object = object?;
+                let type_ = object.data.type_()?.clone();
+                Some((ObjectInfo::new(object_ref, &object), type_))
+            })
+            .collect())
+    }
+
+    /// The object ID from which the live objects remain to be indexed by type, or None if they
+    /// are all indexed. The type indexes are otherwise only updated as transactions are
+    /// post-processed, which misses the objects of the genesis, and the ones written before the
+    /// indexes were introduced.
+    fn object_type_indexes_backfill_start(&self) -> SuiResult<Option<ObjectID>> {
+        let indexes = match &self.indexes {
+            Some(indexes) => indexes,
+            None => return Ok(None),
+        };
+        Ok(match indexes.object_type_indexes_backfill()? {
+            None => {
+                info!("Indexing the live objects by type");
+                Some(ObjectID::ZERO)
+            }
+            Some(ObjectTypeIndexesBackfill::Until(start)) => {
+                info!(?start, "Resuming the indexing of the live objects by type");
+                Some(start)
+            }
+            Some(ObjectTypeIndexesBackfill::Done) => None,
+        })
+    }
+
+    /// Indexes by type the next chunk of the live objects from the object ID `start`, saving the
+    /// progress along with them, and returns the object ID to continue from, or None once all
+    /// the live objects are indexed.
+    fn backfill_object_type_indexes(&self, start: ObjectID) -> SuiResult<Option<ObjectID>> {
+        let indexes = self.get_indexes()?;
+        let mut live_objects = self.database.iter_live_object_set_from(start)?;
+        let chunk: Vec<ObjectRef> = live_objects
+            .by_ref()
+            .take(OBJECT_TYPE_INDEXES_BACKFILL_CHUNK_SIZE)
+            .collect();
+        let next = live_objects.next().map(|object_ref| object_ref.0);
+        indexes.backfill_object_types(
+            self.object_type_index_entries(&chunk)?.into_iter(),
+            next.map_or(
+                ObjectTypeIndexesBackfill::Done,
+                ObjectTypeIndexesBackfill::Until,
+            ),
+        )?;
+        if next.is_none() {
+            info!("Indexed the live objects by type");
+        }
+        Ok(next)
+    }
+
+    /// Indexes by type the live objects from the object ID `start`, a chunk at a time. Each chunk
+    /// runs on a blocking thread, while holding `post_processing` so that it never runs
+    /// concurrently with the post-processing of a transaction. Each chunk indexes the versions
+    /// that are live when it is read, and versions older than the indexed ones are ignored, so
+    /// the indexes end up consistent.
+    async fn run_object_type_indexes_backfill(
+        self: Arc<Self>,
+        mut start: ObjectID,
+        post_processing: Arc<tokio::sync::Mutex<()>>,
+    ) {
+        loop {
+            let _guard = post_processing.lock().await;
+            let state = self.clone();
+            let next =
+                tokio::task::spawn_blocking(move || state.backfill_object_type_indexes(start))
+                    .await
+                    .expect("Indexing the live objects by type panicked");
+            match next {
+                Ok(Some(next)) => start = next,
+                Ok(None) => return,
+                Err(e) => {
+                    error!("Indexing the live objects by type failed, it resumes at the next start: {e}");
+                    return;
+                }
+            }
+        }
+    }
+
     #[instrument(level = "debug", skip_all, fields(seq=?seq, tx_digest=?digest), err)]
     async fn post_process_one_tx(
         &self,
@@ -1042,7 +1172,9 @@ impl AuthorityState {
 
         // Stream transaction
         if let Some(transaction_streamer) = &self.transaction_streamer {
-            transaction_streamer.enqueue((cert, effects.clone())).await;
+            transaction_streamer
+                .enqueue(seq, (cert, effects.clone()))
+                .await;
             self.metrics
                 .post_processing_total_tx_added_to_streamer
                 .inc();
@@ -1066,8 +1198,19 @@ impl AuthorityState {
 
     // TODO: This should persist the last successfully-processed sequence to disk, and upon
     // starting up, look for any sequences in the store since then and process them.
-    pub async fn run_tx_post_processing_process(&self) -> SuiResult {
+    pub async fn run_tx_post_processing_process(self: &Arc<Self>) -> SuiResult {
         let mut subscriber = self.subscribe_batch();
+        let post_processing = Arc::new(tokio::sync::Mutex::new(()));
+        let backfill_start = self
+            .object_type_indexes_backfill_start()
+            .tap_err(|e| error!("Cannot index the live objects by type: {e}"))
+            .unwrap_or(None);
+        if let Some(start) = backfill_start {
+            tokio::spawn(
+                self.clone()
+                    .run_object_type_indexes_backfill(start, post_processing.clone()),
+            );
+        }
 
         loop {
             match subscriber.recv().await {
@@ -1084,6 +1227,7 @@ impl AuthorityState {
                         self.metrics
                             .post_processing_total_tx_sent_to_post_processing
                             .inc();
+                        let _guard = post_processing.lock().await;
                         if let Err(e) = self.post_process_one_tx(seq, &digest).await {
                             warn!(?digest, "Couldn't process tx: {e}");
                         }
@@ -1315,6 +1459,44 @@ impl AuthorityState {
         }
     }
 
+    /// Return the sequence number of the latest certified checkpoint stored by this authority.
+    pub fn get_latest_checkpoint_sequence_number(&self) -> SuiResult<CheckpointSequenceNumber> {
+        self.checkpoints
+            .lock()
+            .latest_certified_checkpoint()
+            .map(|checkpoint| checkpoint.sequence_number())
+            .ok_or_else(|| SuiError::CheckpointingError {
+                error: "No certified checkpoint is available".to_string(),
+            })
+    }
+
+    /// Return the certified summary of the checkpoint with the given sequence number.
+    pub fn get_checkpoint_summary(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> SuiResult<CertifiedCheckpointSummary> {
+        match self.checkpoints.lock().get_checkpoint(seq)? {
+            Some(AuthenticatedCheckpoint::Certified(checkpoint)) => Ok(checkpoint),
+            _ => Err(SuiError::CheckpointingError {
+                error: format!("Certified checkpoint {seq} not found"),
+            }),
+        }
+    }
+
+    /// Return the transactions and effects digests of the checkpoint with the given
+    /// sequence number, in checkpoint order.
+    pub fn get_checkpoint_contents(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> SuiResult<CheckpointContents> {
+        self.checkpoints
+            .lock()
+            .get_checkpoint_contents(seq)?
+            .ok_or_else(|| SuiError::CheckpointingError {
+                error: format!("Contents of checkpoint {seq} not found"),
+            })
+    }
+
     pub async fn handle_checkpoint_streaming(
         &self,
         _request: CheckpointStreamRequest,
@@ -1462,6 +1644,7 @@ impl AuthorityState {
             consensus_guardrail: AtomicUsize::new(0),
             metrics: Arc::new(AuthorityMetrics::new(prometheus_registry)),
             tx_reconfigure_consensus,
+            execution_lock: RwLock::new(()),
         };
 
         // Process tx recovery log first, so that the batch and checkpoint recovery (below)
@@ -1604,6 +1787,7 @@ impl AuthorityState {
         let mut limit = limit.unwrap_or(usize::MAX);
         while limit > 0 {
             limit -= 1;
+            let _execution_guard = self.execution_lock.read().await;
             if let Some((cert, tx_guard)) = self.database.wal.read_one_recoverable_tx().await? {
                 let digest = tx_guard.tx_id();
                 debug!(?digest, "replaying failed cert from log");
@@ -1639,6 +1823,12 @@ impl AuthorityState {
         self.checkpoints.clone()
     }
 
+    /// Waits for the certificates being executed to be committed, and holds off the execution
+    /// of new ones until the returned guard is dropped.
+    pub async fn pause_execution(&self) -> RwLockWriteGuard<'_, ()> {
+        self.execution_lock.write().await
+    }
+
     pub(crate) fn update_committee(&self, new_committee: Committee) -> SuiResult {
         // TODO: It's likely safer to do the following operations atomically, in case this function
         // gets called from different threads. It cannot happen today, but worth the caution.
@@ -1716,11 +1906,53 @@ impl AuthorityState {
         }
     }
 
+    /// Batched version of `get_object_read`, returning one result per object ID.
+    /// The latest references of the objects are looked up together, then the objects are read
+    /// from the object store in one go, and fail independently of each other.
+    pub async fn multi_get_object_read(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<Result<ObjectRead, SuiError>>, SuiError> {
+        let parent_entries = self.database.multi_get_latest_parent_entries(object_ids)?;
+        let keys: Vec<ObjectKey> = parent_entries
+            .iter()
+            .flatten()
+            .filter(|(obj_ref, _)| obj_ref.2.is_alive())
+            .map(|(obj_ref, _)| ObjectKey::from(obj_ref))
+            .collect();
+        let mut objects = self.database.multi_get_object_by_key(&keys)?.into_iter();
+
+        Ok(object_ids
+            .iter()
+            .zip(parent_entries)
+            .map(|(object_id, parent_entry)| match parent_entry {
+                None => Ok(ObjectRead::NotExists(*object_id)),
+                Some((obj_ref, _)) if obj_ref.2.is_alive() => match objects.next().flatten() {
+                    None => {
+                        error!("Object with in parent_entry is missing from object store, datastore is inconsistent");
+                        Err(SuiError::ObjectNotFound {
+                            object_id: *object_id,
+                        })
+                    }
+                    Some(object) => {
+                        let layout = object.get_layout(
+                            ObjectFormatOptions::default(),
+                            self.module_cache.as_ref(),
+                        )?;
+                        Ok(ObjectRead::Exists(obj_ref, object, layout))
+                    }
+                },
+                Some((obj_ref, _)) => Ok(ObjectRead::Deleted(obj_ref)),
+            })
+            .collect())
+    }
+
     /// This function aims to serve rpc reads on past objects and
     /// we don't expect it to be called for other purposes.
-    /// Depending on the object pruning policies that will be enforced in the
-    /// future there is no software-level guarantee/SLA to retrieve an object
-    /// with an old version even if it exists/existed.
+    /// Superseded object versions may be pruned depending on the object pruning
+    /// config, in which case `PastObjectRead::VersionPruned` is returned, so there
+    /// is no software-level guarantee/SLA to retrieve an object with an old version
+    /// even if it exists/existed.
     pub async fn get_past_object_read(
         &self,
         object_id: &ObjectID,
@@ -1740,7 +1972,12 @@ impl AuthorityState {
                 if version < obj_ref.1 {
                     // Read past objects
                     return Ok(match self.database.get_object_by_key(object_id, version)? {
-                        None => PastObjectRead::VersionNotFound(*object_id, version),
+                        None => match self.database.get_pruned_object_version(object_id)? {
+                            Some(pruned_version) if version <= pruned_version => {
+                                PastObjectRead::VersionPruned(*object_id, version)
+                            }
+                            _ => PastObjectRead::VersionNotFound(*object_id, version),
+                        },
                         Some(object) => {
                             let layout = object.get_layout(
                                 ObjectFormatOptions::default(),
@@ -1779,6 +2016,77 @@ impl AuthorityState {
         self.database.get_owner_objects(owner)
     }
 
+    /// Return the `Coin<T>` objects owned by an address, ordered by object ID.
+    /// If `coin_type` is given, only coins with `T = coin_type` are returned.
+    /// At most `limit` coins are returned, starting at object ID `cursor` if given.
+    pub async fn get_owned_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&StructTag>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> SuiResult<Vec<Object>> {
+        let coin_struct = coin_type.map(|coin_type| Coin::type_(coin_type.clone()));
+        let coin_ids: Vec<ObjectID> = self
+            .database
+            .get_owner_objects_iterator(
+                Owner::AddressOwner(owner),
+                cursor.unwrap_or(ObjectID::ZERO),
+            )?
+            .filter(|info| match parse_sui_struct_tag(&info.type_) {
+                Ok(tag) => match &coin_struct {
+                    Some(coin_struct) => &tag == coin_struct,
+                    None => Coin::is_coin(&tag),
+                },
+                Err(_) => false,
+            })
+            .take(limit.unwrap_or(usize::MAX))
+            .map(|info| info.object_id)
+            .collect();
+        Ok(self
+            .get_objects(&coin_ids)
+            .await?
+            .into_iter()
+            .flatten()
+            .collect())
+    }
+
+    /// Return the live objects with Move struct type `type_` owned by `owner`, ordered by object
+    /// ID. At most `limit` objects are returned, starting at object ID `cursor` if given.
+    pub fn get_objects_by_owner_and_type(
+        &self,
+        owner: Owner,
+        type_: &StructTag,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        self.get_indexes()?
+            .get_objects_by_owner_and_type(owner, type_, cursor, limit)
+    }
+
+    /// Return the live objects with Move struct type `type_`, whatever their owner, ordered by
+    /// object ID. At most `limit` objects are returned, starting at object ID `cursor` if given.
+    pub fn get_objects_by_type(
+        &self,
+        type_: &StructTag,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        self.get_indexes()?
+            .get_objects_by_type(type_, cursor, limit)
+    }
+
+    /// Sum the balances of the `Coin<T>` objects owned by an address, grouped by coin type `T`,
+    /// or of the given coin type only.
+    pub async fn get_balances(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&StructTag>,
+    ) -> SuiResult<Vec<Balance>> {
+        let coins = self.get_owned_coins(owner, coin_type, None, None).await?;
+        Balance::from_coins(&coins)
+    }
+
     pub fn get_total_transaction_number(&self) -> Result<u64, anyhow::Error> {
         QueryHelpers::get_total_transaction_number(&self.database)
     }
@@ -1805,6 +2113,23 @@ impl AuthorityState {
         QueryHelpers::get_transaction(&self.database, &digest)
     }
 
+    /// Batched version of `get_transaction`, returning one result per transaction digest.
+    pub async fn multi_get_transactions(
+        &self,
+        digests: &[TransactionDigest],
+    ) -> Result<Vec<Result<(CertifiedTransaction, TransactionEffects), SuiError>>, SuiError> {
+        let certificates = self.database.multi_get_certified_transaction(digests)?;
+        let effects = self.database.multi_get_effects(digests)?;
+        Ok(digests
+            .iter()
+            .zip(certificates.into_iter().zip(effects))
+            .map(|(digest, tx)| match tx {
+                (Some(certificate), Some(effects)) => Ok((certificate, effects)),
+                _ => Err(SuiError::TransactionNotFound { digest: *digest }),
+            })
+            .collect())
+    }
+
     fn get_indexes(&self) -> SuiResult<Arc<IndexStore>> {
         match &self.indexes {
             Some(i) => Ok(i.clone()),
@@ -1891,120 +2216,111 @@ impl AuthorityState {
             .map(|handler| handler.event_store.clone())
     }
 
-    /// Returns at most `limit` events emitted in the given transaction,
-    /// emitted within [start_time, end_time) in order of events emitted.
-    /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_transaction(
-        &self,
-        digest: TransactionDigest,
-        limit: usize,
-    ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
-        let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        let stored_events = es.events_by_transaction(digest, limit).await?;
-        StoredEvent::into_event_envelopes(stored_events)
-    }
-
-    /// Returns at most `limit` events emitted in the given module,
-    /// emitted within [start_time, end_time), sorted in in descending time.
+    /// Returns at most `limit` events matching `query`, starting from `cursor` (inclusive)
+    /// and sorted by event ID, in descending order if `descending` is set.
     /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_transaction_module(
+    pub async fn get_events(
         &self,
-        module_id: &ModuleId,
-        start_time: u64,
-        end_time: u64,
-        limit: usize,
-    ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
-        let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        let stored_events = es
-            .events_by_module_id(start_time, end_time, module_id, limit)
-            .await?;
-        StoredEvent::into_event_envelopes(stored_events)
-    }
-
-    /// Returns at most `limit` events with the given move event struct name, e.g.
-    /// `0x2::devnet_nft::MintNFTEvent` or
-    /// `0x2::SUI::test_foo<address, vector<u8>>` with type params,
-    /// emitted within [start_time, end_time), sorted in in descending time.
-    /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_move_event_struct_name(
-        &self,
-        move_event_struct_name: &str,
-        start_time: u64,
-        end_time: u64,
+        query: EventQuery,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        let stored_events = es
-            .events_by_move_event_struct_name(start_time, end_time, move_event_struct_name, limit)
-            .await?;
-        StoredEvent::into_event_envelopes(stored_events)
-    }
-
-    /// Returns at most `limit` events associated with the given sender,
-    /// emitted within [start_time, end_time), sorted in in descending time.
-    /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_sender(
-        &self,
-        sender: &SuiAddress,
-        start_time: u64,
-        end_time: u64,
-        limit: usize,
-    ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
-        let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        let stored_events = es
-            .events_by_sender(start_time, end_time, sender, limit)
-            .await?;
+        let stored_events = match query {
+            EventQuery::All => es.all_events(cursor, limit, descending).await?,
+            EventQuery::Transaction(digest) => {
+                es.events_by_transaction(digest, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::MoveModule { package, module } => {
+                let module_id = ModuleId::new(
+                    AccountAddress::from(package),
+                    Identifier::from_str(&module)?,
+                );
+                es.events_by_module_id(&module_id, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::MoveEvent(struct_name) => {
+                es.events_by_move_event_struct_name(&struct_name, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::MoveEventField {
+                struct_name,
+                path,
+                value,
+            } => {
+                es.events_by_move_event_field(
+                    &struct_name,
+                    &path,
+                    &value,
+                    cursor,
+                    limit,
+                    descending,
+                )
+                .await?
+            }
+            EventQuery::EventType(event_type) => {
+                es.events_by_type(event_type, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::Sender(sender) => {
+                es.events_by_sender(&sender, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::Recipient(recipient) => {
+                es.events_by_recipient(&recipient, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::Object(object) => {
+                es.events_by_object(&object, cursor, limit, descending)
+                    .await?
+            }
+            EventQuery::TimeRange {
+                start_time,
+                end_time,
+            } => {
+                es.event_iterator(start_time, end_time, cursor, limit, descending)
+                    .await?
+            }
+        };
         StoredEvent::into_event_envelopes(stored_events)
     }
 
-    /// Returns at most `limit` events associated with the given recipient,
-    /// emitted within [start_time, end_time), sorted in in descending time.
+    /// Returns at most `limit` events matching the `filter` tree, starting from `cursor` (inclusive)
+    /// and sorted by event ID, in descending order if `descending` is set.
     /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_recipient(
+    pub async fn query_events(
         &self,
-        recipient: &Owner,
-        start_time: u64,
-        end_time: u64,
+        filter: EventFilter,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
         let stored_events = es
-            .events_by_recipient(start_time, end_time, recipient, limit)
+            .events_by_filter(&filter, cursor, limit, descending)
             .await?;
         StoredEvent::into_event_envelopes(stored_events)
     }
 
-    /// Returns at most `limit` events associated with the given object,
-    /// emitted within [start_time, end_time), sorted in in descending time.
+    /// Returns at most `limit` events matching `filter` and emitted within [start_time, end_time),
+    /// sorted in ascending time.
     /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_object(
+    pub async fn get_events_in_time_range(
         &self,
-        object: &ObjectID,
+        filter: EventFilter,
         start_time: u64,
         end_time: u64,
         limit: usize,
     ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
         let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
         let stored_events = es
-            .events_by_object(start_time, end_time, object, limit)
+            .events_in_time_range(&filter, start_time, end_time, limit)
             .await?;
         StoredEvent::into_event_envelopes(stored_events)
     }
 
-    /// Returns at most `limit` events emitted within [start_time, end_time),
-    /// sorted in in descending time.
-    /// `limit` is capped to EVENT_STORE_QUERY_MAX_LIMIT
-    pub async fn get_events_by_timerange(
-        &self,
-        start_time: u64,
-        end_time: u64,
-        limit: usize,
-    ) -> Result<Vec<SuiEventEnvelope>, anyhow::Error> {
-        let es = self.get_event_store().ok_or(SuiError::NoEventStore)?;
-        let stored_events = es.event_iterator(start_time, end_time, limit).await?;
-        StoredEvent::into_event_envelopes(stored_events)
-    }
-
     pub async fn insert_genesis_object(&self, object: Object) {
         self.database
             .insert_genesis_object(object)
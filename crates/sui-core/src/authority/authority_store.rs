@@ -9,12 +9,14 @@ use crate::authority::authority_store_tables::ExecutionIndicesWithHash;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::iter;
 use std::path::Path;
 use std::sync::atomic::AtomicU64;
 use std::{fmt::Debug, path::PathBuf};
 use sui_storage::{
+    checkpoint_db,
+    db_stats::{db_stats, ColumnFamilyStats},
     mutex_table::{LockGuard, MutexTable},
     write_ahead_log::{DBWriteAheadLog, WriteAheadLog},
     LockService,
@@ -44,6 +46,9 @@ const SHARD_SIZE: usize = 128;
 // TODO: Make a single table (e.g., called `variables`) storing all our lonely variables in one place.
 const LAST_CONSENSUS_INDEX_ADDR: u64 = 0;
 
+/// The key where the last checkpoint whose superseded object versions were pruned is stored.
+const OBJECTS_PRUNED_CHECKPOINT_ADDR: u64 = 0;
+
 /// ALL_OBJ_VER determines whether we want to store all past
 /// versions of every object in the store. Authority doesn't store
 /// them, but other entities such as replicas will.
@@ -106,6 +111,33 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         }
     }
 
+    /// Creates RocksDB checkpoints of the databases of the store in the directory `path`, with
+    /// the same layout as the store directory. The databases are checkpointed one after another,
+    /// so they are only consistent with each other if no certificate is executed meanwhile (see
+    /// `AuthorityState::pause_execution`).
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        std::fs::create_dir_all(path).map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+        self.wal.checkpoint_db(&path.join("recovery_log"))?;
+        checkpoint_db(
+            &self.epoch_tables.transactions,
+            &AuthorityEpochTables::<S>::path(path),
+        )?;
+        checkpoint_db(
+            &self.perpetual_tables.objects,
+            &AuthorityPerpetualTables::<S>::path(path),
+        )?;
+        self.lock_service.checkpoint_db(&path.join("lockdb"))
+    }
+
+    /// Returns the statistics of the tables of the databases of the store.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        let mut stats = db_stats(&self.perpetual_tables.objects)?;
+        stats.extend(db_stats(&self.epoch_tables.transactions)?);
+        stats.extend(self.lock_service.db_stats()?);
+        stats.extend(self.wal.db_stats()?);
+        Ok(stats)
+    }
+
     pub async fn acquire_tx_guard(&self, cert: &CertifiedTransaction) -> SuiResult<CertTxGuard> {
         let digest = cert.digest();
         let guard = self.wal.begin_tx(digest, cert).await?;
@@ -147,6 +179,20 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             })
     }
 
+    /// Get the effects of many transactions, None for the transactions with no effects.
+    pub fn multi_get_effects(
+        &self,
+        transaction_digests: &[TransactionDigest],
+    ) -> SuiResult<Vec<Option<TransactionEffects>>> {
+        Ok(self
+            .perpetual_tables
+            .effects
+            .multi_get(transaction_digests)?
+            .into_iter()
+            .map(|data| data.map(|data| data.effects))
+            .collect())
+    }
+
     /// Returns true if we have an effects structure for this transaction digest
     pub fn effects_exists(&self, transaction_digest: &TransactionDigest) -> SuiResult<bool> {
         self.perpetual_tables
@@ -252,15 +298,25 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
     // Methods to read the store
     pub fn get_owner_objects(&self, owner: Owner) -> Result<Vec<ObjectInfo>, SuiError> {
         debug!(?owner, "get_owner_objects");
+        // The object id 0 is the smallest possible
+        Ok(self
+            .get_owner_objects_iterator(owner, ObjectID::ZERO)?
+            .collect())
+    }
+
+    /// Iterate over the objects owned by `owner` in object ID order, starting at `starting_object_id`.
+    pub fn get_owner_objects_iterator(
+        &self,
+        owner: Owner,
+        starting_object_id: ObjectID,
+    ) -> Result<impl Iterator<Item = ObjectInfo> + '_, SuiError> {
         Ok(self
             .perpetual_tables
             .owner_index
             .iter()
-            // The object id 0 is the smallest possible
-            .skip_to(&(owner, ObjectID::ZERO))?
-            .take_while(|((object_owner, _), _)| (object_owner == &owner))
-            .map(|(_, object_info)| object_info)
-            .collect())
+            .skip_to(&(owner, starting_object_id))?
+            .take_while(move |((object_owner, _), _)| (object_owner == &owner))
+            .map(|(_, object_info)| object_info))
     }
 
     pub fn get_object_by_key(
@@ -274,6 +330,26 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
             .get(&ObjectKey(*object_id, version))?)
     }
 
+    /// Returns the highest version of an object up to which its superseded versions have been
+    /// pruned, if any were.
+    pub fn get_pruned_object_version(
+        &self,
+        object_id: &ObjectID,
+    ) -> Result<Option<SequenceNumber>, SuiError> {
+        Ok(self
+            .perpetual_tables
+            .pruned_object_versions
+            .get(object_id)?)
+    }
+
+    /// Get many objects by their (id, version number) key, None for the keys not found.
+    pub fn multi_get_object_by_key(
+        &self,
+        object_keys: &[ObjectKey],
+    ) -> Result<Vec<Option<Object>>, SuiError> {
+        Ok(self.perpetual_tables.objects.multi_get(object_keys)?)
+    }
+
     /// Read an object and return it, or Err(ObjectNotFound) if the object was not found.
     pub fn get_object(&self, object_id: &ObjectID) -> Result<Option<Object>, SuiError> {
         let obj_entry = self
@@ -1062,6 +1138,29 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         }))
     }
 
+    /// Batched version of `get_latest_parent_entry`, returning one entry per object ID.
+    /// The parent_sync table is keyed by full object references, so the latest entries cannot be
+    /// read with a `multi_get`: instead one iterator seeks to each object ID, in key order.
+    pub fn multi_get_latest_parent_entries(
+        &self,
+        object_ids: &[ObjectID],
+    ) -> Result<Vec<Option<(ObjectRef, TransactionDigest)>>, SuiError> {
+        let mut order: Vec<usize> = (0..object_ids.len()).collect();
+        order.sort_by_key(|i| object_ids[*i]);
+
+        let mut entries = vec![None; object_ids.len()];
+        let mut iterator = self.perpetual_tables.parent_sync.iter();
+        for i in order {
+            let object_id = object_ids[i];
+            iterator =
+                iterator.skip_prior_to(&(object_id, SequenceNumber::MAX, ObjectDigest::MAX))?;
+            entries[i] = iterator
+                .next()
+                .filter(|(obj_ref, _)| obj_ref.0 == object_id);
+        }
+        Ok(entries)
+    }
+
     /// Remove the shared objects locks.
     pub fn remove_shared_objects_locks(
         &self,
@@ -1332,6 +1431,162 @@ impl<S: Eq + Debug + Serialize + for<'de> Deserialize<'de>> SuiDataStore<S> {
         Ok((batches, transactions))
     }
 
+    /// Returns the last checkpoint whose transactions have had the object versions they
+    /// superseded pruned.
+    pub fn objects_pruned_checkpoint(&self) -> SuiResult<Option<CheckpointSequenceNumber>> {
+        Ok(self
+            .perpetual_tables
+            .objects_pruned_checkpoint
+            .get(&OBJECTS_PRUNED_CHECKPOINT_ADDR)?)
+    }
+
+    /// Deletes the object versions that the transactions of `checkpoint` took as input and
+    /// superseded, and records `checkpoint` as pruned, atomically. Returns the number of deleted
+    /// versions, or None without deleting anything if a transaction of the checkpoint has not
+    /// been executed by this authority yet.
+    ///
+    /// Only inputs of transactions whose effects have been written are deleted, as the inputs of
+    /// partially executed transactions are needed to retry them. Checkpoints must be pruned in
+    /// sequence, so that the versions of an object are pruned in increasing order: pruning any
+    /// checkpoint but the one following the last pruned checkpoint fails.
+    pub fn prune_objects_of_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+        transactions: &[ExecutionDigests],
+    ) -> SuiResult<Option<usize>> {
+        self.check_next_objects_pruned_checkpoint(checkpoint)?;
+        let digests: Vec<_> = transactions.iter().map(|d| d.transaction).collect();
+        let effects = self.perpetual_tables.effects.multi_get(&digests)?;
+        let certificates = self.perpetual_tables.certificates.multi_get(&digests)?;
+
+        let mut pruned_keys = Vec::new();
+        let mut pruned_versions: BTreeMap<ObjectID, SequenceNumber> = BTreeMap::new();
+        for (effects, certificate) in effects.into_iter().zip(certificates) {
+            let (effects, certificate) = match (effects, certificate) {
+                (Some(effects), Some(certificate)) => (effects, certificate),
+                _ => return Ok(None),
+            };
+            for key in superseded_object_versions(&certificate, &effects.effects)? {
+                let version = pruned_versions.entry(key.0).or_default();
+                *version = (*version).max(key.1);
+                pruned_keys.push(key);
+            }
+        }
+
+        let mut write_batch = self.perpetual_tables.objects.batch();
+        write_batch =
+            write_batch.delete_batch(&self.perpetual_tables.objects, pruned_keys.iter())?;
+        write_batch = write_batch.insert_batch(
+            &self.perpetual_tables.pruned_object_versions,
+            pruned_versions,
+        )?;
+        write_batch = write_batch.insert_batch(
+            &self.perpetual_tables.objects_pruned_checkpoint,
+            iter::once((OBJECTS_PRUNED_CHECKPOINT_ADDR, checkpoint)),
+        )?;
+        write_batch.write()?;
+        Ok(Some(pruned_keys.len()))
+    }
+
+    /// Records the checkpoints from the one following the last pruned checkpoint up to
+    /// `checkpoint` as pruned, without deleting anything. Used to move past checkpoints whose
+    /// contents are not stored locally, such as those before the checkpoint a node synced from.
+    pub fn skip_objects_pruned_checkpoints(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult {
+        let last_pruned = self.objects_pruned_checkpoint()?;
+        fp_ensure!(
+            last_pruned.map_or(true, |last_pruned| last_pruned < checkpoint),
+            SuiError::GenericStorageError(format!(
+                "Cannot skip object pruning to checkpoint {checkpoint}, \
+                 checkpoint {last_pruned:?} is already pruned"
+            ))
+        );
+        self.perpetual_tables
+            .objects_pruned_checkpoint
+            .insert(&OBJECTS_PRUNED_CHECKPOINT_ADDR, &checkpoint)?;
+        Ok(())
+    }
+
+    fn check_next_objects_pruned_checkpoint(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult {
+        let next_checkpoint = self
+            .objects_pruned_checkpoint()?
+            .map_or(0, |last_pruned| last_pruned + 1);
+        fp_ensure!(
+            checkpoint == next_checkpoint,
+            SuiError::GenericStorageError(format!(
+                "Objects of checkpoint {checkpoint} pruned out of sequence, \
+                 expected checkpoint {next_checkpoint}"
+            ))
+        );
+        Ok(())
+    }
+
+    /// Iterate over the references of the latest versions of the objects that are neither deleted
+    /// nor wrapped, in object ID order.
+    pub fn iter_live_object_set(&self) -> impl Iterator<Item = ObjectRef> + '_ {
+        live_object_refs(self.perpetual_tables.parent_sync.keys())
+    }
+
+    /// Like [`Self::iter_live_object_set`], starting at the object ID `start`.
+    pub fn iter_live_object_set_from(
+        &self,
+        start: ObjectID,
+    ) -> SuiResult<impl Iterator<Item = ObjectRef> + '_> {
+        let parents = self
+            .perpetual_tables
+            .parent_sync
+            .iter()
+            .skip_to(&(start, SequenceNumber::MIN, ObjectDigest::MIN))?
+            .map(|(object_ref, _)| object_ref);
+        Ok(live_object_refs(parents))
+    }
+
+    /// Returns the version that every object written by one of the executed `transactions` had
+    /// before any of them was executed, or None for the objects that were not live then, i.e.
+    /// that they created, or unwrapped before using.
+    pub fn object_versions_before_transactions(
+        &self,
+        transactions: &[TransactionDigest],
+    ) -> SuiResult<BTreeMap<ObjectID, Option<SequenceNumber>>> {
+        let effects = self.perpetual_tables.effects.multi_get(transactions)?;
+        let certificates = self.perpetual_tables.certificates.multi_get(transactions)?;
+
+        // The lowest version of every object used or produced, since object versions increase
+        // with every transaction writing the object.
+        let mut inputs: BTreeMap<ObjectID, SequenceNumber> = BTreeMap::new();
+        let mut outputs: BTreeMap<ObjectID, SequenceNumber> = BTreeMap::new();
+        for ((digest, effects), certificate) in transactions.iter().zip(effects).zip(certificates) {
+            let (effects, certificate) = match (effects, certificate) {
+                (Some(effects), Some(certificate)) => (effects.effects, certificate),
+                _ => return Err(SuiError::TransactionNotFound { digest: *digest }),
+            };
+            for ObjectKey(object_id, version) in superseded_object_versions(&certificate, &effects)?
+            {
+                let lowest = inputs.entry(object_id).or_insert(version);
+                *lowest = (*lowest).min(version);
+            }
+            for ((object_id, version, _), _) in effects.created.iter().chain(&effects.unwrapped) {
+                let lowest = outputs.entry(*object_id).or_insert(*version);
+                *lowest = (*lowest).min(*version);
+            }
+        }
+
+        let mut versions: BTreeMap<_, _> = outputs.keys().map(|id| (*id, None)).collect();
+        for (object_id, input_version) in inputs {
+            let version = match outputs.get(&object_id) {
+                Some(output_version) if *output_version <= input_version => None,
+                _ => Some(input_version),
+            };
+            versions.insert(object_id, version);
+        }
+        Ok(versions)
+    }
+
     /// Return the latest consensus index. It is used to bootstrap the consensus client.
     pub fn last_consensus_index(&self) -> SuiResult<ExecutionIndicesWithHash> {
         self.epoch_tables
@@ -1475,6 +1730,51 @@ impl<T: ModuleResolver> ModuleResolver for ResolverWrapper<T> {
     }
 }
 
+/// Returns the keys of the input objects of a transaction that its execution replaced with a new
+/// version, deleted or wrapped. Immutable inputs are not superseded.
+fn superseded_object_versions(
+    certificate: &CertifiedTransaction,
+    effects: &TransactionEffects,
+) -> SuiResult<Vec<ObjectKey>> {
+    let modified: HashSet<ObjectID> = effects
+        .mutated
+        .iter()
+        .map(|(object_ref, _)| object_ref.0)
+        .chain(effects.deleted.iter().map(|object_ref| object_ref.0))
+        .chain(effects.wrapped.iter().map(|object_ref| object_ref.0))
+        .collect();
+    // Shared objects are referenced by ID in the transaction, their input versions are the
+    // ones recorded in the effects.
+    let owned_inputs = certificate
+        .signed_data
+        .data
+        .input_objects()?
+        .into_iter()
+        .filter_map(|kind| match kind {
+            InputObjectKind::ImmOrOwnedMoveObject(object_ref) => Some(object_ref),
+            _ => None,
+        });
+    Ok(owned_inputs
+        .chain(effects.shared_objects.iter().copied())
+        .filter(|object_ref| modified.contains(&object_ref.0))
+        .map(ObjectKey::from)
+        .collect())
+}
+
+/// The latest versions of the objects that are neither deleted nor wrapped among the `parents`
+/// keys of `parent_sync`, which are ordered by object ID and version.
+fn live_object_refs(parents: impl Iterator<Item = ObjectRef>) -> impl Iterator<Item = ObjectRef> {
+    let mut parents = parents.peekable();
+    iter::from_fn(move || loop {
+        let object_ref = parents.next()?;
+        match parents.peek() {
+            Some(next_ref) if next_ref.0 == object_ref.0 => continue,
+            _ if object_ref.2.is_alive() => return Some(object_ref),
+            _ => continue,
+        }
+    })
+}
+
 // The primary key type for object storage.
 #[serde_as]
 #[derive(Eq, PartialEq, Clone, Copy, PartialOrd, Ord, Hash, Serialize, Deserialize, Debug)]
@@ -80,8 +80,8 @@ pub struct AuthorityPerpetualTables<S> {
     /// This is a map between the object (ID, version) and the latest state of the object, namely the
     /// state that is needed to process new transactions.
     ///
-    /// Note that while this map can store all versions of an object, we will eventually
-    /// prune old object versions from the db.
+    /// Note that while this map can store all versions of an object, old object versions are
+    /// pruned from the db by the `ObjectPruner` when object pruning is configured.
     ///
     /// IMPORTANT: object versions must *only* be pruned if they appear as inputs in some
     /// TransactionEffects. Simply pruning all objects but the most recent is an error!
@@ -91,6 +91,11 @@ pub struct AuthorityPerpetualTables<S> {
     #[default_options_override_fn = "objects_table_default_config"]
     pub(crate) objects: DBMap<ObjectKey, Object>,
 
+    /// The highest version of each object up to which its superseded versions have been pruned
+    /// from `objects`. Versions of an object are pruned in increasing order, so a version at or
+    /// below this one that is missing from `objects` was pruned.
+    pub(crate) pruned_object_versions: DBMap<ObjectID, SequenceNumber>,
+
     /// This is a an index of object references to currently existing objects, indexed by the
     /// composite key of the SuiAddress of their owner and the object ID of the object.
     /// This composite index allows an efficient iterator to list all objected currently owned
@@ -137,6 +142,10 @@ pub struct AuthorityPerpetualTables<S> {
 
     /// A sequence of batches indexing into the sequence of executed transactions.
     pub batches: DBMap<TxSequenceNumber, SignedBatch>,
+
+    /// A single entry table holding the last checkpoint whose transactions have had the object
+    /// versions they superseded pruned from `objects`.
+    pub(crate) objects_pruned_checkpoint: DBMap<u64, CheckpointSequenceNumber>,
 }
 
 impl<S> AuthorityPerpetualTables<S>
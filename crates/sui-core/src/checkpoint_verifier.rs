@@ -0,0 +1,289 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline verification that the checkpoints stored by a node form a valid chain, certified by
+//! the committee of every epoch, to audit archived data without trusting the node it comes from.
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use sui_types::base_types::AuthorityName;
+use sui_types::committee::{Committee, EpochId, StakeUnit};
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CheckpointContentsDigest, CheckpointDigest, CheckpointSequenceNumber,
+};
+use typed_store::Map;
+
+use crate::checkpoints::CheckpointStoreTables;
+use crate::epoch::committee_store::CommitteeStore;
+
+#[cfg(test)]
+#[path = "unit_tests/checkpoint_verifier_tests.rs"]
+mod checkpoint_verifier_tests;
+
+/// Where the verification of a checkpoint chain starts from.
+#[derive(Clone, Debug)]
+pub enum ChainRoot {
+    /// From the first checkpoint, with the committee of the genesis as the only trusted data.
+    Genesis(Committee),
+    /// From a checkpoint whose digest is known, e.g. from a previous audit. The committee of its
+    /// epoch is read from the committee store and trusted.
+    Trusted {
+        sequence_number: CheckpointSequenceNumber,
+        digest: CheckpointDigest,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CheckpointChainFailure {
+    /// No checkpoint with this sequence number, though the chain goes on after it or it is the
+    /// trusted checkpoint.
+    CheckpointMissing {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    /// The digest of the trusted checkpoint is not the expected one.
+    TrustedDigestMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: CheckpointDigest,
+        found: CheckpointDigest,
+    },
+    /// The committee store has no committee for the epoch of a checkpoint.
+    CommitteeMissing { epoch: EpochId },
+    /// The committee stored for an epoch is not the one of the genesis, or the one announced by
+    /// the last checkpoint of the previous epoch.
+    CommitteeMismatch {
+        epoch: EpochId,
+        expected: Vec<(AuthorityName, StakeUnit)>,
+        stored: Vec<(AuthorityName, StakeUnit)>,
+    },
+    /// A checkpoint of another epoch than the one of the previous checkpoint, or the next one if
+    /// the previous checkpoint ended its epoch.
+    EpochMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: EpochId,
+        found: EpochId,
+    },
+    /// A checkpoint whose signatures are not valid signatures of a quorum of the committee of its
+    /// epoch.
+    InvalidSignatures {
+        sequence_number: CheckpointSequenceNumber,
+        error: String,
+    },
+    /// A checkpoint only signed by the node, followed by other checkpoints.
+    NotCertified {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    /// A checkpoint that does not point at the digest of the previous checkpoint.
+    PreviousDigestMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: Option<CheckpointDigest>,
+        found: Option<CheckpointDigest>,
+    },
+    /// A checkpoint without stored contents.
+    ContentsMissing {
+        sequence_number: CheckpointSequenceNumber,
+    },
+    /// A checkpoint whose stored contents do not have the digest of its summary.
+    ContentsDigestMismatch {
+        sequence_number: CheckpointSequenceNumber,
+        expected: CheckpointContentsDigest,
+        found: CheckpointContentsDigest,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedCheckpoint {
+    pub sequence_number: CheckpointSequenceNumber,
+    pub epoch: EpochId,
+    /// The digest of the summary, to resume the verification from this checkpoint later.
+    pub digest: CheckpointDigest,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointChainReport {
+    /// The number of checkpoints verified, up to the first failure.
+    pub verified_checkpoints: u64,
+    pub last_verified: Option<VerifiedCheckpoint>,
+    /// The last stored checkpoint, if only signed by the node and valid otherwise.
+    pub uncertified_tip: Option<CheckpointSequenceNumber>,
+    /// The first failure, where the verification stopped.
+    pub failure: Option<CheckpointChainFailure>,
+}
+
+impl CheckpointChainReport {
+    pub fn is_valid(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Walks the checkpoints from `root` to the last stored checkpoint, and verifies that:
+/// - every checkpoint is certified by a quorum of the committee of its epoch, the committee of
+///   every epoch being the one announced by the last checkpoint of the previous epoch,
+/// - every checkpoint points at the digest of the previous one,
+/// - the stored contents of every checkpoint have the contents digest of its summary.
+///
+/// The last checkpoint can be only signed by the node, if it was not certified yet.
+pub fn verify_checkpoint_chain(
+    checkpoints: &CheckpointStoreTables,
+    committees: &CommitteeStore,
+    root: &ChainRoot,
+) -> SuiResult<CheckpointChainReport> {
+    let mut report = CheckpointChainReport::default();
+    report.failure = walk_checkpoint_chain(checkpoints, committees, root, &mut report)?;
+    Ok(report)
+}
+
+fn walk_checkpoint_chain(
+    checkpoints: &CheckpointStoreTables,
+    committees: &CommitteeStore,
+    root: &ChainRoot,
+    report: &mut CheckpointChainReport,
+) -> SuiResult<Option<CheckpointChainFailure>> {
+    let (start, mut expected_epoch, mut expected_previous, mut announced_committee) = match root {
+        ChainRoot::Genesis(committee) => (
+            0,
+            Some(committee.epoch),
+            Some(None),
+            Some(committee.voting_rights.clone()),
+        ),
+        ChainRoot::Trusted {
+            sequence_number, ..
+        } => (*sequence_number, None, None, None),
+    };
+    let mut committee: Option<Committee> = None;
+
+    let mut iter = checkpoints.checkpoints.iter().skip_to(&start)?.peekable();
+    let mut next_sequence_number = start;
+    while let Some((sequence_number, checkpoint)) = iter.next() {
+        if sequence_number != next_sequence_number {
+            return Ok(Some(CheckpointChainFailure::CheckpointMissing {
+                sequence_number: next_sequence_number,
+            }));
+        }
+        let summary = checkpoint.summary();
+        if sequence_number == start {
+            if let ChainRoot::Trusted { digest, .. } = root {
+                if summary.digest() != *digest {
+                    return Ok(Some(CheckpointChainFailure::TrustedDigestMismatch {
+                        sequence_number,
+                        expected: *digest,
+                        found: summary.digest(),
+                    }));
+                }
+            }
+        }
+        if let Some(expected) = expected_epoch {
+            if summary.epoch != expected {
+                return Ok(Some(CheckpointChainFailure::EpochMismatch {
+                    sequence_number,
+                    expected,
+                    found: summary.epoch,
+                }));
+            }
+        }
+
+        if committee.as_ref().map(|c| c.epoch) != Some(summary.epoch) {
+            let stored = match committees.get_committee(&summary.epoch)? {
+                Some(stored) => stored,
+                None => {
+                    return Ok(Some(CheckpointChainFailure::CommitteeMissing {
+                        epoch: summary.epoch,
+                    }))
+                }
+            };
+            if let Some(expected) = announced_committee.take() {
+                if stored.voting_rights != expected {
+                    return Ok(Some(CheckpointChainFailure::CommitteeMismatch {
+                        epoch: summary.epoch,
+                        expected,
+                        stored: stored.voting_rights,
+                    }));
+                }
+            }
+            info!(epoch = summary.epoch, "Verifying checkpoints");
+            committee = Some(stored);
+        }
+        let epoch_committee = committee.as_ref().unwrap();
+
+        let is_tip = iter.peek().is_none();
+        let signatures = match &checkpoint {
+            AuthenticatedCheckpoint::Certified(certified) => {
+                certified.verify(epoch_committee, None)
+            }
+            AuthenticatedCheckpoint::Signed(signed) if is_tip => {
+                signed.verify(epoch_committee, None)
+            }
+            AuthenticatedCheckpoint::Signed(_) => {
+                return Ok(Some(CheckpointChainFailure::NotCertified {
+                    sequence_number,
+                }))
+            }
+        };
+        if let Err(e) = signatures {
+            return Ok(Some(CheckpointChainFailure::InvalidSignatures {
+                sequence_number,
+                error: e.to_string(),
+            }));
+        }
+
+        if let Some(expected) = expected_previous {
+            if summary.previous_digest != expected {
+                return Ok(Some(CheckpointChainFailure::PreviousDigestMismatch {
+                    sequence_number,
+                    expected,
+                    found: summary.previous_digest,
+                }));
+            }
+        }
+
+        match checkpoints.checkpoint_contents.get(&sequence_number)? {
+            None => {
+                return Ok(Some(CheckpointChainFailure::ContentsMissing {
+                    sequence_number,
+                }))
+            }
+            Some(contents) if contents.digest() != summary.content_digest => {
+                return Ok(Some(CheckpointChainFailure::ContentsDigestMismatch {
+                    sequence_number,
+                    expected: summary.content_digest,
+                    found: contents.digest(),
+                }))
+            }
+            Some(_) => (),
+        }
+
+        if matches!(checkpoint, AuthenticatedCheckpoint::Signed(_)) {
+            report.uncertified_tip = Some(sequence_number);
+            break;
+        }
+        report.verified_checkpoints += 1;
+        report.last_verified = Some(VerifiedCheckpoint {
+            sequence_number,
+            epoch: summary.epoch,
+            digest: summary.digest(),
+        });
+        expected_previous = Some(Some(summary.digest()));
+        expected_epoch = match &summary.next_epoch_committee {
+            Some(next_committee) => {
+                announced_committee = Some(next_committee.clone());
+                Some(summary.epoch + 1)
+            }
+            None => Some(summary.epoch),
+        };
+        next_sequence_number += 1;
+    }
+
+    if report.verified_checkpoints == 0 && report.uncertified_tip.is_none() {
+        if let ChainRoot::Trusted {
+            sequence_number, ..
+        } = root
+        {
+            return Ok(Some(CheckpointChainFailure::CheckpointMissing {
+                sequence_number: *sequence_number,
+            }));
+        }
+    }
+    Ok(None)
+}
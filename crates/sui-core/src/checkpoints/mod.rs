@@ -13,7 +13,8 @@ use rocksdb::Options;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 use std::{path::Path, sync::Arc};
-use sui_storage::default_db_options;
+use sui_storage::db_stats::{db_stats, ColumnFamilyStats};
+use sui_storage::{checkpoint_db, default_db_options};
 use sui_types::messages_checkpoint::{CheckpointProposal, CheckpointProposalContents};
 use sui_types::{
     base_types::{AuthorityName, ExecutionDigests},
@@ -184,6 +185,27 @@ impl CheckpointStore {
         Ok(self.tables.checkpoints.get(&seq)?)
     }
 
+    pub fn get_checkpoint_contents(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointContents>, SuiError> {
+        Ok(self.tables.checkpoint_contents.get(&seq)?)
+    }
+
+    /// Returns the lowest checkpoint after `seq` whose contents are stored.
+    pub fn next_checkpoint_with_contents(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> Result<Option<CheckpointSequenceNumber>, SuiError> {
+        Ok(self
+            .tables
+            .checkpoint_contents
+            .iter()
+            .skip_to(&(seq + 1))?
+            .next()
+            .map(|(seq, _)| seq))
+    }
+
     fn get_prev_checkpoint_digest(
         &mut self,
         checkpoint_sequence: CheckpointSequenceNumber,
@@ -308,6 +330,16 @@ impl CheckpointStore {
         })
     }
 
+    /// Creates a RocksDB checkpoint of the checkpoint store in the directory `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        checkpoint_db(&self.tables.checkpoints, path)
+    }
+
+    /// Returns the statistics of the tables of the checkpoint store.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        db_stats(&self.tables.checkpoints)
+    }
+
     // Define handlers for request
 
     pub fn handle_proposal(&mut self, detail: bool) -> Result<CheckpointResponse, SuiError> {
@@ -835,6 +867,22 @@ impl CheckpointStore {
         Ok(())
     }
 
+    /// Stores the checkpoint certificate that a node bootstrapped from a state snapshot starts
+    /// at, as the first checkpoint of an empty store. Earlier checkpoints are never stored.
+    pub fn bootstrap_from_snapshot_checkpoint(
+        &mut self,
+        checkpoint: &CertifiedCheckpointSummary,
+        contents: &CheckpointContents,
+        committee: &Committee,
+    ) -> SuiResult {
+        fp_ensure!(
+            self.latest_stored_checkpoint().is_none(),
+            SuiError::from("Cannot bootstrap a checkpoint store that is not empty")
+        );
+        self.clear_proposal(*checkpoint.summary.sequence_number())?;
+        self.process_synced_checkpoint_certificate(checkpoint, contents, committee)
+    }
+
     fn notify_new_checkpoint(&self, ckpt: CertifiedCheckpointSummary) {
         let sequence = ckpt.summary.sequence_number;
         let _ = self.notify_new_checkpoint_tx.send(ckpt).tap_err(|_| {
@@ -888,8 +936,7 @@ impl CheckpointStore {
             .iter()
             .skip_to_last()
             .reverse()
-            .take_while(|(_, ckp)| !matches!(ckp, AuthenticatedCheckpoint::Certified(_)))
-            .next()
+            .find(|(_, ckp)| matches!(ckp, AuthenticatedCheckpoint::Certified(_)))
             .map(|(_, ckp)| ckp)
     }
 
@@ -731,6 +731,60 @@ fn set_get_checkpoint() {
     ));
 }
 
+#[test]
+fn latest_certified_checkpoint_skips_newer_signed() {
+    let mut rng = StdRng::from_seed(RNG_SEED);
+    let (keys, committee) = make_committee_key(&mut rng);
+    let k = keys[0].copy();
+
+    let path = env::temp_dir().join(format!("SC_{:?}", ObjectID::random()));
+    fs::create_dir(&path).unwrap();
+    let mut cps = CheckpointStore::open(
+        &path,
+        None,
+        &committee,
+        k.public().into(),
+        Arc::pin(k.copy()),
+        false,
+    )
+    .unwrap();
+
+    let batch: Vec<_> = (0..4)
+        .map(|i| (i as u64, ExecutionDigests::random()))
+        .collect();
+    cps.handle_internal_batch(0, &batch).unwrap();
+
+    // Nothing is certified while the only checkpoint is signed.
+    let first: Vec<_> = batch[..2].iter().map(|(_, d)| *d).collect();
+    cps.sign_new_checkpoint(committee.epoch, 0, first.iter(), TestCausalOrderNoop, None)
+        .unwrap();
+    assert!(cps.latest_certified_checkpoint().is_none());
+
+    let checkpoint = cps.latest_stored_checkpoint().unwrap().summary().clone();
+    let signatures: Vec<_> = keys
+        .iter()
+        .map(|key| {
+            SignedCheckpointSummary::new_from_summary(checkpoint.clone(), key.public().into(), key)
+        })
+        .collect();
+    let cert = CertifiedCheckpointSummary::aggregate(signatures, &committee).unwrap();
+    cps.promote_signed_checkpoint_to_cert(&cert, &committee)
+        .unwrap();
+
+    // A newer checkpoint that is only signed must not hide the certified one before it.
+    let second: Vec<_> = batch[2..].iter().map(|(_, d)| *d).collect();
+    cps.sign_new_checkpoint(committee.epoch, 1, second.iter(), TestCausalOrderNoop, None)
+        .unwrap();
+    assert!(matches!(
+        cps.latest_stored_checkpoint(),
+        Some(AuthenticatedCheckpoint::Signed(s)) if *s.summary.sequence_number() == 1
+    ));
+    assert!(matches!(
+        cps.latest_certified_checkpoint(),
+        Some(AuthenticatedCheckpoint::Certified(c)) if *c.summary.sequence_number() == 0
+    ));
+}
+
 #[test]
 fn checkpoint_integration() {
     telemetry_subscribers::init_for_testing();
@@ -0,0 +1,130 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Online backups of the databases of a running node.
+//!
+//! A backup is a directory of RocksDB checkpoints of the authority, committee, checkpoint and
+//! node sync stores, laid out like the db path of the node, so that it can be restored by
+//! copying it in place of the db path of a stopped node (see `sui-tool restore-db-backup`).
+//! Table files are hard-linked when the backup is on the same filesystem as the databases, so
+//! creating a backup is cheap.
+//!
+//! The execution of certificates is paused while the databases are checkpointed, and the
+//! checkpoint store is locked, so that the stores of a backup are consistent with each other:
+//! every transaction of a backup is fully committed, and its recovery log is empty. Transactions
+//! and checkpoints keep being received meanwhile, and are executed once the backup is created.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::fp_ensure;
+use tokio::sync::Mutex;
+use tracing::info;
+
+use crate::authority::AuthorityState;
+
+/// The directories of the stores in the db path of a node, and in a backup.
+pub const STORE_DIR: &str = "store";
+pub const EPOCHS_DIR: &str = "epochs";
+pub const CHECKPOINTS_DIR: &str = "checkpoints";
+pub const NODE_SYNC_DIR: &str = "node_sync_db";
+
+/// A backup created by [`DbBackups::create`].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbBackup {
+    pub path: PathBuf,
+    /// The total size of the files of the backup. Hard-linked files are counted in full,
+    /// although they share their space with the databases until those compact them away.
+    pub size_bytes: u64,
+}
+
+/// Creates backups of the databases of a node, one at a time.
+#[derive(Clone)]
+pub struct DbBackups {
+    state: Arc<AuthorityState>,
+    backups_dir: PathBuf,
+    lock: Arc<Mutex<()>>,
+}
+
+impl DbBackups {
+    pub fn new(state: Arc<AuthorityState>, backups_dir: PathBuf) -> Self {
+        Self {
+            state,
+            backups_dir,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Creates a backup in `path`, which must not exist yet, or by default in a new
+    /// directory of the backups directory named after the current time.
+    pub async fn create(&self, path: Option<PathBuf>) -> SuiResult<DbBackup> {
+        let _guard = self.lock.lock().await;
+        let _execution_guard = self.state.pause_execution().await;
+        let path = path.unwrap_or_else(|| {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .expect("Time went backwards")
+                .as_millis();
+            self.backups_dir.join(format!("backup-{now}"))
+        });
+        let state = self.state.clone();
+        tokio::task::spawn_blocking(move || create_db_backup(&state, &path))
+            .await
+            .expect("Creating a backup panicked")
+    }
+}
+
+/// Creates a backup of the databases of `state` in `path`, which must not exist yet. The caller
+/// must pause the execution of certificates for the backup to be consistent.
+pub fn create_db_backup(state: &AuthorityState, path: &Path) -> SuiResult<DbBackup> {
+    fp_ensure!(
+        !path.exists(),
+        SuiError::GenericStorageError(format!("{} already exists", path.display()))
+    );
+    info!(?path, "Creating a backup of the databases");
+    std::fs::create_dir_all(path).map_err(io_error)?;
+
+    let checkpoints = state.checkpoints();
+    let checkpoints = checkpoints.lock();
+    state.db().checkpoint_db(&path.join(STORE_DIR))?;
+    state
+        .committee_store()
+        .checkpoint_db(&path.join(EPOCHS_DIR))?;
+    checkpoints.checkpoint_db(&path.join(CHECKPOINTS_DIR))?;
+    state
+        .node_sync_store
+        .checkpoint_db(&path.join(NODE_SYNC_DIR))?;
+    drop(checkpoints);
+
+    let size_bytes = dir_size(path).map_err(io_error)?;
+    info!(?path, size_bytes, "Created a backup of the databases");
+    Ok(DbBackup {
+        path: path.to_path_buf(),
+        size_bytes,
+    })
+}
+
+fn dir_size(path: &Path) -> std::io::Result<u64> {
+    let mut size = 0;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        size += if metadata.is_dir() {
+            dir_size(&entry.path())?
+        } else {
+            metadata.len()
+        };
+    }
+    Ok(size)
+}
+
+fn io_error(e: std::io::Error) -> SuiError {
+    SuiError::GenericStorageError(e.to_string())
+}
+
+#[cfg(test)]
+#[path = "unit_tests/db_backup_tests.rs"]
+mod db_backup_tests;
@@ -0,0 +1,314 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline consistency checks of the authority and checkpoint stores of a node, to diagnose
+//! corruption, e.g. after a crash, instead of wiping the database.
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use sui_types::base_types::{
+    ExecutionDigests, ObjectID, ObjectRef, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::SuiResult;
+use sui_types::messages::TransactionEffects;
+use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSequenceNumber};
+use sui_types::object::Owner;
+use typed_store::Map;
+
+use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStoreTables;
+
+#[cfg(test)]
+#[path = "unit_tests/db_checker_tests.rs"]
+mod db_checker_tests;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DbInconsistency {
+    /// An `owner_index` entry for an object that is deleted, wrapped or unknown in `parent_sync`.
+    OwnerIndexObjectNotLive { owner: Owner, object_id: ObjectID },
+    /// An `owner_index` entry for another version than the latest version of the object.
+    OwnerIndexVersionNotLatest {
+        owner: Owner,
+        indexed: ObjectRef,
+        latest: ObjectRef,
+    },
+    /// An `owner_index` entry for an object version missing from `objects`.
+    OwnerIndexObjectMissing { owner: Owner, object_ref: ObjectRef },
+    /// An `owner_index` entry whose owner is not the owner of the object.
+    OwnerIndexOwnerMismatch {
+        owner: Owner,
+        object_ref: ObjectRef,
+        object_owner: Owner,
+    },
+    /// An `executed_sequence` entry for a transaction without effects.
+    ExecutedTransactionEffectsMissing {
+        seq: TxSequenceNumber,
+        digests: ExecutionDigests,
+    },
+    /// An `executed_sequence` entry whose effects digest is not the one of the stored effects.
+    ExecutedTransactionEffectsMismatch {
+        seq: TxSequenceNumber,
+        digests: ExecutionDigests,
+        stored: ExecutionDigests,
+    },
+    /// An `executed_sequence` entry for a transaction without certificate.
+    ExecutedTransactionCertificateMissing {
+        seq: TxSequenceNumber,
+        transaction: TransactionDigest,
+    },
+    /// An object version written by an executed transaction, whose `parent_sync` entry is
+    /// missing or points at another transaction.
+    ParentSyncEntryMismatch {
+        object_ref: ObjectRef,
+        transaction: TransactionDigest,
+        parent_sync: Option<TransactionDigest>,
+    },
+    /// A `parent_sync` entry pointing at a transaction without effects.
+    ParentSyncEffectsMissing {
+        object_ref: ObjectRef,
+        transaction: TransactionDigest,
+    },
+    /// A `parent_sync` entry pointing at a transaction whose effects do not write the object
+    /// version.
+    ParentSyncNotInEffects {
+        object_ref: ObjectRef,
+        transaction: TransactionDigest,
+    },
+    /// A transaction of the contents of an executed checkpoint without matching effects.
+    CheckpointTransactionUnknown {
+        checkpoint: CheckpointSequenceNumber,
+        digests: ExecutionDigests,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DbCheckReport {
+    pub owner_index_entries: u64,
+    pub executed_transactions: u64,
+    pub parent_sync_entries: u64,
+    /// The number of checkpoints checked, up to the highest executed checkpoint.
+    pub checkpoints: u64,
+    /// The highest checkpoint whose transactions all have effects. The checkpoints above it are
+    /// not checked, as a node which is still syncing has not executed them yet.
+    pub highest_executed_checkpoint: Option<CheckpointSequenceNumber>,
+    /// The number of inconsistencies found, which can be more than the ones reported.
+    pub inconsistency_count: u64,
+    pub inconsistencies: Vec<DbInconsistency>,
+}
+
+impl DbCheckReport {
+    pub fn is_consistent(&self) -> bool {
+        self.inconsistency_count == 0
+    }
+}
+
+struct Checker<'a> {
+    store: &'a AuthorityStore,
+    max_inconsistencies: usize,
+    report: DbCheckReport,
+}
+
+/// Cross-checks the tables of a stopped node, reporting up to `max_inconsistencies` of the
+/// inconsistencies found:
+/// - every `owner_index` entry is for the live latest version of an object, with its owner,
+/// - every executed transaction has a certificate and effects, and `parent_sync` entries for the
+///   object versions it wrote,
+/// - every `parent_sync` entry is for an object version written by the effects of its
+///   transaction,
+/// - every transaction of a checkpoint up to the highest executed checkpoint has effects.
+pub fn check_db(
+    store: &AuthorityStore,
+    checkpoints: &CheckpointStoreTables,
+    max_inconsistencies: usize,
+) -> SuiResult<DbCheckReport> {
+    let mut checker = Checker {
+        store,
+        max_inconsistencies,
+        report: DbCheckReport::default(),
+    };
+    checker.check_owner_index()?;
+    checker.check_executed_sequence()?;
+    checker.check_parent_sync()?;
+    checker.check_checkpoints(checkpoints)?;
+    Ok(checker.report)
+}
+
+impl<'a> Checker<'a> {
+    fn report(&mut self, inconsistency: DbInconsistency) {
+        self.report.inconsistency_count += 1;
+        if self.report.inconsistencies.len() < self.max_inconsistencies {
+            self.report.inconsistencies.push(inconsistency);
+        }
+    }
+
+    fn check_owner_index(&mut self) -> SuiResult {
+        info!("Checking owner_index");
+        for ((owner, object_id), info) in self.store.perpetual_tables.owner_index.iter() {
+            self.report.owner_index_entries += 1;
+            let indexed = (object_id, info.version, info.digest);
+            let latest = match self.store.get_latest_parent_entry(object_id)? {
+                Some((latest, _)) if latest.2.is_alive() => latest,
+                _ => {
+                    self.report(DbInconsistency::OwnerIndexObjectNotLive { owner, object_id });
+                    continue;
+                }
+            };
+            if latest != indexed {
+                self.report(DbInconsistency::OwnerIndexVersionNotLatest {
+                    owner,
+                    indexed,
+                    latest,
+                });
+                continue;
+            }
+            match self.store.get_object_by_key(&object_id, info.version)? {
+                None => self.report(DbInconsistency::OwnerIndexObjectMissing {
+                    owner,
+                    object_ref: indexed,
+                }),
+                Some(object) if object.owner != owner => {
+                    self.report(DbInconsistency::OwnerIndexOwnerMismatch {
+                        owner,
+                        object_ref: indexed,
+                        object_owner: object.owner,
+                    })
+                }
+                Some(_) => (),
+            }
+        }
+        Ok(())
+    }
+
+    fn check_executed_sequence(&mut self) -> SuiResult {
+        info!("Checking executed_sequence");
+        let tables = &self.store.perpetual_tables;
+        for (seq, digests) in tables.executed_sequence.iter() {
+            self.report.executed_transactions += 1;
+            if !tables.certificates.contains_key(&digests.transaction)? {
+                self.report(DbInconsistency::ExecutedTransactionCertificateMissing {
+                    seq,
+                    transaction: digests.transaction,
+                });
+            }
+            let effects = match tables.effects.get(&digests.transaction)? {
+                Some(effects) => effects.effects,
+                None => {
+                    self.report(DbInconsistency::ExecutedTransactionEffectsMissing {
+                        seq,
+                        digests,
+                    });
+                    continue;
+                }
+            };
+            let stored = ExecutionDigests::new(digests.transaction, effects.digest());
+            if stored != digests {
+                self.report(DbInconsistency::ExecutedTransactionEffectsMismatch {
+                    seq,
+                    digests,
+                    stored,
+                });
+            }
+            for object_ref in written_object_refs(&effects) {
+                let parent_sync = tables.parent_sync.get(&object_ref)?;
+                if parent_sync != Some(digests.transaction) {
+                    self.report(DbInconsistency::ParentSyncEntryMismatch {
+                        object_ref,
+                        transaction: digests.transaction,
+                        parent_sync,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_parent_sync(&mut self) -> SuiResult {
+        info!("Checking parent_sync");
+        for (object_ref, transaction) in self.store.perpetual_tables.parent_sync.iter() {
+            self.report.parent_sync_entries += 1;
+            // Genesis objects have no effects.
+            if transaction == TransactionDigest::genesis() {
+                continue;
+            }
+            match self.store.perpetual_tables.effects.get(&transaction)? {
+                None => self.report(DbInconsistency::ParentSyncEffectsMissing {
+                    object_ref,
+                    transaction,
+                }),
+                Some(effects) => {
+                    if !written_object_refs(&effects.effects).any(|r| r == object_ref) {
+                        self.report(DbInconsistency::ParentSyncNotInEffects {
+                            object_ref,
+                            transaction,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_checkpoints(&mut self, checkpoints: &CheckpointStoreTables) -> SuiResult {
+        info!("Checking checkpoint contents");
+        let mut highest_executed = None;
+        for (checkpoint, contents) in checkpoints
+            .checkpoint_contents
+            .iter()
+            .skip_to_last()
+            .reverse()
+        {
+            if self.effects_digests(&contents)?.iter().all(Option::is_some) {
+                highest_executed = Some(checkpoint);
+                break;
+            }
+        }
+        self.report.highest_executed_checkpoint = highest_executed;
+        let highest_executed = match highest_executed {
+            Some(highest_executed) => highest_executed,
+            None => return Ok(()),
+        };
+
+        for (checkpoint, contents) in checkpoints.checkpoint_contents.iter() {
+            if checkpoint > highest_executed {
+                break;
+            }
+            self.report.checkpoints += 1;
+            let effects_digests = self.effects_digests(&contents)?;
+            for (digests, effects_digest) in contents.iter().zip(effects_digests) {
+                if effects_digest != Some(digests.effects) {
+                    self.report(DbInconsistency::CheckpointTransactionUnknown {
+                        checkpoint,
+                        digests: *digests,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The digests of the stored effects of the transactions of `contents`.
+    fn effects_digests(
+        &self,
+        contents: &CheckpointContents,
+    ) -> SuiResult<Vec<Option<TransactionEffectsDigest>>> {
+        let transactions: Vec<_> = contents.iter().map(|d| d.transaction).collect();
+        Ok(self
+            .store
+            .multi_get_effects(&transactions)?
+            .into_iter()
+            .map(|effects| effects.map(|effects| effects.digest()))
+            .collect())
+    }
+}
+
+/// The keys of the `parent_sync` entries written for the effects of a transaction.
+fn written_object_refs(effects: &TransactionEffects) -> impl Iterator<Item = ObjectRef> + '_ {
+    effects
+        .all_mutated()
+        .map(|(object_ref, _, _)| *object_ref)
+        .chain(effects.deleted.iter().copied())
+        .chain(effects.wrapped.iter().copied())
+}
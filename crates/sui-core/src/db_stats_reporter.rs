@@ -0,0 +1,132 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use prometheus::{register_int_gauge_vec_with_registry, IntGaugeVec, Registry};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{error, info};
+
+use sui_storage::db_stats::ColumnFamilyStats;
+use sui_storage::IndexStore;
+use sui_types::error::SuiResult;
+
+use crate::authority::AuthorityState;
+
+#[cfg(test)]
+#[path = "unit_tests/db_stats_reporter_tests.rs"]
+mod db_stats_reporter_tests;
+
+/// How often the statistics of the tables are collected.
+const DB_STATS_INTERVAL: Duration = Duration::from_secs(60);
+
+pub struct DbStatsMetrics {
+    pub estimated_keys: IntGaugeVec,
+    pub live_sst_size: IntGaugeVec,
+    pub memtable_size: IntGaugeVec,
+    pub pending_compaction_bytes: IntGaugeVec,
+}
+
+impl DbStatsMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            estimated_keys: register_int_gauge_vec_with_registry!(
+                "db_table_estimated_keys",
+                "Estimated number of keys of a table",
+                &["store", "table"],
+                registry,
+            )
+            .unwrap(),
+            live_sst_size: register_int_gauge_vec_with_registry!(
+                "db_table_live_sst_size_bytes",
+                "Total size of the live SST files of a table",
+                &["store", "table"],
+                registry,
+            )
+            .unwrap(),
+            memtable_size: register_int_gauge_vec_with_registry!(
+                "db_table_memtable_size_bytes",
+                "Size of the memtables of a table",
+                &["store", "table"],
+                registry,
+            )
+            .unwrap(),
+            pending_compaction_bytes: register_int_gauge_vec_with_registry!(
+                "db_table_pending_compaction_bytes",
+                "Estimated number of bytes compaction needs to rewrite for a table",
+                &["store", "table"],
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    fn set(&self, store: &str, stats: &[ColumnFamilyStats]) {
+        for table in stats {
+            let labels = [store, table.name.as_str()];
+            self.estimated_keys
+                .with_label_values(&labels)
+                .set(table.estimated_keys as i64);
+            self.live_sst_size
+                .with_label_values(&labels)
+                .set(table.live_sst_size as i64);
+            self.memtable_size
+                .with_label_values(&labels)
+                .set(table.memtable_size as i64);
+            self.pending_compaction_bytes
+                .with_label_values(&labels)
+                .set(table.pending_compaction_bytes as i64);
+        }
+    }
+}
+
+/// Background task exporting the statistics of the tables of the stores of a node as gauges.
+pub struct DbStatsReporter {
+    state: Arc<AuthorityState>,
+    index_store: Option<Arc<IndexStore>>,
+    metrics: DbStatsMetrics,
+}
+
+impl DbStatsReporter {
+    pub fn new(
+        state: Arc<AuthorityState>,
+        index_store: Option<Arc<IndexStore>>,
+        registry: &Registry,
+    ) -> Self {
+        Self {
+            state,
+            index_store,
+            metrics: DbStatsMetrics::new(registry),
+        }
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(interval = ?DB_STATS_INTERVAL, "Starting db stats reporter");
+        let mut report_interval = interval(DB_STATS_INTERVAL);
+        report_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            report_interval.tick().await;
+            if let Err(e) = self.report() {
+                error!("Failed to collect db stats: {e}");
+            }
+        }
+    }
+
+    pub fn report(&self) -> SuiResult {
+        self.metrics.set("authority", &self.state.db().db_stats()?);
+        self.metrics
+            .set("checkpoints", &self.state.checkpoints().lock().db_stats()?);
+        self.metrics
+            .set("node_sync", &self.state.node_sync_store.db_stats()?);
+        if let Some(index_store) = &self.index_store {
+            self.metrics.set("indexes", &index_store.db_stats()?);
+        }
+        Ok(())
+    }
+}
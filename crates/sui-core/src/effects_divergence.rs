@@ -0,0 +1,347 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Comparison of the effects signed by every validator of the committee for the same
+//! transactions, to find the authorities that forked or lag behind the others.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+
+use futures::future::join_all;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::time::timeout;
+
+use sui_types::base_types::{
+    AuthorityName, ExecutionDigests, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::batch::{TxSequenceNumber, UpdateItem};
+use sui_types::committee::StakeUnit;
+use sui_types::error::SuiResult;
+use sui_types::messages::{BatchInfoRequest, BatchInfoResponseItem, SignedTransactionEffects};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::authority::MAX_ITEMS_LIMIT;
+use crate::authority_aggregator::AuthorityAggregator;
+use crate::authority_client::AuthorityAPI;
+use crate::safe_client::SafeClient;
+use crate::transaction_replay::effects_differences;
+
+#[cfg(test)]
+#[path = "unit_tests/effects_divergence_tests.rs"]
+mod effects_divergence_tests;
+
+/// How long to wait for the next item of the batch stream of an authority before assuming it
+/// has not executed the rest of the requested range yet.
+const BATCH_STREAM_ITEM_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The effects an authority signed for a transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorityEffects {
+    pub authority: AuthorityName,
+    /// The digest of the signed effects, `None` if the authority has not executed the
+    /// transaction.
+    pub effects_digest: Option<TransactionEffectsDigest>,
+    /// The sequence number of the transaction in the batches of the authority, only known when
+    /// checking a sequence range.
+    pub batch_position: Option<TxSequenceNumber>,
+    /// The first field of the effects that differs from the reference effects, if they differ.
+    pub first_differing_field: Option<String>,
+    /// The error returned by the authority when it was queried.
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionDivergence {
+    pub transaction: TransactionDigest,
+    /// The effects digest the effects of every authority are compared to: the one of the
+    /// checkpoint when checking a checkpoint, the one signed by the most stake otherwise.
+    pub reference_effects: Option<TransactionEffectsDigest>,
+    pub reference_stake: StakeUnit,
+    /// The authorities that signed other effects than the reference effects.
+    pub disagreeing: Vec<AuthorityName>,
+    /// The authorities that have not executed the transaction, or could not be queried.
+    pub missing: Vec<AuthorityName>,
+    /// The effects of every authority of the committee, in committee order.
+    pub authorities: Vec<AuthorityEffects>,
+}
+
+impl TransactionDivergence {
+    /// Whether some authorities signed conflicting effects, rather than only lagging behind.
+    pub fn is_fork(&self) -> bool {
+        !self.disagreeing.is_empty()
+    }
+
+    /// Whether every authority signed the reference effects.
+    pub fn is_consistent(&self) -> bool {
+        self.reference_effects.is_some() && self.disagreeing.is_empty() && self.missing.is_empty()
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DivergenceReport {
+    /// The number of transactions checked.
+    pub transactions: u64,
+    /// The number of transactions for which some authorities signed conflicting effects.
+    pub forks: u64,
+    /// The transactions not every authority signed the reference effects of.
+    pub divergent: Vec<TransactionDivergence>,
+}
+
+impl DivergenceReport {
+    pub fn has_forks(&self) -> bool {
+        self.forks > 0
+    }
+
+    /// Counts a checked transaction, keeping it if not every authority signed the reference
+    /// effects.
+    pub fn push(&mut self, divergence: TransactionDivergence) {
+        self.transactions += 1;
+        if divergence.is_fork() {
+            self.forks += 1;
+        }
+        if !divergence.is_consistent() {
+            self.divergent.push(divergence);
+        }
+    }
+}
+
+/// Queries every authority of the committee for the effects of `transaction` and compares them.
+pub async fn check_transaction<A>(
+    aggregator: &AuthorityAggregator<A>,
+    transaction: TransactionDigest,
+) -> TransactionDivergence
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    compare_effects(aggregator, transaction, None, &BTreeMap::new()).await
+}
+
+/// Compares the effects of the transactions at sequence numbers `[start, end)` of the batches of
+/// every authority. As every authority sequences transactions in its own order, the range of each
+/// authority can hold different transactions: the effects of all of them are compared.
+pub async fn check_sequence_range<A>(
+    aggregator: &AuthorityAggregator<A>,
+    start: TxSequenceNumber,
+    end: TxSequenceNumber,
+) -> DivergenceReport
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    let batches =
+        join_all(
+            aggregator
+                .authority_clients
+                .iter()
+                .map(|(name, client)| async move {
+                    (*name, batch_transactions(client, start, end).await)
+                }),
+        )
+        .await;
+
+    // The position of every transaction in the batches of every authority.
+    let mut positions: BTreeMap<TransactionDigest, BTreeMap<AuthorityName, TxSequenceNumber>> =
+        BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    for (name, transactions) in batches {
+        match transactions {
+            Ok(transactions) => {
+                for (seq, digests) in transactions {
+                    positions
+                        .entry(digests.transaction)
+                        .or_default()
+                        .insert(name, seq);
+                }
+            }
+            Err(e) => {
+                errors.insert(name, e.to_string());
+            }
+        }
+    }
+
+    let mut report = DivergenceReport::default();
+    for (transaction, positions) in positions {
+        let mut divergence = compare_effects(aggregator, transaction, None, &positions).await;
+        // An authority whose batch stream failed is reported with the error of the stream if
+        // it did not answer the transaction query either.
+        for effects in &mut divergence.authorities {
+            if effects.error.is_none() && effects.effects_digest.is_none() {
+                effects.error = errors.get(&effects.authority).cloned();
+            }
+        }
+        report.push(divergence);
+    }
+    report
+}
+
+/// Compares the effects of every authority for the transactions of the certified checkpoint
+/// `sequence_number`, with the effects digests of the checkpoint as reference.
+pub async fn check_checkpoint<A>(
+    aggregator: &AuthorityAggregator<A>,
+    sequence_number: CheckpointSequenceNumber,
+) -> SuiResult<DivergenceReport>
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    let authorities: BTreeSet<_> = aggregator.authority_clients.keys().copied().collect();
+    let (_, contents) = aggregator
+        .get_certified_checkpoint(sequence_number, true, &authorities, None)
+        .await?;
+    let mut report = DivergenceReport::default();
+    for digests in contents.iter().flat_map(|contents| contents.iter()) {
+        report.push(
+            compare_effects(
+                aggregator,
+                digests.transaction,
+                Some(digests.effects),
+                &BTreeMap::new(),
+            )
+            .await,
+        );
+    }
+    Ok(report)
+}
+
+async fn compare_effects<A>(
+    aggregator: &AuthorityAggregator<A>,
+    transaction: TransactionDigest,
+    expected_effects: Option<TransactionEffectsDigest>,
+    positions: &BTreeMap<AuthorityName, TxSequenceNumber>,
+) -> TransactionDivergence
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    let responses = join_all(
+        aggregator
+            .authority_clients
+            .iter()
+            .map(
+                |(name, client)| async move { (*name, signed_effects(client, transaction).await) },
+            ),
+    )
+    .await;
+
+    let mut stakes: BTreeMap<TransactionEffectsDigest, StakeUnit> = BTreeMap::new();
+    for (name, response) in &responses {
+        if let Ok(Some(effects)) = response {
+            *stakes.entry(*effects.digest()).or_default() += aggregator.committee.weight(name);
+        }
+    }
+    let reference_effects = expected_effects.or_else(|| {
+        stakes
+            .iter()
+            .max_by_key(|(_, stake)| **stake)
+            .map(|(digest, _)| *digest)
+    });
+    let reference_stake = reference_effects
+        .and_then(|digest| stakes.get(&digest).copied())
+        .unwrap_or(0);
+    let reference = responses
+        .iter()
+        .filter_map(|(_, response)| response.as_ref().ok().and_then(|r| r.as_ref()))
+        .find(|effects| Some(*effects.digest()) == reference_effects);
+
+    let mut disagreeing = Vec::new();
+    let mut missing = Vec::new();
+    let mut authorities = Vec::new();
+    for (name, response) in responses {
+        let (effects, error) = match response {
+            Ok(effects) => (effects, None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        let effects_digest = effects.as_ref().map(|effects| *effects.digest());
+        let mut first_differing_field = None;
+        match &effects {
+            None => missing.push(name),
+            Some(effects) if effects_digest != reference_effects => {
+                disagreeing.push(name);
+                first_differing_field = reference.and_then(|reference| {
+                    effects_differences(&reference.effects, &effects.effects)
+                        .into_iter()
+                        .next()
+                });
+            }
+            Some(_) => (),
+        }
+        authorities.push(AuthorityEffects {
+            authority: name,
+            effects_digest,
+            batch_position: positions.get(&name).copied(),
+            first_differing_field,
+            error,
+        });
+    }
+
+    TransactionDivergence {
+        transaction,
+        reference_effects,
+        reference_stake,
+        disagreeing,
+        missing,
+        authorities,
+    }
+}
+
+/// The effects an authority signed for `transaction`, if it executed it.
+async fn signed_effects<A>(
+    client: &SafeClient<A>,
+    transaction: TransactionDigest,
+) -> SuiResult<Option<SignedTransactionEffects>>
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    Ok(client
+        .handle_transaction_info_request(transaction.into())
+        .await?
+        .signed_effects)
+}
+
+/// The transactions at sequence numbers `[start, end)` of the batches of an authority, up to the
+/// last one it executed.
+async fn batch_transactions<A>(
+    client: &SafeClient<A>,
+    start: TxSequenceNumber,
+    end: TxSequenceNumber,
+) -> SuiResult<Vec<(TxSequenceNumber, ExecutionDigests)>>
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    let mut transactions = Vec::new();
+    let mut next = start;
+    while next < end {
+        let mut stream = client
+            .handle_batch_stream(BatchInfoRequest {
+                start: Some(next),
+                length: std::cmp::min(MAX_ITEMS_LIMIT, end - next),
+            })
+            .await?;
+        let chunk_end = std::cmp::min(next + MAX_ITEMS_LIMIT, end);
+        let mut complete = false;
+        // The stream goes on with live updates once past the last batch of the authority, so
+        // stop at the end of the chunk or when the authority stops sending items.
+        while let Ok(Some(item)) = timeout(BATCH_STREAM_ITEM_TIMEOUT, stream.next()).await {
+            match item? {
+                BatchInfoResponseItem(UpdateItem::Transaction((seq, digests))) => {
+                    if seq >= next && seq < chunk_end {
+                        transactions.push((seq, digests));
+                    }
+                    if seq + 1 >= chunk_end {
+                        complete = true;
+                        break;
+                    }
+                }
+                BatchInfoResponseItem(UpdateItem::Batch(batch)) => {
+                    if batch.data().next_sequence_number >= chunk_end {
+                        complete = true;
+                        break;
+                    }
+                }
+            }
+        }
+        if !complete {
+            break;
+        }
+        next = chunk_end;
+    }
+    Ok(transactions)
+}
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use rocksdb::Options;
-use std::path::PathBuf;
-use sui_storage::default_db_options;
+use std::path::{Path, PathBuf};
+use sui_storage::{checkpoint_db, default_db_options};
 use sui_types::base_types::ObjectID;
 use sui_types::committee::{Committee, EpochId};
 use sui_types::error::{SuiError, SuiResult};
@@ -47,6 +47,11 @@ impl CommitteeStore {
         Self::new(path, genesis_committee, None)
     }
 
+    /// Creates a RocksDB checkpoint of the committee store in the directory `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        checkpoint_db(&self.committee_map, path)
+    }
+
     pub fn init_genesis_committee(&self, genesis_committee: Committee) -> SuiResult {
         assert_eq!(genesis_committee.epoch, 0);
         self.committee_map.insert(&0, &genesis_committee)?;
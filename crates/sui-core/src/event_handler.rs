@@ -3,16 +3,17 @@
 
 use std::sync::Arc;
 
+use futures::{future, stream, StreamExt};
 use move_bytecode_utils::module_cache::SyncModuleCache;
-use sui_json_rpc_types::SuiMoveStruct;
+use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope, SuiMoveStruct};
 use tokio_stream::Stream;
 use tracing::{debug, error, instrument, trace};
 
-use sui_storage::event_store::{EventStore, EventStoreType};
+use sui_storage::event_store::{EventStore, EventStoreType, StoredEvent};
 use sui_types::base_types::TransactionDigest;
 use sui_types::{
     error::{SuiError, SuiResult},
-    event::{Event, EventEnvelope},
+    event::{Event, EventEnvelope, EventID},
     messages::TransactionEffects,
 };
 
@@ -26,6 +27,9 @@ mod event_handler_tests;
 
 pub const EVENT_DISPATCH_BUFFER_SIZE: usize = 1000;
 
+/// Number of stored events read at a time when replaying events to a subscriber
+pub const EVENT_REPLAY_PAGE_SIZE: usize = 100;
+
 pub struct EventHandler {
     module_cache: Arc<SyncModuleCache<ResolverWrapper<AuthorityStore>>>,
     event_streamer: Streamer<EventEnvelope, EventFilter>,
@@ -52,7 +56,16 @@ impl EventHandler {
         let res: Result<Vec<_>, _> = effects
             .events
             .iter()
-            .map(|e| self.create_envelope(e, effects.transaction_digest, seq_num, timestamp_ms))
+            .enumerate()
+            .map(|(event_num, e)| {
+                self.create_envelope(
+                    e,
+                    effects.transaction_digest,
+                    seq_num,
+                    event_num as u64,
+                    timestamp_ms,
+                )
+            })
             .collect();
         let envelopes = res?;
 
@@ -79,6 +92,7 @@ impl EventHandler {
         event: &Event,
         digest: TransactionDigest,
         seq_num: u64,
+        event_num: u64,
         timestamp_ms: u64,
     ) -> Result<EventEnvelope, SuiError> {
         let json_value = match event {
@@ -103,6 +117,7 @@ impl EventHandler {
             timestamp_ms,
             Some(digest),
             seq_num,
+            event_num,
             event.clone(),
             json_value,
         ))
@@ -111,4 +126,72 @@ impl EventHandler {
     pub fn subscribe(&self, filter: EventFilter) -> impl Stream<Item = EventEnvelope> {
         self.event_streamer.subscribe(filter)
     }
+
+    /// Subscribes to events matching `filter`, first replaying the stored events starting from
+    /// `cursor` (inclusive) and then switching over to live events.
+    /// The live subscription is opened before the replay starts and live events which were
+    /// already replayed are skipped, so there is no gap and no duplicate at the switch over.
+    pub fn subscribe_from(
+        &self,
+        filter: EventFilter,
+        cursor: EventID,
+    ) -> impl Stream<Item = Result<SuiEventEnvelope, anyhow::Error>> {
+        let live = self.subscribe(filter.clone());
+        let module_cache = self.module_cache.clone();
+        let live = live.map(move |e| {
+            let id = e.id();
+            SuiEvent::try_from(e.event, module_cache.as_ref()).map(|event| SuiEventEnvelope {
+                timestamp: e.timestamp,
+                tx_digest: e.tx_digest,
+                id,
+                event,
+            })
+        });
+
+        let event_store = self.event_store.clone();
+        let replay = stream::unfold(Some(cursor), move |cursor| {
+            let event_store = event_store.clone();
+            let filter = filter.clone();
+            async move {
+                let cursor = cursor?;
+                // Retrieve 1 extra event for the cursor of the next page
+                let page = event_store
+                    .events_by_filter(&filter, Some(cursor), EVENT_REPLAY_PAGE_SIZE + 1, false)
+                    .await;
+                Some(match page {
+                    Ok(mut events) => {
+                        let next_cursor = if events.len() > EVENT_REPLAY_PAGE_SIZE {
+                            events.pop().map(|e| e.id())
+                        } else {
+                            None
+                        };
+                        (StoredEvent::into_event_envelopes(events), next_cursor)
+                    }
+                    Err(e) => (Err(e.into()), None),
+                })
+            }
+        })
+        .flat_map(|page| {
+            stream::iter(match page {
+                Ok(events) => events.into_iter().map(Ok).collect::<Vec<_>>(),
+                Err(e) => vec![Err(e)],
+            })
+        });
+
+        let mut last_replayed = None;
+        replay
+            .map(|event| (true, event))
+            .chain(live.map(|event| (false, event)))
+            .filter_map(move |(replayed, event)| {
+                let keep = match &event {
+                    Ok(e) if replayed => {
+                        last_replayed = Some(e.id);
+                        true
+                    }
+                    Ok(e) => e.id >= cursor && last_replayed.map_or(true, |last| e.id > last),
+                    Err(_) => true,
+                };
+                future::ready(keep.then_some(event))
+            })
+    }
 }
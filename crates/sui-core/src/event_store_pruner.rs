@@ -0,0 +1,169 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::anyhow;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, Instant, MissedTickBehavior};
+use tracing::{error, info};
+
+use sui_config::node::EventStoreRetentionConfig;
+use sui_storage::event_store::{EventStore, EventStoreType, RetentionPolicy};
+use sui_types::event::EventType;
+
+use crate::metrics::{start_timer, PRUNING_LATENCY_SEC_BUCKETS};
+
+#[cfg(test)]
+#[path = "unit_tests/event_store_pruner_tests.rs"]
+mod event_store_pruner_tests;
+
+pub struct EventStorePrunerMetrics {
+    pub pruned_events: IntCounter,
+    pub prune_errors: IntCounter,
+    pub prune_latency_sec: Histogram,
+    pub vacuum_latency_sec: Histogram,
+    pub oldest_retained_seq_num: IntGauge,
+}
+
+impl EventStorePrunerMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            pruned_events: register_int_counter_with_registry!(
+                "event_store_pruned_events",
+                "Total number of events deleted from the event store by the retention policy",
+                registry,
+            )
+            .unwrap(),
+            prune_errors: register_int_counter_with_registry!(
+                "event_store_prune_errors",
+                "Total number of failed event store pruning or vacuuming runs",
+                registry,
+            )
+            .unwrap(),
+            prune_latency_sec: register_histogram_with_registry!(
+                "event_store_prune_latency_sec",
+                "Latency of pruning the event store",
+                PRUNING_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            vacuum_latency_sec: register_histogram_with_registry!(
+                "event_store_vacuum_latency_sec",
+                "Latency of vacuuming the event store",
+                PRUNING_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            oldest_retained_seq_num: register_int_gauge_with_registry!(
+                "event_store_oldest_retained_seq_num",
+                "Sequence number from which events of all types are retained in the event store",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Background task enforcing an `EventStoreRetentionConfig` on an event store. Retention by age
+/// relies on event timestamps growing with sequence numbers, see `RetentionPolicy`.
+pub struct EventStorePruner {
+    event_store: Arc<EventStoreType>,
+    max_age: Option<Duration>,
+    max_event_count: Option<u64>,
+    max_age_by_type: Vec<(EventType, Duration)>,
+    prune_interval: Duration,
+    vacuum_interval: Option<Duration>,
+    metrics: EventStorePrunerMetrics,
+}
+
+impl EventStorePruner {
+    /// Fails if the config names an unknown event type.
+    pub fn new(
+        event_store: Arc<EventStoreType>,
+        config: &EventStoreRetentionConfig,
+        registry: &Registry,
+    ) -> Result<Self, anyhow::Error> {
+        let max_age_by_type = config
+            .max_age_secs_by_event_type
+            .iter()
+            .map(|(event_type, max_age_secs)| {
+                let event_type = EventType::from_str(event_type)
+                    .map_err(|e| anyhow!("Invalid event type {event_type} in retention: {e}"))?;
+                Ok((event_type, Duration::from_secs(*max_age_secs)))
+            })
+            .collect::<Result<_, anyhow::Error>>()?;
+        Ok(Self {
+            event_store,
+            max_age: config.max_age_secs.map(Duration::from_secs),
+            max_event_count: config.max_event_count,
+            max_age_by_type,
+            prune_interval: Duration::from_secs(config.prune_interval_secs),
+            vacuum_interval: config.vacuum_interval_secs.map(Duration::from_secs),
+            metrics: EventStorePrunerMetrics::new(registry),
+        })
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(
+            prune_interval = ?self.prune_interval,
+            vacuum_interval = ?self.vacuum_interval,
+            "Starting event store pruner"
+        );
+        let mut prune_interval = interval(self.prune_interval);
+        prune_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut last_vacuum = Instant::now();
+        loop {
+            prune_interval.tick().await;
+            if let Err(e) = self.prune().await {
+                error!("Failed to prune the event store: {e}");
+                self.metrics.prune_errors.inc();
+            }
+            if let Some(vacuum_interval) = self.vacuum_interval {
+                if last_vacuum.elapsed() >= vacuum_interval {
+                    let _timer = start_timer(self.metrics.vacuum_latency_sec.clone());
+                    if let Err(e) = self.event_store.vacuum().await {
+                        error!("Failed to vacuum the event store: {e}");
+                        self.metrics.prune_errors.inc();
+                    }
+                    last_vacuum = Instant::now();
+                }
+            }
+        }
+    }
+
+    async fn prune(&self) -> Result<(), anyhow::Error> {
+        let _timer = start_timer(self.metrics.prune_latency_sec.clone());
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        let min_timestamp_ms = |max_age: Duration| now.saturating_sub(max_age).as_millis() as u64;
+        let policy = RetentionPolicy {
+            min_timestamp_ms: self.max_age.map(min_timestamp_ms),
+            max_event_count: self.max_event_count,
+            min_timestamp_ms_by_type: self
+                .max_age_by_type
+                .iter()
+                .map(|(event_type, max_age)| (*event_type, min_timestamp_ms(*max_age)))
+                .collect(),
+        };
+        let pruned = self.event_store.prune(&policy).await?;
+        let oldest_retained_seq_num = self.event_store.oldest_retained_seq_num();
+        self.metrics.pruned_events.inc_by(pruned);
+        self.metrics
+            .oldest_retained_seq_num
+            .set(oldest_retained_seq_num as i64);
+        if pruned > 0 {
+            info!(pruned, oldest_retained_seq_num, "Pruned the event store");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Regenerates the event store from the effects kept in the authority store, e.g. after the
+//! event store was lost or event processing was turned off for a while.
+
+use tracing::{debug, warn};
+
+use sui_storage::event_store::EventStore;
+use sui_storage::TransactionTimestamps;
+use sui_types::error::{SuiError, SuiResult};
+
+use crate::authority::AuthorityStore;
+use crate::event_handler::EventHandler;
+
+#[cfg(test)]
+#[path = "unit_tests/event_store_rebuilder_tests.rs"]
+mod event_store_rebuilder_tests;
+
+/// Number of executed transactions read from the authority store at a time.
+pub const REBUILD_BATCH_SIZE: u64 = 1000;
+
+#[derive(Clone, Debug, Default)]
+pub struct EventStoreRebuildProgress {
+    /// Sequence number of the next transaction to replay.
+    pub next_seq: u64,
+    /// Sequence number the rebuild stops at (exclusive).
+    pub end_seq: u64,
+    /// Number of transactions replayed so far.
+    pub transactions: u64,
+    /// Number of events replayed so far.
+    pub events: u64,
+    /// Number of transactions without a timestamp in the index store, whose events were given
+    /// the timestamp of the previous transaction.
+    pub missing_timestamps: u64,
+}
+
+/// Replays the effects of every executed transaction that the event store of `event_handler`
+/// has not seen through `EventHandler::process_events`, in sequence number order.
+///
+/// The rebuild resumes from the highest sequence number in the event store, whose events may
+/// have been partially written, so an interrupted rebuild can be run again. Events of
+/// transactions below it are never added, so a store that was enabled late must be deleted to be
+/// rebuilt from the first transaction. Events are timestamped from `indexes` when available,
+/// as the timestamps recorded at execution time are not kept in the authority store.
+pub async fn rebuild_event_store(
+    store: &AuthorityStore,
+    indexes: Option<&impl TransactionTimestamps>,
+    event_handler: &EventHandler,
+    mut on_progress: impl FnMut(&EventStoreRebuildProgress),
+) -> SuiResult<EventStoreRebuildProgress> {
+    let mut progress = EventStoreRebuildProgress {
+        next_seq: event_handler.event_store.current_seq_num(),
+        end_seq: store.next_sequence_number()?,
+        ..Default::default()
+    };
+    let mut last_timestamp_ms = 0;
+    while progress.next_seq < progress.end_seq {
+        let batch_end = progress
+            .end_seq
+            .min(progress.next_seq.saturating_add(REBUILD_BATCH_SIZE));
+        let transactions = store.transactions_in_seq_range(progress.next_seq, batch_end)?;
+        if transactions.is_empty() {
+            return Err(SuiError::StorageCorruptedFieldError(format!(
+                "Executed transactions {} to {} are missing from the authority store",
+                progress.next_seq, batch_end
+            )));
+        }
+        for (seq, digests) in transactions {
+            if seq != progress.next_seq {
+                return Err(SuiError::StorageCorruptedFieldError(format!(
+                    "Executed transactions {} to {} are missing from the authority store",
+                    progress.next_seq, seq
+                )));
+            }
+            let effects = store.get_effects(&digests.transaction)?;
+            let timestamp_ms = match indexes
+                .map(|indexes| indexes.get_timestamp_ms(&digests.transaction))
+                .transpose()?
+                .flatten()
+            {
+                Some(timestamp_ms) => timestamp_ms,
+                None => {
+                    debug!(seq, tx_digest = ?digests.transaction, "No timestamp for transaction");
+                    progress.missing_timestamps += 1;
+                    last_timestamp_ms
+                }
+            };
+            event_handler
+                .process_events(&effects, timestamp_ms, seq)
+                .await?;
+            last_timestamp_ms = timestamp_ms;
+            progress.next_seq = seq + 1;
+            progress.transactions += 1;
+            progress.events += effects.events.len() as u64;
+        }
+        on_progress(&progress);
+    }
+    if progress.missing_timestamps > 0 {
+        warn!(
+            missing_timestamps = progress.missing_timestamps,
+            "Rebuilt events of transactions without a timestamp"
+        );
+    }
+    Ok(progress)
+}
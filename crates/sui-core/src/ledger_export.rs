@@ -0,0 +1,197 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Flattening of the executed transactions of a node into records of transactions, object
+//! changes and events, with the JSON types of the RPC API, to export the ledger to files.
+
+use std::sync::Arc;
+
+use move_bytecode_utils::module_cache::SyncModuleCache;
+use serde::{Deserialize, Serialize};
+
+use sui_json_rpc_types::{
+    SuiEvent, SuiEventEnvelope, SuiExecutionStatus, SuiGasCostSummary, SuiObjectRef,
+    SuiTransactionData,
+};
+use sui_storage::IndexStore;
+use sui_types::base_types::{ObjectRef, SuiAddress, TransactionDigest, TransactionEffectsDigest};
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::SuiError;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::object::Owner;
+use typed_store::Map;
+
+use crate::authority::{AuthorityStore, ResolverWrapper};
+use crate::checkpoints::CheckpointStoreTables;
+
+#[cfg(test)]
+#[path = "unit_tests/ledger_export_tests.rs"]
+mod ledger_export_tests;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRecord {
+    pub seq: TxSequenceNumber,
+    pub transaction_digest: TransactionDigest,
+    pub effects_digest: TransactionEffectsDigest,
+    /// The checkpoint of the transaction, if the checkpoint store was given and has it.
+    pub checkpoint: Option<CheckpointSequenceNumber>,
+    /// The execution time of the transaction, if the index store was given and has it.
+    pub timestamp_ms: Option<u64>,
+    pub sender: SuiAddress,
+    pub status: SuiExecutionStatus,
+    pub gas_used: SuiGasCostSummary,
+    pub data: SuiTransactionData,
+    pub dependencies: Vec<TransactionDigest>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectChangeKind {
+    Created,
+    Mutated,
+    Unwrapped,
+    Deleted,
+    Wrapped,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectChangeRecord {
+    pub seq: TxSequenceNumber,
+    pub transaction_digest: TransactionDigest,
+    pub timestamp_ms: Option<u64>,
+    pub kind: ObjectChangeKind,
+    /// The object version written by the transaction, or the last version of a deleted or
+    /// wrapped object.
+    #[serde(flatten)]
+    pub reference: SuiObjectRef,
+    /// The owner of the object after the transaction, `None` for deleted and wrapped objects.
+    pub owner: Option<Owner>,
+}
+
+/// The records of the executed transactions at sequence numbers `[start, end)`.
+#[derive(Clone, Debug, Default)]
+pub struct LedgerChunk {
+    pub start: TxSequenceNumber,
+    pub end: TxSequenceNumber,
+    pub transactions: Vec<TransactionRecord>,
+    pub object_changes: Vec<ObjectChangeRecord>,
+    pub events: Vec<SuiEventEnvelope>,
+}
+
+pub struct LedgerExporter {
+    store: Arc<AuthorityStore>,
+    indexes: Option<IndexStore>,
+    checkpoints: Option<CheckpointStoreTables>,
+    module_cache: SyncModuleCache<ResolverWrapper<AuthorityStore>>,
+}
+
+impl LedgerExporter {
+    /// The index store provides the timestamps of the transactions and the checkpoint store
+    /// their checkpoints, the records miss them otherwise.
+    pub fn new(
+        store: Arc<AuthorityStore>,
+        indexes: Option<IndexStore>,
+        checkpoints: Option<CheckpointStoreTables>,
+    ) -> Self {
+        Self {
+            module_cache: SyncModuleCache::new(ResolverWrapper(store.clone())),
+            store,
+            indexes,
+            checkpoints,
+        }
+    }
+
+    /// The sequence number of the next transaction the node will execute, where an export of
+    /// the whole ledger stops.
+    pub fn next_sequence_number(&self) -> Result<TxSequenceNumber, SuiError> {
+        self.store.next_sequence_number()
+    }
+
+    /// Reads the records of the executed transactions at sequence numbers `[start, end)`.
+    pub fn export_range(
+        &self,
+        start: TxSequenceNumber,
+        end: TxSequenceNumber,
+    ) -> Result<LedgerChunk, anyhow::Error> {
+        let mut chunk = LedgerChunk {
+            start,
+            end,
+            ..Default::default()
+        };
+        for (seq, digests) in self.store.transactions_in_seq_range(start, end)? {
+            let certificate = self
+                .store
+                .get_certified_transaction(&digests.transaction)?
+                .ok_or(SuiError::TransactionNotFound {
+                    digest: digests.transaction,
+                })?;
+            let effects = self.store.get_effects(&digests.transaction)?;
+            let timestamp_ms = match &self.indexes {
+                Some(indexes) => indexes.get_timestamp_ms(&digests.transaction)?,
+                None => None,
+            };
+            let checkpoint = match &self.checkpoints {
+                Some(checkpoints) => checkpoints.transactions_to_checkpoint.get(&digests)?,
+                None => None,
+            };
+
+            let object_change =
+                |kind, object_ref: &ObjectRef, owner: Option<Owner>| ObjectChangeRecord {
+                    seq,
+                    transaction_digest: digests.transaction,
+                    timestamp_ms,
+                    kind,
+                    reference: (*object_ref).into(),
+                    owner,
+                };
+            for (kind, changes) in [
+                (ObjectChangeKind::Created, &effects.created),
+                (ObjectChangeKind::Mutated, &effects.mutated),
+                (ObjectChangeKind::Unwrapped, &effects.unwrapped),
+            ] {
+                chunk.object_changes.extend(
+                    changes
+                        .iter()
+                        .map(|(object_ref, owner)| object_change(kind, object_ref, Some(*owner))),
+                );
+            }
+            for (kind, changes) in [
+                (ObjectChangeKind::Deleted, &effects.deleted),
+                (ObjectChangeKind::Wrapped, &effects.wrapped),
+            ] {
+                chunk.object_changes.extend(
+                    changes
+                        .iter()
+                        .map(|object_ref| object_change(kind, object_ref, None)),
+                );
+            }
+
+            for (event_seq, event) in effects.events.iter().enumerate() {
+                chunk.events.push(SuiEventEnvelope {
+                    // Events of transactions without a known timestamp are at 0.
+                    timestamp: timestamp_ms.unwrap_or_default(),
+                    tx_digest: Some(digests.transaction),
+                    id: (seq, event_seq as u64).into(),
+                    event: SuiEvent::try_from(event.clone(), &self.module_cache)?,
+                });
+            }
+
+            let data = certificate.signed_data.data;
+            chunk.transactions.push(TransactionRecord {
+                seq,
+                transaction_digest: digests.transaction,
+                effects_digest: digests.effects,
+                checkpoint,
+                timestamp_ms,
+                sender: data.signer(),
+                status: effects.status.into(),
+                gas_used: effects.gas_used.into(),
+                data: data.try_into()?,
+                dependencies: effects.dependencies,
+            });
+        }
+        Ok(chunk)
+    }
+}
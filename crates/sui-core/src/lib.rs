@@ -7,18 +7,29 @@ pub mod authority_aggregator;
 pub mod authority_batch;
 pub mod authority_client;
 pub mod authority_server;
+pub mod checkpoint_verifier;
 pub mod checkpoints;
 pub mod consensus_adapter;
+pub mod db_backup;
+pub mod db_checker;
+pub mod db_stats_reporter;
+pub mod effects_divergence;
 pub mod epoch;
 pub mod event_handler;
+pub mod event_store_pruner;
+pub mod event_store_rebuilder;
 pub mod execution_engine;
 pub mod gateway_state;
+pub mod ledger_export;
 pub mod metrics;
+pub mod object_pruner;
 pub mod quorum_driver;
 pub mod safe_client;
+pub mod state_snapshot;
 pub mod streamer;
 pub mod transaction_input_checker;
 pub mod transaction_orchestrator;
+pub mod transaction_replay;
 pub mod transaction_streamer;
 pub mod validator_info;
 
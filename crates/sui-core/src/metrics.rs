@@ -4,6 +4,11 @@
 use prometheus::{Histogram, IntCounter};
 use tokio::time::Instant;
 
+/// Latency buckets of the background pruning tasks, whose runs can take minutes.
+pub const PRUNING_LATENCY_SEC_BUCKETS: &[f64] = &[
+    0.01, 0.05, 0.1, 0.5, 1., 2.5, 5., 10., 30., 60., 120., 300., 600.,
+];
+
 pub fn start_timer(metrics: Histogram) -> impl Drop {
     let start_ts = Instant::now();
     scopeguard::guard((metrics, start_ts), |(metrics, start_ts)| {
@@ -0,0 +1,295 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::anyhow;
+use parking_lot::Mutex;
+use prometheus::{
+    register_histogram_with_registry, register_int_counter_with_registry,
+    register_int_gauge_with_registry, Histogram, IntCounter, IntGauge, Registry,
+};
+use tokio::task::JoinHandle;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{debug, error, info, warn};
+
+use sui_config::node::ObjectPruningConfig;
+use sui_types::base_types::ExecutionDigests;
+use sui_types::error::SuiResult;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+
+use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStore;
+use crate::metrics::{start_timer, PRUNING_LATENCY_SEC_BUCKETS};
+
+#[cfg(test)]
+#[path = "unit_tests/object_pruner_tests.rs"]
+mod object_pruner_tests;
+
+pub struct ObjectPrunerMetrics {
+    pub pruned_objects: IntCounter,
+    pub pruned_checkpoint: IntGauge,
+    pub prune_errors: IntCounter,
+    pub prune_latency_sec: Histogram,
+    pub skipped_checkpoints: IntCounter,
+    pub enabled: IntGauge,
+}
+
+impl ObjectPrunerMetrics {
+    pub fn new(registry: &Registry) -> Self {
+        Self {
+            pruned_objects: register_int_counter_with_registry!(
+                "object_pruner_pruned_objects",
+                "Total number of superseded object versions deleted from the object store",
+                registry,
+            )
+            .unwrap(),
+            pruned_checkpoint: register_int_gauge_with_registry!(
+                "object_pruner_pruned_checkpoint",
+                "Last checkpoint whose superseded object versions have been pruned",
+                registry,
+            )
+            .unwrap(),
+            prune_errors: register_int_counter_with_registry!(
+                "object_pruner_errors",
+                "Total number of failed object pruning runs",
+                registry,
+            )
+            .unwrap(),
+            prune_latency_sec: register_histogram_with_registry!(
+                "object_pruner_latency_sec",
+                "Latency of pruning the object store",
+                PRUNING_LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+            skipped_checkpoints: register_int_counter_with_registry!(
+                "object_pruner_skipped_checkpoints",
+                "Total number of checkpoints skipped by the object pruner as their contents are not stored",
+                registry,
+            )
+            .unwrap(),
+            enabled: register_int_gauge_with_registry!(
+                "object_pruner_enabled",
+                "Whether the object pruner is switched on",
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+/// Switches a running `ObjectPruner` on and off. Pruning stops at the next run once switched off.
+#[derive(Clone, Debug)]
+pub struct ObjectPrunerSwitch(Arc<AtomicBool>);
+
+impl ObjectPrunerSwitch {
+    pub fn is_enabled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        let was_enabled = self.0.swap(enabled, Ordering::Relaxed);
+        if was_enabled != enabled {
+            info!(enabled, "Object pruner switched");
+        }
+    }
+}
+
+/// Whether the objects of a checkpoint can be pruned.
+enum PrunableCheckpoint {
+    /// The checkpoint is beyond the horizon, with these transactions.
+    Prunable(Vec<ExecutionDigests>),
+    /// The checkpoint is within the horizon, or not certified yet.
+    Retained,
+    /// The contents or summary of the checkpoint are not stored, with the next certified
+    /// checkpoint whose contents are.
+    Missing(Option<CheckpointSequenceNumber>),
+}
+
+/// Background task deleting the object versions superseded by the transactions of checkpoints
+/// older than an `ObjectPruningConfig` horizon, one checkpoint at a time in sequence.
+pub struct ObjectPruner {
+    store: Arc<AuthorityStore>,
+    checkpoints: Arc<Mutex<CheckpointStore>>,
+    num_checkpoints_to_retain: Option<u64>,
+    num_epochs_to_retain: Option<u64>,
+    prune_interval: Duration,
+    max_checkpoints_per_run: u64,
+    switch: ObjectPrunerSwitch,
+    metrics: ObjectPrunerMetrics,
+}
+
+impl ObjectPruner {
+    /// Fails if the config sets no horizon.
+    pub fn new(
+        store: Arc<AuthorityStore>,
+        checkpoints: Arc<Mutex<CheckpointStore>>,
+        config: &ObjectPruningConfig,
+        registry: &Registry,
+    ) -> Result<Self, anyhow::Error> {
+        if config.num_checkpoints_to_retain.is_none() && config.num_epochs_to_retain.is_none() {
+            return Err(anyhow!(
+                "Object pruning needs num-checkpoints-to-retain or num-epochs-to-retain"
+            ));
+        }
+        let metrics = ObjectPrunerMetrics::new(registry);
+        metrics.enabled.set(config.enabled as i64);
+        Ok(Self {
+            store,
+            checkpoints,
+            num_checkpoints_to_retain: config.num_checkpoints_to_retain,
+            num_epochs_to_retain: config.num_epochs_to_retain,
+            prune_interval: Duration::from_secs(config.prune_interval_secs),
+            max_checkpoints_per_run: config.max_checkpoints_per_run,
+            switch: ObjectPrunerSwitch(Arc::new(AtomicBool::new(config.enabled))),
+            metrics,
+        })
+    }
+
+    pub fn switch(&self) -> ObjectPrunerSwitch {
+        self.switch.clone()
+    }
+
+    pub fn spawn(self) -> JoinHandle<()> {
+        tokio::spawn(self.run())
+    }
+
+    async fn run(self) {
+        info!(
+            prune_interval = ?self.prune_interval,
+            num_checkpoints_to_retain = ?self.num_checkpoints_to_retain,
+            num_epochs_to_retain = ?self.num_epochs_to_retain,
+            "Starting object pruner"
+        );
+        let mut prune_interval = interval(self.prune_interval);
+        prune_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let pruner = Arc::new(self);
+        loop {
+            prune_interval.tick().await;
+            let enabled = pruner.switch.is_enabled();
+            pruner.metrics.enabled.set(enabled as i64);
+            if !enabled {
+                continue;
+            }
+            // Pruning reads and deletes from the databases synchronously, for up to
+            // max_checkpoints_per_run checkpoints, so it is kept off the runtime workers
+            let blocking_pruner = pruner.clone();
+            let result = tokio::task::spawn_blocking(move || blocking_pruner.prune())
+                .await
+                .expect("Pruning the object store panicked");
+            if let Err(e) = result {
+                error!("Failed to prune the object store: {e}");
+                pruner.metrics.prune_errors.inc();
+            }
+        }
+    }
+
+    /// Prunes the checkpoints following the last pruned one that are beyond the horizon, up to
+    /// `max_checkpoints_per_run` of them. Stops early at a checkpoint whose transactions have not
+    /// all been executed. Checkpoints that are not stored, such as those before the checkpoint a
+    /// node synced from, are skipped up to the next stored one. Returns the number of deleted
+    /// object versions.
+    pub fn prune(&self) -> SuiResult<usize> {
+        let _timer = start_timer(self.metrics.prune_latency_sec.clone());
+        let mut next_checkpoint = self
+            .store
+            .objects_pruned_checkpoint()?
+            .map_or(0, |checkpoint| checkpoint + 1);
+        let mut pruned = 0;
+        for _ in 0..self.max_checkpoints_per_run {
+            let transactions = match self.prunable_checkpoint_contents(next_checkpoint)? {
+                PrunableCheckpoint::Prunable(transactions) => transactions,
+                PrunableCheckpoint::Retained => break,
+                PrunableCheckpoint::Missing(Some(next_stored)) => {
+                    warn!(
+                        checkpoint = next_checkpoint,
+                        next_stored, "Skipping checkpoints missing from the checkpoint store"
+                    );
+                    self.store
+                        .skip_objects_pruned_checkpoints(next_stored - 1)?;
+                    self.metrics
+                        .skipped_checkpoints
+                        .inc_by(next_stored - next_checkpoint);
+                    self.metrics.pruned_checkpoint.set(next_stored as i64 - 1);
+                    next_checkpoint = next_stored;
+                    continue;
+                }
+                PrunableCheckpoint::Missing(None) => {
+                    debug!(
+                        checkpoint = next_checkpoint,
+                        "Not pruning a checkpoint whose contents are not stored yet"
+                    );
+                    break;
+                }
+            };
+            match self
+                .store
+                .prune_objects_of_checkpoint(next_checkpoint, &transactions)?
+            {
+                Some(pruned_objects) => {
+                    debug!(
+                        checkpoint = next_checkpoint,
+                        pruned_objects, "Pruned object versions"
+                    );
+                    pruned += pruned_objects;
+                    self.metrics.pruned_objects.inc_by(pruned_objects as u64);
+                    self.metrics.pruned_checkpoint.set(next_checkpoint as i64);
+                    next_checkpoint += 1;
+                }
+                None => {
+                    debug!(
+                        checkpoint = next_checkpoint,
+                        "Not pruning a checkpoint with transactions yet to be executed"
+                    );
+                    break;
+                }
+            }
+        }
+        if pruned > 0 {
+            info!(pruned, next_checkpoint, "Pruned superseded object versions");
+        }
+        Ok(pruned)
+    }
+
+    /// Returns the transactions of `checkpoint` if it is older than the horizon and stored.
+    fn prunable_checkpoint_contents(
+        &self,
+        checkpoint: CheckpointSequenceNumber,
+    ) -> SuiResult<PrunableCheckpoint> {
+        let checkpoints = self.checkpoints.lock();
+        let latest = match checkpoints.latest_certified_checkpoint() {
+            Some(latest) => latest,
+            None => return Ok(PrunableCheckpoint::Retained),
+        };
+        if checkpoint > latest.sequence_number() {
+            return Ok(PrunableCheckpoint::Retained);
+        }
+        let missing = || -> SuiResult<PrunableCheckpoint> {
+            let next_stored = checkpoints
+                .next_checkpoint_with_contents(checkpoint)?
+                .filter(|next_stored| *next_stored <= latest.sequence_number());
+            Ok(PrunableCheckpoint::Missing(next_stored))
+        };
+        if let Some(num_checkpoints) = self.num_checkpoints_to_retain {
+            if checkpoint.saturating_add(num_checkpoints) > latest.sequence_number() {
+                return Ok(PrunableCheckpoint::Retained);
+            }
+        }
+        if let Some(num_epochs) = self.num_epochs_to_retain {
+            let epoch = match checkpoints.get_checkpoint(checkpoint)? {
+                Some(summary) => summary.epoch(),
+                None => return missing(),
+            };
+            if epoch.saturating_add(num_epochs) > latest.epoch() {
+                return Ok(PrunableCheckpoint::Retained);
+            }
+        }
+        Ok(match checkpoints.get_checkpoint_contents(checkpoint)? {
+            Some(contents) => PrunableCheckpoint::Prunable(contents.iter().cloned().collect()),
+            None => missing()?,
+        })
+    }
+}
@@ -0,0 +1,610 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! State snapshots hold the live object set of a node at a certified checkpoint, to bootstrap new
+//! nodes without replaying the history of the chain.
+//!
+//! A snapshot file starts with `STATE_SNAPSHOT_MAGIC`, followed by length-prefixed BCS records: a
+//! `StateSnapshotHeader`, then the certified earlier checkpoints holding the transactions that last
+//! wrote the objects, the effects of these transactions, every live object followed by its owner
+//! index entry, and a closing record with the counts.
+//!
+//! Every object is checked on import against the effects of the transaction that wrote it, or
+//! against the genesis objects, and every effects against the contents of a certified checkpoint.
+//! This proves that each object is a version written on chain, but not that it is still the latest
+//! version at the snapshot checkpoint, nor that no live object is missing, as checkpoints do not
+//! commit to the live object set.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{self, Read, Write};
+use std::iter;
+
+use parking_lot::Mutex;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info};
+
+use sui_types::base_types::{
+    ExecutionDigests, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, TransactionDigest,
+};
+use sui_types::committee::{Committee, EpochId};
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::fp_ensure;
+use sui_types::messages::SignedTransactionEffects;
+use sui_types::messages_checkpoint::{
+    AuthenticatedCheckpoint, CertifiedCheckpointSummary, CheckpointContents,
+    CheckpointSequenceNumber,
+};
+use sui_types::object::{Object, Owner};
+use typed_store::Map;
+
+use crate::authority::AuthorityStore;
+use crate::checkpoints::{CheckpointStore, CheckpointStoreTables};
+use crate::epoch::committee_store::CommitteeStore;
+
+#[cfg(test)]
+#[path = "unit_tests/state_snapshot_tests.rs"]
+mod state_snapshot_tests;
+
+pub const STATE_SNAPSHOT_MAGIC: [u8; 8] = *b"SUISNAP\0";
+pub const STATE_SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+/// Records larger than this are rejected when reading a snapshot. The largest records are the
+/// contents of busy checkpoints, at 64 bytes per transaction.
+const MAX_RECORD_SIZE: usize = 16 << 20;
+
+/// Number of objects written to the authority store at a time when importing a snapshot.
+const IMPORT_BATCH_SIZE: usize = 10_000;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StateSnapshotHeader {
+    pub format_version: u32,
+    /// The checkpoint the live object set is taken at.
+    pub checkpoint: CertifiedCheckpointSummary,
+    pub contents: CheckpointContents,
+    /// The committee of the epoch of `checkpoint`.
+    pub committee: Committee,
+    /// The certified last checkpoint of every epoch before the one of `checkpoint`, in epoch
+    /// order. Their next epoch committees link the genesis committee to `committee`.
+    pub epoch_last_checkpoints: Vec<CertifiedCheckpointSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum StateSnapshotRecord {
+    Checkpoint(CertifiedCheckpointSummary, CheckpointContents),
+    Object(Object),
+    OwnerIndexEntry(Owner, ObjectID, ObjectInfo),
+    Effects(SignedTransactionEffects),
+    End { objects: u64, effects: u64 },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StateSnapshotSummary {
+    pub checkpoint: CheckpointSequenceNumber,
+    pub epoch: EpochId,
+    pub objects: u64,
+    pub effects: u64,
+}
+
+/// Writes a snapshot of the live object set of a stopped node at `checkpoint`, or at its latest
+/// certified checkpoint. The objects written by transactions executed after the checkpoint are
+/// exported at their version before these transactions.
+pub fn export_state_snapshot<W: Write>(
+    store: &AuthorityStore,
+    checkpoints: &CheckpointStoreTables,
+    committees: &CommitteeStore,
+    checkpoint: Option<CheckpointSequenceNumber>,
+    mut writer: W,
+) -> SuiResult<StateSnapshotSummary> {
+    let authenticated = match checkpoint {
+        Some(sequence_number) => checkpoints.checkpoints.get(&sequence_number)?,
+        None => checkpoints
+            .checkpoints
+            .iter()
+            .skip_to_last()
+            .reverse()
+            .map(|(_, checkpoint)| checkpoint)
+            .find(|checkpoint| matches!(checkpoint, AuthenticatedCheckpoint::Certified(_))),
+    };
+    let checkpoint = match authenticated {
+        Some(AuthenticatedCheckpoint::Certified(checkpoint)) => checkpoint,
+        _ => {
+            return Err(SuiError::InvalidStateSnapshot(format!(
+                "No certified checkpoint {}",
+                checkpoint.map_or("found".to_string(), |seq| seq.to_string())
+            )))
+        }
+    };
+    let sequence_number = *checkpoint.summary.sequence_number();
+    let epoch = checkpoint.summary.epoch;
+    let contents = checkpoints
+        .checkpoint_contents
+        .get(&sequence_number)?
+        .ok_or_else(|| {
+            SuiError::StorageMissingFieldError(format!("Contents of checkpoint {sequence_number}"))
+        })?;
+    let committee = committees
+        .get_committee(&epoch)?
+        .ok_or_else(|| SuiError::StorageMissingFieldError(format!("Committee of epoch {epoch}")))?;
+    checkpoint.verify(&committee, Some(&contents))?;
+
+    let epoch_last_checkpoints: Vec<_> = checkpoints
+        .checkpoints
+        .iter()
+        .take_while(|(seq, _)| *seq < sequence_number)
+        .filter_map(|(_, checkpoint)| match checkpoint {
+            AuthenticatedCheckpoint::Certified(checkpoint)
+                if checkpoint.summary.next_epoch_committee.is_some() =>
+            {
+                Some(checkpoint)
+            }
+            _ => None,
+        })
+        .collect();
+    fp_ensure!(
+        epoch_last_checkpoints
+            .iter()
+            .map(|checkpoint| checkpoint.summary.epoch)
+            .eq(0..epoch),
+        SuiError::InvalidStateSnapshot(format!(
+            "The certified last checkpoints of the epochs before epoch {epoch} are missing"
+        ))
+    );
+
+    // Transactions executed after the checkpoint are either not checkpointed yet, or in a later
+    // checkpoint.
+    let mut later_transactions: Vec<_> = checkpoints
+        .extra_transactions
+        .keys()
+        .map(|digests| digests.transaction)
+        .collect();
+    for (_, later_contents) in checkpoints
+        .checkpoint_contents
+        .iter()
+        .skip_to(&(sequence_number + 1))?
+    {
+        later_transactions.extend(later_contents.iter().map(|digests| digests.transaction));
+    }
+    let earlier_versions = store.object_versions_before_transactions(&later_transactions)?;
+    debug!(
+        later_transactions = later_transactions.len(),
+        reverted_objects = earlier_versions.len(),
+        "Reverting the objects written after the checkpoint"
+    );
+
+    // Merge the live objects with the reverted ones, in object ID order.
+    let mut live_objects = store
+        .iter_live_object_set()
+        .filter(|object_ref| !earlier_versions.contains_key(&object_ref.0))
+        .map(|(object_id, version, _)| (object_id, version))
+        .peekable();
+    let mut reverted_objects = earlier_versions
+        .iter()
+        .filter_map(|(object_id, version)| version.map(|version| (*object_id, version)))
+        .peekable();
+    let object_keys: Vec<_> = iter::from_fn(|| {
+        let next_is_reverted = match (live_objects.peek(), reverted_objects.peek()) {
+            (Some(live), Some(reverted)) => reverted.0 < live.0,
+            (live, _) => live.is_none(),
+        };
+        if next_is_reverted {
+            reverted_objects.next()
+        } else {
+            live_objects.next()
+        }
+    })
+    .collect();
+
+    // The effects and checkpoints proving the objects precede them, so that the objects can be
+    // checked as they are imported.
+    let mut previous_transactions = BTreeSet::new();
+    for (object_id, version) in &object_keys {
+        let object = get_object(store, *object_id, *version)?;
+        if object.previous_transaction != TransactionDigest::genesis() {
+            previous_transactions.insert(object.previous_transaction);
+        }
+    }
+    let previous_transactions: Vec<_> = previous_transactions.into_iter().collect();
+    let previous_effects = previous_transactions
+        .iter()
+        .zip(
+            store
+                .perpetual_tables
+                .effects
+                .multi_get(&previous_transactions)?,
+        )
+        .map(|(digest, signed_effects)| {
+            signed_effects.ok_or(SuiError::TransactionNotFound { digest: *digest })
+        })
+        .collect::<SuiResult<Vec<_>>>()?;
+    let previous_digests: Vec<_> = previous_effects
+        .iter()
+        .map(|signed_effects| {
+            ExecutionDigests::new(
+                signed_effects.effects.transaction_digest,
+                *signed_effects.digest(),
+            )
+        })
+        .collect();
+    let mut earlier_checkpoints = BTreeSet::new();
+    for (digests, checkpoint) in previous_digests.iter().zip(
+        checkpoints
+            .transactions_to_checkpoint
+            .multi_get(&previous_digests)?,
+    ) {
+        match checkpoint {
+            Some(checkpoint) if checkpoint < sequence_number => {
+                earlier_checkpoints.insert(checkpoint);
+            }
+            Some(checkpoint) if checkpoint == sequence_number => (),
+            _ => {
+                return Err(SuiError::InvalidStateSnapshot(format!(
+                    "Transaction {:?} is not in checkpoint {sequence_number} or before",
+                    digests.transaction
+                )))
+            }
+        }
+    }
+
+    writer.write_all(&STATE_SNAPSHOT_MAGIC).map_err(io_error)?;
+    write_record(
+        &mut writer,
+        &StateSnapshotHeader {
+            format_version: STATE_SNAPSHOT_FORMAT_VERSION,
+            checkpoint,
+            contents,
+            committee,
+            epoch_last_checkpoints,
+        },
+    )?;
+
+    for earlier_checkpoint in earlier_checkpoints {
+        let summary = match checkpoints.checkpoints.get(&earlier_checkpoint)? {
+            Some(AuthenticatedCheckpoint::Certified(summary)) => summary,
+            _ => {
+                return Err(SuiError::InvalidStateSnapshot(format!(
+                    "Checkpoint {earlier_checkpoint} is not certified"
+                )))
+            }
+        };
+        let contents = checkpoints
+            .checkpoint_contents
+            .get(&earlier_checkpoint)?
+            .ok_or_else(|| {
+                SuiError::StorageMissingFieldError(format!(
+                    "Contents of checkpoint {earlier_checkpoint}"
+                ))
+            })?;
+        write_record(
+            &mut writer,
+            &StateSnapshotRecord::Checkpoint(summary, contents),
+        )?;
+    }
+
+    let mut effects = 0;
+    for signed_effects in previous_effects {
+        write_record(&mut writer, &StateSnapshotRecord::Effects(signed_effects))?;
+        effects += 1;
+    }
+
+    let mut objects = 0;
+    for (object_id, version) in object_keys {
+        let object = get_object(store, object_id, version)?;
+        let object_ref = object.compute_object_reference();
+        let info = ObjectInfo::new(&object_ref, &object);
+        let owner = object.owner;
+        write_record(&mut writer, &StateSnapshotRecord::Object(object))?;
+        write_record(
+            &mut writer,
+            &StateSnapshotRecord::OwnerIndexEntry(owner, object_id, info),
+        )?;
+        objects += 1;
+    }
+
+    write_record(&mut writer, &StateSnapshotRecord::End { objects, effects })?;
+    writer.flush().map_err(io_error)?;
+
+    Ok(StateSnapshotSummary {
+        checkpoint: sequence_number,
+        epoch,
+        objects,
+        effects,
+    })
+}
+
+fn get_object(
+    store: &AuthorityStore,
+    object_id: ObjectID,
+    version: SequenceNumber,
+) -> SuiResult<Object> {
+    store
+        .get_object_by_key(&object_id, version)?
+        .ok_or_else(|| {
+            SuiError::StorageMissingFieldError(format!("Object {object_id} at version {version}"))
+        })
+}
+
+/// Reads the header of a snapshot, e.g. to inspect it before importing it.
+pub fn read_state_snapshot_header<R: Read>(mut reader: R) -> SuiResult<StateSnapshotHeader> {
+    let mut magic = [0; STATE_SNAPSHOT_MAGIC.len()];
+    reader.read_exact(&mut magic).map_err(io_error)?;
+    fp_ensure!(
+        magic == STATE_SNAPSHOT_MAGIC,
+        SuiError::InvalidStateSnapshot("Not a state snapshot".into())
+    );
+    let header: StateSnapshotHeader = read_record(&mut reader)?;
+    fp_ensure!(
+        header.format_version == STATE_SNAPSHOT_FORMAT_VERSION,
+        SuiError::InvalidStateSnapshot(format!(
+            "Unsupported format version {}",
+            header.format_version
+        ))
+    );
+    Ok(header)
+}
+
+/// Returns the committees of the epochs after genesis up to the epoch of the snapshot checkpoint,
+/// after checking that each of them was certified by the committee of the previous epoch, and
+/// that the checkpoint is certified by the last one.
+pub fn verify_state_snapshot_header(
+    header: &StateSnapshotHeader,
+    genesis_committee: &Committee,
+) -> SuiResult<Vec<Committee>> {
+    let mut committee = genesis_committee.clone();
+    let mut committees = Vec::new();
+    for checkpoint in &header.epoch_last_checkpoints {
+        checkpoint.verify(&committee, None)?;
+        let next_committee = checkpoint
+            .summary
+            .next_epoch_committee
+            .as_ref()
+            .ok_or_else(|| {
+                SuiError::InvalidStateSnapshot(format!(
+                    "Checkpoint {} is not the last checkpoint of epoch {}",
+                    checkpoint.summary.sequence_number, checkpoint.summary.epoch
+                ))
+            })?;
+        committee = Committee::new(
+            committee.epoch + 1,
+            next_committee.iter().cloned().collect(),
+        )?;
+        committees.push(committee.clone());
+    }
+    fp_ensure!(
+        committee == header.committee,
+        SuiError::InvalidStateSnapshot(format!(
+            "The committee of epoch {} does not follow from the genesis committee",
+            header.committee.epoch
+        ))
+    );
+    header
+        .checkpoint
+        .verify(&committee, Some(&header.contents))?;
+    Ok(committees)
+}
+
+/// Fills the empty stores of a node from a snapshot, after verifying the snapshot checkpoint
+/// against the genesis committee. Records are verified before they are written: checkpoints
+/// against the committee of their epoch, effects against their signature and the contents of the
+/// checkpoints, and objects against the effects of the transaction that last wrote them, or
+/// against `genesis_objects`.
+///
+/// The stores are left partially filled if the snapshot turns out to be invalid or the import is
+/// interrupted. The snapshot checkpoint is stored last, but callers should import into fresh
+/// stores that they only start using once the import succeeds.
+pub async fn import_state_snapshot<R: Read>(
+    mut reader: R,
+    genesis_committee: &Committee,
+    genesis_objects: &[Object],
+    store: &AuthorityStore,
+    committee_store: &CommitteeStore,
+    checkpoint_store: &Mutex<CheckpointStore>,
+) -> SuiResult<StateSnapshotSummary> {
+    fp_ensure!(
+        store.database_is_empty()?,
+        SuiError::InvalidStateSnapshot("The authority store is not empty".into())
+    );
+    let header = read_state_snapshot_header(&mut reader)?;
+    let committees = verify_state_snapshot_header(&header, genesis_committee)?;
+    let sequence_number = *header.checkpoint.summary.sequence_number();
+    info!(
+        checkpoint = sequence_number,
+        epoch = header.committee.epoch,
+        "Importing state snapshot"
+    );
+    let committees: BTreeMap<_, _> = iter::once(genesis_committee)
+        .chain(&committees)
+        .map(|committee| (committee.epoch, committee))
+        .collect();
+    let committee_of = |epoch: EpochId| {
+        committees.get(&epoch).copied().ok_or_else(|| {
+            SuiError::InvalidStateSnapshot(format!("Epoch {epoch} is after the snapshot"))
+        })
+    };
+    let genesis_objects: HashSet<_> = genesis_objects
+        .iter()
+        .map(|object| object.compute_object_reference())
+        .collect();
+
+    // Transactions of the verified checkpoints, and objects written by the verified effects
+    let mut checkpointed: HashSet<ExecutionDigests> = header.contents.iter().cloned().collect();
+    let mut last_checkpoint = None;
+    let mut written_objects: HashMap<ObjectRef, (TransactionDigest, Owner)> = HashMap::new();
+    let mut unused_effects = HashSet::new();
+
+    let mut objects = Vec::with_capacity(IMPORT_BATCH_SIZE);
+    let mut objects_count = 0;
+    let mut last_object_id = None;
+    let mut unindexed_object: Option<(Owner, ObjectID, ObjectInfo)> = None;
+    let mut effects_count = 0;
+    loop {
+        let record: StateSnapshotRecord = read_record(&mut reader)?;
+        if !matches!(record, StateSnapshotRecord::OwnerIndexEntry(..)) {
+            fp_ensure!(
+                unindexed_object.is_none(),
+                SuiError::InvalidStateSnapshot("Missing owner index entry".into())
+            );
+        }
+        match record {
+            StateSnapshotRecord::Checkpoint(checkpoint, contents) => {
+                let seq = *checkpoint.summary.sequence_number();
+                fp_ensure!(
+                    effects_count == 0
+                        && objects_count == 0
+                        && last_checkpoint < Some(seq)
+                        && seq < sequence_number,
+                    SuiError::InvalidStateSnapshot(format!("Checkpoint {seq} is out of order"))
+                );
+                last_checkpoint = Some(seq);
+                checkpoint.verify(committee_of(checkpoint.summary.epoch)?, Some(&contents))?;
+                checkpointed.extend(contents.iter().cloned());
+            }
+            StateSnapshotRecord::Effects(signed_effects) => {
+                let digest = signed_effects.effects.transaction_digest;
+                fp_ensure!(
+                    objects_count == 0,
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Effects of transaction {digest:?} are out of order"
+                    ))
+                );
+                signed_effects.verify(committee_of(signed_effects.auth_signature.epoch)?)?;
+                fp_ensure!(
+                    checkpointed.contains(&ExecutionDigests::new(digest, *signed_effects.digest())),
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Effects of transaction {digest:?} are not in a certified checkpoint"
+                    ))
+                );
+                fp_ensure!(
+                    unused_effects.insert(digest),
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Duplicate effects of transaction {digest:?}"
+                    ))
+                );
+                for (object_ref, owner, _) in signed_effects.effects.all_mutated() {
+                    written_objects.insert(*object_ref, (digest, *owner));
+                }
+                store
+                    .perpetual_tables
+                    .effects
+                    .insert(&digest, &signed_effects)?;
+                effects_count += 1;
+            }
+            StateSnapshotRecord::Object(object) => {
+                let object_ref = object.compute_object_reference();
+                fp_ensure!(
+                    last_object_id < Some(object_ref.0),
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Object {} is out of order",
+                        object_ref.0
+                    ))
+                );
+                last_object_id = Some(object_ref.0);
+                let verified = if object.previous_transaction == TransactionDigest::genesis() {
+                    genesis_objects.contains(&object_ref)
+                } else {
+                    unused_effects.remove(&object.previous_transaction);
+                    written_objects.get(&object_ref)
+                        == Some(&(object.previous_transaction, object.owner))
+                };
+                fp_ensure!(
+                    verified,
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Object {:?} was not written by its previous transaction {:?}",
+                        object_ref, object.previous_transaction
+                    ))
+                );
+                unindexed_object = Some((
+                    object.owner,
+                    object_ref.0,
+                    ObjectInfo::new(&object_ref, &object),
+                ));
+                objects.push(object);
+                objects_count += 1;
+                if objects.len() == IMPORT_BATCH_SIZE {
+                    store
+                        .bulk_object_insert(&objects.iter().collect::<Vec<_>>())
+                        .await?;
+                    objects.clear();
+                }
+            }
+            StateSnapshotRecord::OwnerIndexEntry(owner, object_id, info) => {
+                fp_ensure!(
+                    unindexed_object.take() == Some((owner, object_id, info)),
+                    SuiError::InvalidStateSnapshot(format!(
+                        "Owner index entry of object {object_id} does not match the object"
+                    ))
+                );
+            }
+            StateSnapshotRecord::End {
+                objects: expected_objects,
+                effects: expected_effects,
+            } => {
+                fp_ensure!(
+                    objects_count == expected_objects
+                        && effects_count == expected_effects
+                        && unused_effects.is_empty(),
+                    SuiError::InvalidStateSnapshot("Missing objects or effects".into())
+                );
+                break;
+            }
+        }
+    }
+    store
+        .bulk_object_insert(&objects.iter().collect::<Vec<_>>())
+        .await?;
+
+    for committee in committees.values().skip(1) {
+        committee_store.insert_new_committee(committee)?;
+    }
+    checkpoint_store.lock().bootstrap_from_snapshot_checkpoint(
+        &header.checkpoint,
+        &header.contents,
+        &header.committee,
+    )?;
+
+    let summary = StateSnapshotSummary {
+        checkpoint: sequence_number,
+        epoch: header.committee.epoch,
+        objects: objects_count,
+        effects: effects_count,
+    };
+    info!(?summary, "Imported state snapshot");
+    Ok(summary)
+}
+
+fn write_record<W: Write, T: Serialize>(writer: &mut W, record: &T) -> SuiResult {
+    let bytes = bcs::to_bytes(record).map_err(|e| SuiError::GenericStorageError(e.to_string()))?;
+    writer
+        .write_all(&(bytes.len() as u32).to_le_bytes())
+        .and_then(|_| writer.write_all(&bytes))
+        .map_err(io_error)
+}
+
+fn read_record<R: Read, T: DeserializeOwned>(reader: &mut R) -> SuiResult<T> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len).map_err(io_error)?;
+    let len = u32::from_le_bytes(len) as usize;
+    fp_ensure!(
+        len <= MAX_RECORD_SIZE,
+        SuiError::InvalidStateSnapshot(format!("Record of {len} bytes is too large"))
+    );
+    // The buffer grows with the bytes actually read, rather than with the untrusted length.
+    let mut bytes = Vec::new();
+    reader
+        .take(len as u64)
+        .read_to_end(&mut bytes)
+        .map_err(io_error)?;
+    fp_ensure!(
+        bytes.len() == len,
+        SuiError::InvalidStateSnapshot("Truncated snapshot".into())
+    );
+    bcs::from_bytes(&bytes).map_err(|e| SuiError::InvalidStateSnapshot(e.to_string()))
+}
+
+fn io_error(error: io::Error) -> SuiError {
+    if error.kind() == io::ErrorKind::UnexpectedEof {
+        SuiError::InvalidStateSnapshot("Truncated snapshot".into())
+    } else {
+        SuiError::GenericStorageError(error.to_string())
+    }
+}
@@ -0,0 +1,490 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Re-execution of a certified transaction outside of the validators, on the exact versions of
+//! the objects it was executed on, to investigate effects that do not match expectations.
+//!
+//! The replay reports the gas cost summary of the effects, not a breakdown of the gas charged per
+//! instruction, which the Move VM does not expose. A trace of the instructions executed is only
+//! written by debug builds of the Move VM, through the `MOVE_VM_TRACE` environment variable.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use move_binary_format::CompiledModule;
+use move_core_types::language_storage::TypeTag;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use sui_adapter::adapter;
+use sui_types::base_types::{ObjectDigest, ObjectID, ObjectRef, SequenceNumber, TransactionDigest};
+use sui_types::committee::EpochId;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::gas::{self, SuiGasStatus};
+use sui_types::messages::{
+    CertifiedTransaction, InputObjectKind, InputObjects, ObjectInfoRequest, ObjectInfoRequestKind,
+    SingleTransactionKind, TransactionEffects,
+};
+use sui_types::object::Object;
+use sui_types::storage::{BackingPackageStore, ParentSync};
+use sui_types::sui_system_state::SuiSystemState;
+use sui_types::temporary_store::TemporaryStore;
+use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS, SUI_SYSTEM_STATE_OBJECT_ID};
+
+use crate::authority::AuthorityStore;
+use crate::authority_aggregator::AuthorityAggregator;
+use crate::authority_client::AuthorityAPI;
+use crate::execution_engine;
+
+#[cfg(test)]
+#[path = "unit_tests/transaction_replay_tests.rs"]
+mod transaction_replay_tests;
+
+/// Where the certificate, the certified effects and the input objects of a replayed transaction
+/// are read from.
+#[async_trait]
+pub trait ReplaySource {
+    async fn get_certificate_and_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<(CertifiedTransaction, TransactionEffects)>;
+
+    /// The given version of an object, None if it is unknown or pruned.
+    async fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>>;
+
+    /// The latest version of an object, which is the only version of packages.
+    async fn get_latest_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>>;
+}
+
+/// Replays transactions from the database of a stopped node.
+#[async_trait]
+impl ReplaySource for AuthorityStore {
+    async fn get_certificate_and_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<(CertifiedTransaction, TransactionEffects)> {
+        let certificate = self
+            .read_certificate(digest)?
+            .ok_or(SuiError::TransactionNotFound { digest: *digest })?;
+        Ok((certificate, self.get_effects(digest)?))
+    }
+
+    async fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        AuthorityStore::get_object_by_key(self, object_id, version)
+    }
+
+    async fn get_latest_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        self.get_object(object_id)
+    }
+}
+
+/// Replays transactions from the validators. The effects compared to are the ones signed by the
+/// validator that returned the certificate, and objects are read from the first validator that
+/// has them.
+#[async_trait]
+impl<A> ReplaySource for AuthorityAggregator<A>
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    async fn get_certificate_and_effects(
+        &self,
+        digest: &TransactionDigest,
+    ) -> SuiResult<(CertifiedTransaction, TransactionEffects)> {
+        let info = self.handle_cert_info_request(digest, None).await?;
+        match (info.certified_transaction, info.signed_effects) {
+            (Some(certificate), Some(effects)) => Ok((certificate, effects.effects)),
+            _ => Err(SuiError::TransactionNotFound { digest: *digest }),
+        }
+    }
+
+    async fn get_object_by_key(
+        &self,
+        object_id: &ObjectID,
+        version: SequenceNumber,
+    ) -> SuiResult<Option<Object>> {
+        let request = ObjectInfoRequest {
+            object_id: *object_id,
+            request_kind: ObjectInfoRequestKind::PastObjectInfoDebug(version, None),
+        };
+        get_object_from_any(self, request).await
+    }
+
+    async fn get_latest_object(&self, object_id: &ObjectID) -> SuiResult<Option<Object>> {
+        let request = ObjectInfoRequest::latest_object_info_request(*object_id, None);
+        get_object_from_any(self, request).await
+    }
+}
+
+/// Fetches an object from the first validator that has it. Fails only if all validators failed.
+async fn get_object_from_any<A>(
+    aggregator: &AuthorityAggregator<A>,
+    request: ObjectInfoRequest,
+) -> SuiResult<Option<Object>>
+where
+    A: AuthorityAPI + Send + Sync + Clone + 'static,
+{
+    let mut error = None;
+    let mut answered = false;
+    for (name, client) in &aggregator.authority_clients {
+        match client
+            .handle_object_info_request(request.clone(), false)
+            .await
+        {
+            Ok(response) => match response.object_and_lock {
+                Some(object_and_lock) => return Ok(Some(object_and_lock.object)),
+                None => answered = true,
+            },
+            Err(e) => {
+                debug!(authority = ?name, "Failed to fetch {:?}: {e}", request.object_id);
+                error = Some(e);
+            }
+        }
+    }
+    match error {
+        Some(e) if !answered => Err(e),
+        _ => Ok(None),
+    }
+}
+
+/// The outcome of the replay of a transaction.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransactionReplay {
+    pub certificate: CertifiedTransaction,
+    pub certified_effects: TransactionEffects,
+    pub replayed_effects: TransactionEffects,
+    /// The error the replayed execution failed with, if it did.
+    pub execution_error: Option<String>,
+    /// The packages loaded for the execution.
+    pub packages: Vec<ObjectRef>,
+    /// The names of the fields of the effects that differ between the certified and the replayed
+    /// effects.
+    pub differences: Vec<String>,
+}
+
+impl TransactionReplay {
+    pub fn effects_match(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// The objects the execution reads besides its input objects: the packages it links to, and the
+/// `parent_sync` entries of the wrapped objects it unwraps or deletes.
+struct ReplayStore {
+    packages: BTreeMap<ObjectID, Object>,
+    parent_entries: BTreeMap<ObjectID, ObjectRef>,
+}
+
+impl BackingPackageStore for ReplayStore {
+    fn get_package(&self, package_id: &ObjectID) -> SuiResult<Option<Object>> {
+        Ok(self.packages.get(package_id).cloned())
+    }
+}
+
+impl ParentSync for ReplayStore {
+    fn get_latest_parent_entry_ref(&self, object_id: ObjectID) -> SuiResult<Option<ObjectRef>> {
+        Ok(self.parent_entries.get(&object_id).copied())
+    }
+}
+
+/// Re-executes the transaction `digest` in-process on the versions of its input objects recorded
+/// in its certificate and certified effects, and compares the effects.
+///
+/// The `parent_sync` entries of the wrapped objects the transaction unwraps or deletes are derived
+/// from the versions of these objects in the certified effects, which is one below. The storage
+/// gas price is the one of the version of the system state the transaction read if it did, of the
+/// last version of the epoch of the certificate otherwise.
+pub async fn replay_transaction<S>(
+    source: &S,
+    digest: &TransactionDigest,
+) -> SuiResult<TransactionReplay>
+where
+    S: ReplaySource + Sync + ?Sized,
+{
+    let (certificate, certified_effects) = source.get_certificate_and_effects(digest).await?;
+    let data = certificate.signed_data.data.clone();
+
+    let input_kinds = data.input_objects()?;
+    let mut objects = BTreeMap::new();
+    let mut package_ids = BTreeSet::new();
+    for kind in &input_kinds {
+        let (id, version) = match kind {
+            InputObjectKind::MovePackage(id) => {
+                package_ids.insert(*id);
+                continue;
+            }
+            InputObjectKind::ImmOrOwnedMoveObject((id, version, _)) => (*id, *version),
+            // Shared objects are referenced by ID in the transaction, their input versions are
+            // the ones recorded in the effects.
+            InputObjectKind::SharedMoveObject(id) => {
+                let version = certified_effects
+                    .shared_objects
+                    .iter()
+                    .find(|object_ref| object_ref.0 == *id)
+                    .map(|object_ref| object_ref.1)
+                    .ok_or(SuiError::ObjectNotFound { object_id: *id })?;
+                (*id, version)
+            }
+        };
+        let object = source.get_object_by_key(&id, version).await?.ok_or(
+            SuiError::ObjectVersionNotFound {
+                object_id: id,
+                version,
+            },
+        )?;
+        if let Some(type_) = object.data.type_() {
+            collect_packages(&TypeTag::Struct(type_.clone()), &mut package_ids);
+        }
+        objects.insert(id, object);
+    }
+    for call in data.move_calls() {
+        for type_argument in &call.type_arguments {
+            collect_packages(type_argument, &mut package_ids);
+        }
+    }
+    for transaction in data.kind.single_transactions() {
+        if let SingleTransactionKind::Publish(publish) = transaction {
+            for module in &publish.modules {
+                package_ids.extend(module_dependencies(module)?);
+            }
+        }
+    }
+    let packages = load_packages(source, package_ids).await?;
+    let input_objects: Vec<_> = input_kinds
+        .into_iter()
+        .map(|kind| {
+            let object = match kind {
+                InputObjectKind::MovePackage(id) => packages[&id].clone(),
+                _ => objects[&kind.object_id()].clone(),
+            };
+            (kind, object)
+        })
+        .collect();
+
+    let gas_status = replay_gas_status(source, &certificate, &input_objects).await?;
+    let input_ids: BTreeSet<ObjectID> = input_objects.iter().map(|(_, o)| o.id()).collect();
+    let parent_entries = certified_effects
+        .unwrapped
+        .iter()
+        .map(|(object_ref, _)| object_ref)
+        .chain(&certified_effects.deleted)
+        .filter(|object_ref| !input_ids.contains(&object_ref.0))
+        .map(|(id, version, _)| {
+            Ok((
+                *id,
+                (
+                    *id,
+                    version.decrement()?,
+                    ObjectDigest::OBJECT_DIGEST_WRAPPED,
+                ),
+            ))
+        })
+        .collect::<SuiResult<_>>()?;
+    let package_refs = packages
+        .values()
+        .map(|package| package.compute_object_reference())
+        .collect();
+    let store = ReplayStore {
+        packages,
+        parent_entries,
+    };
+
+    let input_objects = InputObjects::new(input_objects);
+    let shared_object_refs = input_objects.filter_shared_objects();
+    let transaction_dependencies = input_objects.transaction_dependencies();
+    let temporary_store = TemporaryStore::new(store, input_objects, *digest);
+    let native_functions =
+        sui_framework::natives::all_natives(MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS);
+    let move_vm = Arc::new(
+        adapter::new_move_vm(native_functions.clone())
+            .expect("We defined natives to not fail here"),
+    );
+    let (_, replayed_effects, execution_error) = execution_engine::execute_transaction_to_effects(
+        shared_object_refs,
+        temporary_store,
+        data,
+        *digest,
+        transaction_dependencies,
+        &move_vm,
+        &native_functions,
+        gas_status,
+        certificate.auth_sign_info.epoch,
+    );
+
+    Ok(TransactionReplay {
+        differences: effects_differences(&certified_effects, &replayed_effects),
+        certificate,
+        certified_effects,
+        replayed_effects,
+        execution_error: execution_error.map(|e| e.to_string()),
+        packages: package_refs,
+    })
+}
+
+/// Loads the packages `package_ids` and the packages they depend on, transitively.
+async fn load_packages<S>(
+    source: &S,
+    package_ids: BTreeSet<ObjectID>,
+) -> SuiResult<BTreeMap<ObjectID, Object>>
+where
+    S: ReplaySource + Sync + ?Sized,
+{
+    let mut packages = BTreeMap::new();
+    let mut pending: Vec<_> = package_ids.into_iter().collect();
+    while let Some(id) = pending.pop() {
+        if packages.contains_key(&id) {
+            continue;
+        }
+        let package = source
+            .get_latest_object(&id)
+            .await?
+            .ok_or(SuiError::ObjectNotFound { object_id: id })?;
+        let modules = match package.data.try_as_package() {
+            Some(package) => package.serialized_module_map(),
+            None => return Err(SuiError::MoveObjectAsPackage { object_id: id }),
+        };
+        for module in modules.values() {
+            pending.extend(module_dependencies(module)?);
+        }
+        packages.insert(id, package);
+    }
+    Ok(packages)
+}
+
+/// The packages a serialized module links to, itself included.
+fn module_dependencies(module: &[u8]) -> SuiResult<impl Iterator<Item = ObjectID>> {
+    let module = CompiledModule::deserialize(module).map_err(|e| {
+        SuiError::ModuleDeserializationFailure {
+            error: e.to_string(),
+        }
+    })?;
+    let dependencies: Vec<_> = module
+        .module_handles
+        .iter()
+        .map(|handle| ObjectID::from(*module.address_identifier_at(handle.address)))
+        .collect();
+    Ok(dependencies.into_iter())
+}
+
+/// Adds the packages defining the structs of `type_` to `package_ids`.
+fn collect_packages(type_: &TypeTag, package_ids: &mut BTreeSet<ObjectID>) {
+    match type_ {
+        TypeTag::Struct(tag) => {
+            package_ids.insert(ObjectID::from(tag.address));
+            for type_param in &tag.type_params {
+                collect_packages(type_param, package_ids);
+            }
+        }
+        TypeTag::Vector(type_) => collect_packages(type_, package_ids),
+        _ => (),
+    }
+}
+
+/// The gas status the transaction was executed with. The balance of the gas object is not
+/// checked, as the certificate proves that it was.
+async fn replay_gas_status<S>(
+    source: &S,
+    certificate: &CertifiedTransaction,
+    input_objects: &[(InputObjectKind, Object)],
+) -> SuiResult<SuiGasStatus<'static>>
+where
+    S: ReplaySource + Sync + ?Sized,
+{
+    let data = &certificate.signed_data.data;
+    if data.kind.is_system_tx() {
+        return Ok(SuiGasStatus::new_unmetered());
+    }
+    let system_state = match input_objects
+        .iter()
+        .find(|(_, object)| object.id() == SUI_SYSTEM_STATE_OBJECT_ID)
+    {
+        Some((_, object)) => parse_system_state(object)?,
+        None => system_state_of_epoch(source, certificate.auth_sign_info.epoch).await?,
+    };
+    let mut gas_status = gas::start_gas_metering(
+        data.gas_budget,
+        data.gas_price,
+        system_state.parameters.storage_gas_price,
+    )?;
+    if certificate.contains_shared_object() {
+        gas_status.charge_consensus()?;
+    }
+    Ok(gas_status)
+}
+
+/// The last version of the system state in `epoch`, whose parameters the transactions of the epoch
+/// were executed with, found by walking back the versions of the system state from the latest.
+async fn system_state_of_epoch<S>(source: &S, epoch: EpochId) -> SuiResult<SuiSystemState>
+where
+    S: ReplaySource + Sync + ?Sized,
+{
+    let mut object = source
+        .get_latest_object(&SUI_SYSTEM_STATE_OBJECT_ID)
+        .await?
+        .ok_or(SuiError::ObjectNotFound {
+            object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+        })?;
+    loop {
+        let system_state = parse_system_state(&object)?;
+        if system_state.epoch <= epoch {
+            return Ok(system_state);
+        }
+        let version = object.version().decrement()?;
+        object = source
+            .get_object_by_key(&SUI_SYSTEM_STATE_OBJECT_ID, version)
+            .await?
+            .ok_or(SuiError::ObjectVersionNotFound {
+                object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+                version,
+            })?;
+    }
+}
+
+fn parse_system_state(object: &Object) -> SuiResult<SuiSystemState> {
+    object
+        .data
+        .try_as_move()
+        .and_then(|object| bcs::from_bytes(object.contents()).ok())
+        .ok_or(SuiError::ObjectNotFound {
+            object_id: SUI_SYSTEM_STATE_OBJECT_ID,
+        })
+}
+
+/// The names of the fields that differ between two effects of the same transaction.
+pub fn effects_differences(
+    certified: &TransactionEffects,
+    replayed: &TransactionEffects,
+) -> Vec<String> {
+    let mut differences = Vec::new();
+    let mut compare = |field: &str, equal: bool| {
+        if !equal {
+            differences.push(field.to_string());
+        }
+    };
+    compare("status", certified.status == replayed.status);
+    compare("gas_used", certified.gas_used == replayed.gas_used);
+    compare(
+        "shared_objects",
+        certified.shared_objects == replayed.shared_objects,
+    );
+    compare("created", certified.created == replayed.created);
+    compare("mutated", certified.mutated == replayed.mutated);
+    compare("unwrapped", certified.unwrapped == replayed.unwrapped);
+    compare("deleted", certified.deleted == replayed.deleted);
+    compare("wrapped", certified.wrapped == replayed.wrapped);
+    compare("gas_object", certified.gas_object == replayed.gas_object);
+    compare("events", certified.events == replayed.events);
+    compare(
+        "dependencies",
+        certified.dependencies == replayed.dependencies,
+    );
+    differences
+}
@@ -1,20 +1,29 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use futures::Stream;
+use std::sync::Arc;
 
-use sui_types::messages::TxCertAndSignedEffects;
+use futures::{future, stream, Stream, StreamExt};
 
-use sui_types::filter::TransactionFilter;
+use sui_types::base_types::TransactionDigest;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::{SuiError, SuiResult};
+use sui_types::messages::{TransactionInfoResponse, TxCertAndSignedEffects};
+
+use sui_types::filter::{Filter, TransactionFilter};
 
 use tracing::error;
 
+use super::authority::AuthorityState;
 use super::streamer::Streamer;
 
 const CHANNEL_SIZE: usize = 1000;
 
+/// Number of executed transactions read at a time when replaying transactions to a subscriber
+const REPLAY_PAGE_SIZE: u64 = 100;
+
 pub struct TransactionStreamer {
-    streamer: Streamer<TxCertAndSignedEffects, TransactionFilter>,
+    streamer: Streamer<(TxSequenceNumber, TxCertAndSignedEffects), TransactionFilter>,
 }
 
 impl TransactionStreamer {
@@ -27,13 +36,90 @@ impl TransactionStreamer {
     pub fn subscribe(
         &self,
         filter: TransactionFilter,
-    ) -> impl Stream<Item = TxCertAndSignedEffects> {
+    ) -> impl Stream<Item = (TxSequenceNumber, TxCertAndSignedEffects)> {
         self.streamer.subscribe(filter)
     }
 
-    pub async fn enqueue(&self, tx: TxCertAndSignedEffects) -> bool {
+    /// Subscribes to transactions matching `filter`, first replaying the transactions executed
+    /// by `state` from sequence number `start_seq` (inclusive) and then switching over to live
+    /// transactions.
+    /// The live subscription is opened before the replay starts and live transactions which
+    /// were already replayed are skipped, so there is no gap and no duplicate at the switch over.
+    pub fn subscribe_from(
+        &self,
+        state: Arc<AuthorityState>,
+        filter: TransactionFilter,
+        start_seq: TxSequenceNumber,
+    ) -> impl Stream<Item = SuiResult<(TxSequenceNumber, TxCertAndSignedEffects)>> {
+        let live = self.subscribe(filter.clone());
+
+        let replay = stream::unfold(Some(start_seq), move |next_seq| {
+            let state = state.clone();
+            async move {
+                let next_seq = next_seq?;
+                let page = match state
+                    .database
+                    .transactions_in_seq_range(next_seq, next_seq + REPLAY_PAGE_SIZE)
+                {
+                    Ok(page) if page.is_empty() => return None,
+                    Ok(page) => page,
+                    Err(e) => return Some((vec![Err(e)], None)),
+                };
+                let next_seq = page.last().map(|(seq, _)| seq + 1);
+                let txs = page
+                    .into_iter()
+                    .map(|(seq, digests)| Self::load_executed_tx(&state, seq, &digests.transaction))
+                    .collect::<Vec<_>>();
+                Some((txs, next_seq))
+            }
+        })
+        .flat_map(stream::iter)
+        .filter(move |tx| {
+            future::ready(match tx {
+                Ok(tx) => filter.matches(tx),
+                Err(_) => true,
+            })
+        });
+
+        let mut last_replayed = None;
+        replay
+            .map(|tx| (true, tx))
+            .chain(live.map(|tx| (false, Ok(tx))))
+            .filter_map(move |(replayed, tx)| {
+                let keep = match &tx {
+                    Ok((seq, _)) if replayed => {
+                        last_replayed = Some(*seq);
+                        true
+                    }
+                    Ok((seq, _)) => {
+                        *seq >= start_seq && last_replayed.map_or(true, |last| *seq > last)
+                    }
+                    Err(_) => true,
+                };
+                future::ready(keep.then_some(tx))
+            })
+    }
+
+    fn load_executed_tx(
+        state: &AuthorityState,
+        seq: TxSequenceNumber,
+        digest: &TransactionDigest,
+    ) -> SuiResult<(TxSequenceNumber, TxCertAndSignedEffects)> {
+        match state.database.get_signed_transaction_info(digest)? {
+            TransactionInfoResponse {
+                certified_transaction: Some(cert),
+                signed_effects: Some(effects),
+                ..
+            } => Ok((seq, (cert, effects))),
+            _ => Err(SuiError::CertificateNotfound {
+                certificate_digest: *digest,
+            }),
+        }
+    }
+
+    pub async fn enqueue(&self, seq: TxSequenceNumber, tx: TxCertAndSignedEffects) -> bool {
         let tx_digest = *tx.0.digest();
-        if let Err(e) = self.streamer.send(tx).await {
+        if let Err(e) = self.streamer.send((seq, tx)).await {
             error!(?tx_digest, error =? e, "Failed to send tx to dispatch");
             return false;
         }
@@ -63,10 +149,11 @@ mod tests {
         let tx_cert = tx_certs.swap_remove(0);
         let tx_digest = *tx_cert.digest();
         let signed_effects = signed_effects.swap_remove(0);
-        let result = streamer.enqueue((tx_cert, signed_effects.clone())).await;
+        let result = streamer.enqueue(7, (tx_cert, signed_effects.clone())).await;
 
         assert!(result);
-        if let Some((cert, effects)) = stream.next().await {
+        if let Some((seq, (cert, effects))) = stream.next().await {
+            assert_eq!(seq, 7);
             assert_eq!(cert.digest(), &tx_digest);
             assert_eq!(effects, signed_effects);
         } else {
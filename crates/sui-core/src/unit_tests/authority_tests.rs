@@ -26,6 +26,7 @@ use std::task::{Context, Poll};
 
 use std::{convert::TryInto, env};
 use sui_adapter::genesis;
+use sui_json_rpc_types::{BalanceChange, SuiObjectChangeKind};
 use sui_types::{
     base_types::dbg_addr,
     crypto::{get_key_pair, Signature},
@@ -162,8 +163,36 @@ async fn test_dry_run_transaction() {
 
     let response = authority
         .dry_run_transaction(&transaction, transaction_digest)
-        .await;
-    assert!(response.is_ok());
+        .await
+        .unwrap();
+
+    // The sender only pays for gas.
+    let gas_used = &response.effects.gas_used;
+    let gas_charge = gas_used.computation_cost as i128 + gas_used.storage_cost as i128
+        - gas_used.storage_rebate as i128;
+    assert_eq!(
+        response.balance_changes,
+        vec![BalanceChange {
+            owner: Owner::AddressOwner(transaction.sender_address()),
+            coin_type: sui_types::gas_coin::GAS::type_tag().to_string(),
+            amount: -gas_charge,
+        }]
+    );
+    let gas_object_change = response
+        .object_changes
+        .iter()
+        .find(|change| change.object_id == gas_object_id)
+        .unwrap();
+    assert_eq!(gas_object_change.kind, SuiObjectChangeKind::Mutated);
+    assert_eq!(
+        gas_object_change.owner_before,
+        gas_object_change.owner_after
+    );
+    assert!(response
+        .object_changes
+        .iter()
+        .any(|change| change.object_id == shared_object_id
+            && change.kind == SuiObjectChangeKind::Mutated));
 
     // Make sure that objects are not mutated after dry run.
     let gas_object_version = authority
@@ -182,6 +211,87 @@ async fn test_dry_run_transaction() {
     assert_eq!(shared_object_version, SequenceNumber::MIN);
 }
 
+#[tokio::test]
+async fn test_dry_run_coin_transfer() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let recipient = dbg_addr(2);
+    let coin_id = ObjectID::random();
+    let gas_object_id = ObjectID::random();
+    let authority_state =
+        init_state_with_ids(vec![(sender, coin_id), (sender, gas_object_id)]).await;
+    let coin = authority_state.get_object(&coin_id).await.unwrap().unwrap();
+    let gas_object = authority_state
+        .get_object(&gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let transaction = init_transfer_transaction(
+        sender,
+        &sender_key,
+        recipient,
+        coin.compute_object_reference(),
+        gas_object.compute_object_reference(),
+    );
+
+    let response = authority_state
+        .dry_run_transaction(&transaction, *transaction.digest())
+        .await
+        .unwrap();
+
+    // The sender pays the coin and the gas, the recipient gets the coin.
+    let gas_used = &response.effects.gas_used;
+    let gas_charge = gas_used.computation_cost as i128 + gas_used.storage_cost as i128
+        - gas_used.storage_rebate as i128;
+    let coin_type = sui_types::gas_coin::GAS::type_tag().to_string();
+    let balance_change = |owner| {
+        response
+            .balance_changes
+            .iter()
+            .find(|change| change.owner == Owner::AddressOwner(owner))
+            .cloned()
+    };
+    assert_eq!(response.balance_changes.len(), 2);
+    assert_eq!(
+        balance_change(sender),
+        Some(BalanceChange {
+            owner: Owner::AddressOwner(sender),
+            coin_type: coin_type.clone(),
+            amount: -(GAS_VALUE_FOR_TESTING as i128) - gas_charge,
+        })
+    );
+    assert_eq!(
+        balance_change(recipient),
+        Some(BalanceChange {
+            owner: Owner::AddressOwner(recipient),
+            coin_type,
+            amount: GAS_VALUE_FOR_TESTING as i128,
+        })
+    );
+
+    let coin_change = response
+        .object_changes
+        .iter()
+        .find(|change| change.object_id == coin_id)
+        .unwrap();
+    assert_eq!(coin_change.kind, SuiObjectChangeKind::Mutated);
+    assert_eq!(coin_change.owner_before, Some(Owner::AddressOwner(sender)));
+    assert_eq!(
+        coin_change.owner_after,
+        Some(Owner::AddressOwner(recipient))
+    );
+
+    // Amounts are serialized as strings, as they can exceed the precision of JSON numbers.
+    let json = serde_json::to_value(balance_change(sender).unwrap()).unwrap();
+    assert_eq!(
+        json["amount"],
+        (-(GAS_VALUE_FOR_TESTING as i128) - gas_charge).to_string()
+    );
+
+    // The coin is not transferred by the dry run.
+    let coin = authority_state.get_object(&coin_id).await.unwrap().unwrap();
+    assert_eq!(coin.owner, Owner::AddressOwner(sender));
+}
+
 #[tokio::test]
 async fn test_handle_transfer_transaction_bad_signature() {
     let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
@@ -1608,6 +1718,44 @@ async fn test_get_latest_parent_entry() {
     assert_eq!(obj_ref.1, SequenceNumber::from(3));
     assert_eq!(obj_ref.2, ObjectDigest::OBJECT_DIGEST_DELETED);
     assert_eq!(effects.transaction_digest, tx);
+
+    // Batched lookups return the same entries, in the order of the object IDs
+    let object_ids = vec![
+        new_object_id2,
+        unknown_object_id,
+        gas_object_id,
+        new_object_id1,
+        new_object_id2,
+    ];
+    let mut expected = Vec::new();
+    for object_id in &object_ids {
+        expected.push(
+            authority_state
+                .get_latest_parent_entry(*object_id)
+                .await
+                .unwrap(),
+        );
+    }
+    assert_eq!(
+        authority_state
+            .database
+            .multi_get_latest_parent_entries(&object_ids)
+            .unwrap(),
+        expected
+    );
+    let reads = authority_state
+        .multi_get_object_read(&object_ids)
+        .await
+        .unwrap();
+    assert!(
+        matches!(&reads[0], Ok(ObjectRead::Exists(obj_ref, ..)) if obj_ref.0 == new_object_id2)
+    );
+    assert!(matches!(&reads[1], Ok(ObjectRead::NotExists(id)) if *id == unknown_object_id));
+    assert!(matches!(&reads[2], Ok(ObjectRead::Exists(obj_ref, ..)) if obj_ref.0 == gas_object_id));
+    assert!(matches!(&reads[3], Ok(ObjectRead::Deleted(obj_ref)) if obj_ref.0 == new_object_id1));
+    assert!(
+        matches!(&reads[4], Ok(ObjectRead::Exists(obj_ref, ..)) if obj_ref.0 == new_object_id2)
+    );
 }
 
 #[tokio::test]
@@ -2070,6 +2218,66 @@ pub fn init_transfer_transaction(
     to_sender_signed_transaction(data, secret)
 }
 
+/// An authority where `sender` owns `object_ids` and a gas object, for tests of transfers.
+#[cfg(test)]
+pub struct TransferTestState {
+    pub authority_state: Arc<AuthorityState>,
+    pub sender: SuiAddress,
+    pub sender_key: AccountKeyPair,
+    pub object_ids: Vec<ObjectID>,
+    pub gas_object_id: ObjectID,
+}
+
+#[cfg(test)]
+pub async fn init_state_for_transfers(num_objects: usize) -> TransferTestState {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let object_ids: Vec<_> = (0..num_objects).map(|_| ObjectID::random()).collect();
+    let gas_object_id = ObjectID::random();
+    let authority_state = init_state_with_ids(
+        object_ids
+            .iter()
+            .chain([&gas_object_id])
+            .map(|object_id| (sender, *object_id)),
+    )
+    .await;
+    TransferTestState {
+        authority_state: Arc::new(authority_state),
+        sender,
+        sender_key,
+        object_ids,
+        gas_object_id,
+    }
+}
+
+#[cfg(test)]
+impl TransferTestState {
+    /// Transfers the latest version of `object_id` to `dbg_addr(2)`, returning the effects.
+    pub async fn transfer(&self, object_id: ObjectID) -> TransactionEffects {
+        let transaction = init_transfer_transaction(
+            self.sender,
+            &self.sender_key,
+            dbg_addr(2),
+            self.latest_ref(object_id).await,
+            self.latest_ref(self.gas_object_id).await,
+        );
+        send_and_confirm_transaction(&self.authority_state, transaction)
+            .await
+            .unwrap()
+            .signed_effects
+            .unwrap()
+            .effects
+    }
+
+    pub async fn latest_ref(&self, object_id: ObjectID) -> ObjectRef {
+        self.authority_state
+            .get_object(&object_id)
+            .await
+            .unwrap()
+            .unwrap()
+            .compute_object_reference()
+    }
+}
+
 #[cfg(test)]
 fn init_certified_transfer_transaction(
     sender: SuiAddress,
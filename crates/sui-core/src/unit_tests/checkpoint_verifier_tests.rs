@@ -0,0 +1,205 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::env;
+
+use rand::{prelude::StdRng, SeedableRng};
+use sui_types::base_types::{ExecutionDigests, ObjectID};
+use sui_types::crypto::{AuthorityKeyPair, KeypairTraits};
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, SignedCheckpointSummary,
+};
+use sui_types::utils::make_committee_key;
+
+use super::*;
+
+fn certify(
+    keys: &[AuthorityKeyPair],
+    committee: &Committee,
+    sequence_number: CheckpointSequenceNumber,
+    contents: &CheckpointContents,
+    previous_digest: Option<CheckpointDigest>,
+    next_epoch_committee: Option<Committee>,
+) -> CertifiedCheckpointSummary {
+    let signed = keys
+        .iter()
+        .map(|key| {
+            SignedCheckpointSummary::new(
+                committee.epoch,
+                sequence_number,
+                key.public().into(),
+                key,
+                contents,
+                previous_digest,
+                next_epoch_committee.clone(),
+            )
+        })
+        .collect();
+    CertifiedCheckpointSummary::aggregate(signed, committee).unwrap()
+}
+
+#[test]
+fn test_verify_checkpoint_chain() {
+    let mut rng = StdRng::from_seed([1; 32]);
+    let (keys, genesis_committee) = make_committee_key(&mut rng);
+    let next_committee =
+        Committee::new(1, genesis_committee.voting_rights.iter().cloned().collect()).unwrap();
+    let path = env::temp_dir().join(format!("DB_{:?}", ObjectID::random()));
+    let tables =
+        CheckpointStoreTables::open_tables_read_write(path.join("checkpoints"), None, None);
+    let committees = CommitteeStore::new(path.join("epochs"), &genesis_committee, None);
+    committees.insert_new_committee(&next_committee).unwrap();
+
+    // Checkpoint 1 ends epoch 0, checkpoint 2 is in epoch 1
+    let mut previous_digest = None;
+    let mut certified_checkpoints = Vec::new();
+    for (sequence_number, committee, next_epoch_committee) in [
+        (0, &genesis_committee, None),
+        (1, &genesis_committee, Some(next_committee.clone())),
+        (2, &next_committee, None),
+    ] {
+        let contents = CheckpointContents::new_with_causally_ordered_transactions(
+            [ExecutionDigests::random()].into_iter(),
+        );
+        let certified = certify(
+            &keys,
+            committee,
+            sequence_number,
+            &contents,
+            previous_digest,
+            next_epoch_committee,
+        );
+        previous_digest = Some(certified.summary.digest());
+        tables
+            .checkpoints
+            .insert(
+                &sequence_number,
+                &AuthenticatedCheckpoint::Certified(certified.clone()),
+            )
+            .unwrap();
+        certified_checkpoints.push(certified);
+        tables
+            .checkpoint_contents
+            .insert(&sequence_number, &contents)
+            .unwrap();
+    }
+    let digests: Vec<_> = certified_checkpoints
+        .iter()
+        .map(|certified| certified.summary.digest())
+        .collect();
+
+    let genesis = ChainRoot::Genesis(genesis_committee.clone());
+    let report = verify_checkpoint_chain(&tables, &committees, &genesis).unwrap();
+    assert!(report.is_valid(), "{:?}", report.failure);
+    assert_eq!(report.verified_checkpoints, 3);
+    assert_eq!(
+        report.last_verified,
+        Some(VerifiedCheckpoint {
+            sequence_number: 2,
+            epoch: 1,
+            digest: digests[2],
+        })
+    );
+
+    // From a trusted checkpoint
+    let trusted = ChainRoot::Trusted {
+        sequence_number: 1,
+        digest: digests[1],
+    };
+    let report = verify_checkpoint_chain(&tables, &committees, &trusted).unwrap();
+    assert!(report.is_valid(), "{:?}", report.failure);
+    assert_eq!(report.verified_checkpoints, 2);
+    let untrusted = ChainRoot::Trusted {
+        sequence_number: 1,
+        digest: digests[0],
+    };
+    let report = verify_checkpoint_chain(&tables, &committees, &untrusted).unwrap();
+    assert_eq!(
+        report.failure,
+        Some(CheckpointChainFailure::TrustedDigestMismatch {
+            sequence_number: 1,
+            expected: digests[0],
+            found: digests[1],
+        })
+    );
+
+    // Contents that are not the ones of the checkpoint
+    let other_contents = CheckpointContents::new_with_causally_ordered_transactions(
+        [ExecutionDigests::random()].into_iter(),
+    );
+    let contents_2 = tables.checkpoint_contents.get(&2).unwrap().unwrap();
+    tables
+        .checkpoint_contents
+        .insert(&2, &other_contents)
+        .unwrap();
+    let report = verify_checkpoint_chain(&tables, &committees, &genesis).unwrap();
+    assert_eq!(report.verified_checkpoints, 2);
+    assert_eq!(
+        report.failure,
+        Some(CheckpointChainFailure::ContentsDigestMismatch {
+            sequence_number: 2,
+            expected: contents_2.digest(),
+            found: other_contents.digest(),
+        })
+    );
+    tables.checkpoint_contents.insert(&2, &contents_2).unwrap();
+
+    // A checkpoint that does not follow the previous one
+    let contents_1 = tables.checkpoint_contents.get(&1).unwrap().unwrap();
+    let forked = certify(
+        &keys,
+        &genesis_committee,
+        1,
+        &contents_1,
+        None,
+        Some(next_committee.clone()),
+    );
+    tables
+        .checkpoints
+        .insert(&1, &AuthenticatedCheckpoint::Certified(forked))
+        .unwrap();
+    let report = verify_checkpoint_chain(&tables, &committees, &genesis).unwrap();
+    assert_eq!(report.verified_checkpoints, 1);
+    assert_eq!(
+        report.failure,
+        Some(CheckpointChainFailure::PreviousDigestMismatch {
+            sequence_number: 1,
+            expected: Some(digests[0]),
+            found: None,
+        })
+    );
+    tables
+        .checkpoints
+        .insert(
+            &1,
+            &AuthenticatedCheckpoint::Certified(certified_checkpoints[1].clone()),
+        )
+        .unwrap();
+
+    // A checkpoint of epoch 1 certified by a committee that was not announced
+    let (other_keys, other_committee) = make_committee_key(&mut rng);
+    let other_committee =
+        Committee::new(1, other_committee.voting_rights.iter().cloned().collect()).unwrap();
+    let contents_2 = tables.checkpoint_contents.get(&2).unwrap().unwrap();
+    let forged = certify(
+        &other_keys,
+        &other_committee,
+        2,
+        &contents_2,
+        Some(digests[1]),
+        None,
+    );
+    tables
+        .checkpoints
+        .insert(&2, &AuthenticatedCheckpoint::Certified(forged))
+        .unwrap();
+    let report = verify_checkpoint_chain(&tables, &committees, &trusted).unwrap();
+    assert_eq!(report.verified_checkpoints, 1);
+    assert!(matches!(
+        report.failure,
+        Some(CheckpointChainFailure::InvalidSignatures {
+            sequence_number: 2,
+            ..
+        })
+    ));
+}
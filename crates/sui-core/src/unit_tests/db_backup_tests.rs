@@ -0,0 +1,89 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::time::Duration;
+
+use sui_storage::write_ahead_log::WriteAheadLog;
+use sui_types::base_types::dbg_addr;
+use sui_types::object::Owner;
+
+use super::*;
+use crate::authority::authority_tests::init_state_for_transfers;
+use crate::authority::AuthorityStore;
+use crate::checkpoints::CheckpointStoreTables;
+use crate::db_checker::check_db;
+use crate::epoch::committee_store::CommitteeStore;
+
+#[tokio::test]
+async fn test_create_db_backup() {
+    let state = init_state_for_transfers(1).await;
+    let authority_state = &state.authority_state;
+    let object_id = state.object_ids[0];
+    let tx_digest = state.transfer(object_id).await.transaction_digest;
+
+    let dir = tempfile::tempdir().unwrap();
+    let backups = DbBackups::new(authority_state.clone(), dir.path().to_path_buf());
+    let backup = backups.create(None).await.unwrap();
+    assert!(backup.path.starts_with(dir.path()));
+    assert!(backup.size_bytes > 0);
+
+    // The backup holds the state of the node when it was created
+    let store = AuthorityStore::open(&backup.path.join(STORE_DIR), None);
+    assert!(store.effects_exists(&tx_digest).unwrap());
+    assert_eq!(
+        store.get_object(&object_id).unwrap().unwrap().owner,
+        Owner::AddressOwner(dbg_addr(2))
+    );
+    let committee = authority_state.committee_store().get_latest_committee();
+    let committee_store = CommitteeStore::new(backup.path.join(EPOCHS_DIR), &committee, None);
+    assert_eq!(committee_store.get_latest_committee(), committee);
+
+    // A backup is never written over an existing directory
+    assert!(backups.create(Some(backup.path.clone())).await.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_db_backups_during_execution_are_consistent() {
+    let state = Arc::new(init_state_for_transfers(20).await);
+    let dir = tempfile::tempdir().unwrap();
+    let backups = DbBackups::new(state.authority_state.clone(), dir.path().to_path_buf());
+
+    let transfers = {
+        let state = state.clone();
+        tokio::spawn(async move {
+            for object_id in &state.object_ids {
+                state.transfer(*object_id).await;
+            }
+        })
+    };
+    let mut backup_paths = Vec::new();
+    for i in 0..5 {
+        let path = dir.path().join(format!("backup-{i}"));
+        backup_paths.push(backups.create(Some(path)).await.unwrap().path);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    transfers.await.unwrap();
+
+    for path in backup_paths {
+        let store = AuthorityStore::open(&path.join(STORE_DIR), None);
+        let checkpoints =
+            CheckpointStoreTables::open_tables_read_write(path.join(CHECKPOINTS_DIR), None, None);
+        assert!(check_db(&store, &checkpoints, 10).unwrap().is_consistent());
+
+        // No transaction is left half executed
+        assert!(store.wal.read_one_recoverable_tx().await.unwrap().is_none());
+        for (object_id, version, _) in store.iter_live_object_set() {
+            let object = store
+                .get_object_by_key(&object_id, version)
+                .unwrap()
+                .unwrap();
+            if object.is_owned_or_quasi_shared() {
+                let object_ref = object.compute_object_reference();
+                assert!(store
+                    .get_object_locking_transaction(&object_ref)
+                    .await
+                    .is_ok());
+            }
+        }
+    }
+}
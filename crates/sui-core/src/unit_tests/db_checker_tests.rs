@@ -0,0 +1,111 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::{ObjectDigest, ObjectInfo, SequenceNumber};
+
+use super::*;
+use crate::authority::authority_tests::init_state_for_transfers;
+
+fn checkpoint_of(transactions: &[ExecutionDigests]) -> CheckpointContents {
+    CheckpointContents::new_with_causally_ordered_transactions(transactions.iter().copied())
+}
+
+#[tokio::test]
+async fn test_check_db() {
+    let state = init_state_for_transfers(1).await;
+    let authority_state = &state.authority_state;
+    let (sender, object_id) = (state.sender, state.object_ids[0]);
+    let store = authority_state.db();
+
+    let object = store.get_object(&object_id).unwrap().unwrap();
+    let old_object_ref = object.compute_object_reference();
+    let effects = state.transfer(object_id).await;
+    let tx_digest = effects.transaction_digest;
+    let executed = ExecutionDigests::new(tx_digest, effects.digest());
+    let new_object_ref = store
+        .get_object(&object_id)
+        .unwrap()
+        .unwrap()
+        .compute_object_reference();
+
+    let checkpoints = authority_state.checkpoints();
+    let report = check_db(&store, &checkpoints.lock().tables, 100).unwrap();
+    assert!(report.is_consistent(), "{:?}", report.inconsistencies);
+    assert_eq!(report.executed_transactions, 1);
+    assert!(report.owner_index_entries >= 2);
+
+    // A stale owner index entry for the previous owner
+    let sender_owner = Owner::AddressOwner(sender);
+    store
+        .perpetual_tables
+        .owner_index
+        .insert(
+            &(sender_owner, object_id),
+            &ObjectInfo::new(&old_object_ref, &object),
+        )
+        .unwrap();
+    // A lost certificate
+    store
+        .perpetual_tables
+        .certificates
+        .remove(&tx_digest)
+        .unwrap();
+    // A parent_sync entry for an object the transaction did not write
+    let unknown_object_ref = (
+        ObjectID::random(),
+        SequenceNumber::from(1),
+        ObjectDigest::random(),
+    );
+    store
+        .perpetual_tables
+        .parent_sync
+        .insert(&unknown_object_ref, &tx_digest)
+        .unwrap();
+    // A checkpoint of an unknown transaction, before an executed checkpoint
+    let unknown = ExecutionDigests::random();
+    let checkpoint_store = checkpoints.lock();
+    let checkpoint_contents = &checkpoint_store.tables.checkpoint_contents;
+    checkpoint_contents
+        .insert(&0, &checkpoint_of(&[unknown]))
+        .unwrap();
+    checkpoint_contents
+        .insert(&1, &checkpoint_of(&[executed]))
+        .unwrap();
+    // A checkpoint which is not executed yet is not an inconsistency
+    checkpoint_contents
+        .insert(&2, &checkpoint_of(&[executed, ExecutionDigests::random()]))
+        .unwrap();
+    drop(checkpoint_store);
+
+    let report = check_db(&store, &checkpoints.lock().tables, 100).unwrap();
+    assert_eq!(report.checkpoints, 2);
+    assert_eq!(report.highest_executed_checkpoint, Some(1));
+    assert_eq!(report.inconsistency_count, 4);
+    assert_eq!(
+        report.inconsistencies,
+        vec![
+            DbInconsistency::OwnerIndexVersionNotLatest {
+                owner: sender_owner,
+                indexed: old_object_ref,
+                latest: new_object_ref,
+            },
+            DbInconsistency::ExecutedTransactionCertificateMissing {
+                seq: 0,
+                transaction: tx_digest,
+            },
+            DbInconsistency::ParentSyncNotInEffects {
+                object_ref: unknown_object_ref,
+                transaction: tx_digest,
+            },
+            DbInconsistency::CheckpointTransactionUnknown {
+                checkpoint: 0,
+                digests: unknown,
+            },
+        ]
+    );
+
+    // Only the first inconsistencies are kept
+    let report = check_db(&store, &checkpoints.lock().tables, 1).unwrap();
+    assert_eq!(report.inconsistency_count, 4);
+    assert_eq!(report.inconsistencies.len(), 1);
+}
@@ -0,0 +1,48 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::{ObjectID, SuiAddress};
+
+use super::*;
+use crate::authority::authority_tests::init_state_with_ids;
+
+#[tokio::test]
+async fn test_report_db_stats() {
+    let objects: Vec<_> = (0..10)
+        .map(|_| (SuiAddress::random_for_testing_only(), ObjectID::random()))
+        .collect();
+    let authority_state = Arc::new(init_state_with_ids(objects).await);
+    let registry = Registry::new();
+    let reporter = DbStatsReporter::new(authority_state, None, &registry);
+    reporter.report().unwrap();
+
+    let objects = reporter
+        .metrics
+        .estimated_keys
+        .with_label_values(&["authority", "objects"])
+        .get();
+    assert!(objects >= 10, "{objects}");
+    // Every table of the stores has its gauges
+    let families = registry.gather();
+    let live_sst_size = families
+        .iter()
+        .find(|family| family.get_name() == "db_table_live_sst_size_bytes")
+        .unwrap();
+    let tables: Vec<_> = live_sst_size
+        .get_metric()
+        .iter()
+        .map(|metric| {
+            let labels = metric.get_label();
+            (labels[0].get_value(), labels[1].get_value())
+        })
+        .collect();
+    for table in [
+        ("authority", "transactions"),
+        ("authority", "transaction_lock"),
+        ("authority", "log"),
+        ("checkpoints", "checkpoint_contents"),
+        ("node_sync", "pending_certs"),
+    ] {
+        assert!(tables.contains(&table), "{table:?}");
+    }
+}
@@ -0,0 +1,81 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use sui_types::object::Object;
+use typed_store::Map;
+
+use super::*;
+use crate::authority_aggregator::authority_aggregator_tests::{
+    crate_object_move_transaction, do_cert, do_transaction, extract_cert, init_local_authorities,
+};
+
+#[tokio::test]
+async fn test_check_transaction() {
+    let (addr1, key1): (_, AccountKeyPair) = get_key_pair();
+    let gas_object = Object::with_owner_for_testing(addr1);
+    let (authorities, states, pkg_ref) = init_local_authorities(4, vec![gas_object.clone()]).await;
+    let authority_clients: Vec<_> = authorities.authority_clients.values().collect();
+
+    let create = crate_object_move_transaction(
+        addr1,
+        &key1,
+        addr1,
+        100,
+        pkg_ref,
+        gas_object.compute_object_reference(),
+    );
+    let digest = *create.digest();
+    for client in &authority_clients {
+        do_transaction(client, &create).await;
+    }
+    let cert = extract_cert(&authority_clients, &authorities.committee, &digest).await;
+
+    // The last authority lags behind
+    for client in &authority_clients[..3] {
+        do_cert(client, &cert).await;
+    }
+    let lagging = *authorities.authority_clients.keys().last().unwrap();
+    let divergence = check_transaction(&authorities, digest).await;
+    assert!(!divergence.is_fork());
+    assert!(!divergence.is_consistent());
+    assert_eq!(divergence.missing, vec![lagging]);
+    assert_eq!(divergence.authorities.len(), 4);
+
+    let effects = do_cert(authority_clients[3], &cert).await;
+    let divergence = check_transaction(&authorities, digest).await;
+    assert!(divergence.is_consistent());
+    assert_eq!(divergence.reference_effects, Some(effects.digest()));
+    assert_eq!(
+        divergence.reference_stake,
+        authorities.committee.total_votes
+    );
+
+    // An authority signs effects with another gas cost
+    let forked = &states[0];
+    let mut forked_effects = effects.clone();
+    forked_effects.gas_used.computation_cost += 1;
+    let signed_effects = forked_effects.to_sign_effects(0, &forked.name, &*forked.secret);
+    forked
+        .db()
+        .perpetual_tables
+        .effects
+        .insert(&digest, &signed_effects)
+        .unwrap();
+
+    let divergence = check_transaction(&authorities, digest).await;
+    assert!(divergence.is_fork());
+    assert_eq!(divergence.reference_effects, Some(effects.digest()));
+    assert_eq!(divergence.disagreeing, vec![forked.name]);
+    assert!(divergence.missing.is_empty());
+    let forked_report = divergence
+        .authorities
+        .iter()
+        .find(|effects| effects.authority == forked.name)
+        .unwrap();
+    assert_eq!(forked_report.effects_digest, Some(*signed_effects.digest()));
+    assert_eq!(
+        forked_report.first_differing_field,
+        Some("gas_used".to_string())
+    );
+}
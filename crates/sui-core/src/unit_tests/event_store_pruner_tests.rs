@@ -0,0 +1,196 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+
+use sui_storage::event_store::{test_utils, SqlEventStore};
+use sui_types::error::SuiError;
+use sui_types::event::{EventEnvelope, EventID, TransferType};
+
+use super::*;
+
+const HOUR_MS: u64 = 3600 * 1000;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+fn retention() -> EventStoreRetentionConfig {
+    EventStoreRetentionConfig {
+        max_age_secs: None,
+        max_event_count: None,
+        max_age_secs_by_event_type: BTreeMap::new(),
+        prune_interval_secs: 600,
+        vacuum_interval_secs: None,
+    }
+}
+
+fn transfer_event(timestamp: u64, seq_num: u64) -> EventEnvelope {
+    test_utils::new_test_transfer_event(
+        timestamp,
+        seq_num,
+        0,
+        1,
+        TransferType::Coin,
+        None,
+        None,
+        None,
+    )
+}
+
+fn new_object_event(timestamp: u64, seq_num: u64) -> EventEnvelope {
+    test_utils::new_test_newobj_event(timestamp, seq_num, 0, None, None, None)
+}
+
+async fn event_store_with(events: &[EventEnvelope]) -> Arc<EventStoreType> {
+    let event_store = SqlEventStore::new_memory_only_not_prod().await.unwrap();
+    event_store.initialize().await.unwrap();
+    assert_eq!(
+        event_store.add_events(events).await.unwrap(),
+        events.len() as u64
+    );
+    Arc::new(EventStoreType::SqlEventStore(event_store))
+}
+
+async fn retained_seq_nums(event_store: &EventStoreType) -> Vec<u64> {
+    // Read from the newest event, as ascending queries without a cursor fail once pruned
+    event_store
+        .all_events(None, 100, true)
+        .await
+        .unwrap()
+        .iter()
+        .rev()
+        .map(|event| event.id.tx_seq)
+        .collect()
+}
+
+#[tokio::test]
+async fn test_prune_by_event_count() {
+    let now = now_ms();
+    let events: Vec<_> = (1..=5).map(|seq| new_object_event(now, seq)).collect();
+    let event_store = event_store_with(&events).await;
+    let config = EventStoreRetentionConfig {
+        max_event_count: Some(2),
+        ..retention()
+    };
+    let pruner = EventStorePruner::new(event_store.clone(), &config, &Registry::new()).unwrap();
+
+    pruner.prune().await.unwrap();
+    assert_eq!(retained_seq_nums(&event_store).await, vec![4, 5]);
+    assert_eq!(pruner.metrics.pruned_events.get(), 3);
+    assert_eq!(pruner.metrics.oldest_retained_seq_num.get(), 4);
+
+    // Queries from a pruned cursor fail rather than miss events
+    assert!(matches!(
+        event_store
+            .all_events(Some(EventID::from((2, 0))), 10, false)
+            .await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 2,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    let events = event_store
+        .all_events(Some(EventID::from((4, 0))), 10, false)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+
+    // Nothing more is pruned until new events are added
+    pruner.prune().await.unwrap();
+    assert_eq!(pruner.metrics.pruned_events.get(), 3);
+}
+
+#[tokio::test]
+async fn test_prune_by_age() {
+    let now = now_ms();
+    let events = vec![
+        new_object_event(now - 3 * HOUR_MS, 1),
+        transfer_event(now - 2 * HOUR_MS, 2),
+        new_object_event(now - HOUR_MS / 2, 3),
+        transfer_event(now, 4),
+    ];
+    let event_store = event_store_with(&events).await;
+    let config = EventStoreRetentionConfig {
+        max_age_secs: Some(3600),
+        ..retention()
+    };
+    let pruner = EventStorePruner::new(event_store.clone(), &config, &Registry::new()).unwrap();
+
+    pruner.prune().await.unwrap();
+    assert_eq!(retained_seq_nums(&event_store).await, vec![3, 4]);
+    assert_eq!(pruner.metrics.pruned_events.get(), 2);
+    assert_eq!(pruner.metrics.oldest_retained_seq_num.get(), 3);
+    assert!(matches!(
+        event_store
+            .events_by_type(EventType::NewObject, Some(EventID::from((1, 0))), 10, false)
+            .await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 1,
+            oldest_retained_seq_num: 3
+        })
+    ));
+}
+
+#[tokio::test]
+async fn test_prune_by_event_type() {
+    let now = now_ms();
+    let events = vec![
+        transfer_event(now - 2 * HOUR_MS, 1),
+        new_object_event(now - 2 * HOUR_MS, 2),
+        transfer_event(now - 2 * HOUR_MS, 3),
+        transfer_event(now, 4),
+    ];
+    let event_store = event_store_with(&events).await;
+    let config = EventStoreRetentionConfig {
+        max_age_secs_by_event_type: BTreeMap::from([("TransferObject".to_string(), 3600)]),
+        ..retention()
+    };
+    let pruner = EventStorePruner::new(event_store.clone(), &config, &Registry::new()).unwrap();
+
+    pruner.prune().await.unwrap();
+    assert_eq!(retained_seq_nums(&event_store).await, vec![2, 4]);
+    assert_eq!(pruner.metrics.pruned_events.get(), 2);
+    assert_eq!(pruner.metrics.oldest_retained_seq_num.get(), 4);
+
+    // Queries of the pruned type fail below its pruned events
+    assert!(matches!(
+        event_store
+            .events_by_type(
+                EventType::TransferObject,
+                Some(EventID::from((2, 0))),
+                10,
+                false
+            )
+            .await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 2,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    // Other queries skip them
+    let events = event_store
+        .all_events(Some(EventID::from((1, 0))), 10, false)
+        .await
+        .unwrap();
+    let seq_nums: Vec<_> = events.iter().map(|event| event.id.tx_seq).collect();
+    assert_eq!(seq_nums, vec![2, 4]);
+    let events = event_store
+        .events_by_type(EventType::NewObject, Some(EventID::from((1, 0))), 10, false)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+}
+
+#[tokio::test]
+async fn test_unknown_event_type_in_retention() {
+    let event_store = event_store_with(&[]).await;
+    let config = EventStoreRetentionConfig {
+        max_age_secs_by_event_type: BTreeMap::from([("NotAnEventType".to_string(), 3600)]),
+        ..retention()
+    };
+    assert!(EventStorePruner::new(event_store, &config, &Registry::new()).is_err());
+}
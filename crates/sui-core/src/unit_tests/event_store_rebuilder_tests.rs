@@ -0,0 +1,66 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::sync::Arc;
+
+use sui_storage::event_store::{EventStore, EventStoreType, SqlEventStore};
+use sui_storage::IndexStore;
+
+use super::*;
+use crate::authority::authority_tests::init_state_for_transfers;
+
+#[tokio::test]
+async fn test_rebuild_event_store() {
+    let fixture = init_state_for_transfers(3).await;
+    let mut digests = vec![];
+    for object_id in &fixture.object_ids {
+        let effects = fixture.transfer(*object_id).await;
+        assert!(!effects.events.is_empty());
+        digests.push(effects.transaction_digest);
+    }
+
+    let store = fixture.authority_state.db();
+    let event_store = SqlEventStore::new_memory_only_not_prod().await.unwrap();
+    event_store.initialize().await.unwrap();
+    let event_handler = EventHandler::new(
+        store.clone(),
+        Arc::new(EventStoreType::SqlEventStore(event_store)),
+    );
+
+    let mut reported = vec![];
+    let progress = rebuild_event_store(&store, None::<&IndexStore>, &event_handler, |progress| {
+        reported.push(progress.next_seq)
+    })
+    .await
+    .unwrap();
+    let end_seq = store.next_sequence_number().unwrap();
+    assert_eq!(progress.end_seq, end_seq);
+    assert_eq!(progress.next_seq, end_seq);
+    assert_eq!(progress.transactions, end_seq);
+    assert_eq!(progress.missing_timestamps, end_seq);
+    assert_eq!(reported, vec![end_seq]);
+
+    let event_store = &event_handler.event_store;
+    for digest in &digests {
+        let effects = store.get_effects(digest).unwrap();
+        let events = event_store
+            .events_by_transaction(*digest, None, 100, false)
+            .await
+            .unwrap();
+        assert_eq!(events.len(), effects.events.len());
+    }
+    let event_count = event_store
+        .all_events(None, 100, false)
+        .await
+        .unwrap()
+        .len();
+    assert_eq!(progress.events, event_count as u64);
+
+    // Rebuilding again only replays the last transaction, whose events are already stored
+    let progress = rebuild_event_store(&store, None::<&IndexStore>, &event_handler, |_| {})
+        .await
+        .unwrap();
+    assert_eq!(progress.transactions, 1);
+    let events = event_store.all_events(None, 100, false).await.unwrap();
+    assert_eq!(events.len(), event_count);
+}
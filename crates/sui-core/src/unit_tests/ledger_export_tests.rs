@@ -0,0 +1,62 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::base_types::ObjectID;
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+
+use super::*;
+use crate::authority::authority_tests::{
+    create_move_object, init_state_with_ids_and_object_basics,
+};
+
+#[tokio::test]
+async fn test_export_range() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, package_ref) =
+        init_state_with_ids_and_object_basics([(sender, gas_object_id)]).await;
+    let effects = create_move_object(
+        &package_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+
+    let exporter = LedgerExporter::new(authority_state.db(), None, None);
+    let end = exporter.next_sequence_number().unwrap();
+    let chunk = exporter.export_range(0, end).unwrap();
+    let transaction = chunk.transactions.last().unwrap();
+    assert_eq!(transaction.transaction_digest, effects.transaction_digest);
+    assert_eq!(transaction.effects_digest, effects.digest());
+    assert_eq!(transaction.sender, sender);
+    assert_eq!(transaction.timestamp_ms, None);
+
+    let changes: Vec<_> = chunk
+        .object_changes
+        .iter()
+        .filter(|change| change.seq == transaction.seq)
+        .collect();
+    assert_eq!(changes.len(), effects.created.len() + effects.mutated.len());
+    let created = changes
+        .iter()
+        .find(|change| change.kind == ObjectChangeKind::Created)
+        .unwrap();
+    assert_eq!(
+        (created.reference.object_id, created.owner),
+        (effects.created[0].0 .0, Some(effects.created[0].1))
+    );
+
+    let events: Vec<_> = chunk
+        .events
+        .iter()
+        .filter(|event| event.id.tx_seq == transaction.seq)
+        .collect();
+    assert_eq!(events.len(), effects.events.len());
+
+    // An empty range
+    let chunk = exporter.export_range(end, end + 10).unwrap();
+    assert!(chunk.transactions.is_empty());
+}
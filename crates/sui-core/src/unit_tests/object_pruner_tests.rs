@@ -0,0 +1,175 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use prometheus::Registry;
+use sui_types::base_types::dbg_addr;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSummary, SignedCheckpointSummary,
+};
+use sui_types::object::{Owner, PastObjectRead};
+
+use super::*;
+use crate::authority::authority_tests::{init_state, init_state_for_transfers};
+
+fn pruning_config() -> ObjectPruningConfig {
+    ObjectPruningConfig {
+        num_checkpoints_to_retain: None,
+        num_epochs_to_retain: None,
+        prune_interval_secs: 60,
+        max_checkpoints_per_run: 100,
+        enabled: true,
+    }
+}
+
+#[tokio::test]
+async fn test_prune_objects_of_checkpoint() {
+    let state = init_state_for_transfers(1).await;
+    let authority_state = &state.authority_state;
+    let object_id = state.object_ids[0];
+    let object = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let gas_object = authority_state
+        .get_object(&state.gas_object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    let effects = state.transfer(object_id).await;
+    let digests = ExecutionDigests::new(effects.transaction_digest, effects.digest());
+
+    let store = authority_state.db();
+    assert_eq!(store.objects_pruned_checkpoint().unwrap(), None);
+
+    // A checkpoint with a transaction that was not executed is left alone
+    assert_eq!(
+        store
+            .prune_objects_of_checkpoint(0, &[digests, ExecutionDigests::random()])
+            .unwrap(),
+        None
+    );
+    assert_eq!(store.objects_pruned_checkpoint().unwrap(), None);
+    assert!(store
+        .get_object_by_key(&object_id, object.version())
+        .unwrap()
+        .is_some());
+
+    // Checkpoints are pruned in sequence
+    assert!(store.prune_objects_of_checkpoint(1, &[digests]).is_err());
+
+    // The transferred object and the gas object are superseded
+    assert_eq!(
+        store.prune_objects_of_checkpoint(0, &[digests]).unwrap(),
+        Some(2)
+    );
+    assert_eq!(store.objects_pruned_checkpoint().unwrap(), Some(0));
+    assert!(store.prune_objects_of_checkpoint(0, &[digests]).is_err());
+    for old_object in [&object, &gas_object] {
+        let read = authority_state
+            .get_past_object_read(&old_object.id(), old_object.version())
+            .await
+            .unwrap();
+        assert!(matches!(
+            read,
+            PastObjectRead::VersionPruned(id, version)
+                if id == old_object.id() && version == old_object.version()
+        ));
+        assert_eq!(
+            store.get_pruned_object_version(&old_object.id()).unwrap(),
+            Some(old_object.version())
+        );
+    }
+
+    // The latest versions are kept
+    let latest = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(latest.owner, Owner::AddressOwner(dbg_addr(2)));
+    let read = authority_state
+        .get_past_object_read(&object_id, latest.version())
+        .await
+        .unwrap();
+    assert!(matches!(read, PastObjectRead::VersionFound(..)));
+}
+
+#[tokio::test]
+async fn test_object_pruner_needs_horizon() {
+    let authority_state = init_state().await;
+    let mut config = pruning_config();
+    assert!(ObjectPruner::new(
+        authority_state.db(),
+        authority_state.checkpoints(),
+        &config,
+        &Registry::new(),
+    )
+    .is_err());
+
+    config.num_checkpoints_to_retain = Some(10);
+    let pruner = ObjectPruner::new(
+        authority_state.db(),
+        authority_state.checkpoints(),
+        &config,
+        &Registry::new(),
+    )
+    .unwrap();
+    // Nothing to prune before the first certified checkpoint
+    assert_eq!(pruner.prune().unwrap(), 0);
+
+    let switch = pruner.switch();
+    assert!(switch.is_enabled());
+    switch.set_enabled(false);
+    assert!(!pruner.switch().is_enabled());
+}
+
+#[tokio::test]
+async fn test_object_pruner_skips_missing_checkpoints() {
+    let state = init_state_for_transfers(1).await;
+    let authority_state = &state.authority_state;
+    let committee = authority_state.committee_store().get_latest_committee();
+    let effects = state.transfer(state.object_ids[0]).await;
+    let digests = ExecutionDigests::new(effects.transaction_digest, effects.digest());
+
+    // As on a node synced from a state snapshot, checkpoint 2 is the first one stored
+    let contents =
+        CheckpointContents::new_with_causally_ordered_transactions([digests].into_iter());
+    let summary = CheckpointSummary::new(committee.epoch, 2, &contents, None, None);
+    let signed = SignedCheckpointSummary::new_from_summary(
+        summary,
+        authority_state.name,
+        &*authority_state.secret,
+    );
+    let certified = CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap();
+    authority_state
+        .checkpoints()
+        .lock()
+        .bootstrap_from_snapshot_checkpoint(&certified, &contents, &committee)
+        .unwrap();
+
+    let config = ObjectPruningConfig {
+        num_checkpoints_to_retain: Some(0),
+        ..pruning_config()
+    };
+    let pruner = ObjectPruner::new(
+        authority_state.db(),
+        authority_state.checkpoints(),
+        &config,
+        &Registry::new(),
+    )
+    .unwrap();
+
+    // Checkpoints 0 and 1 are skipped rather than stopping the pruner
+    assert_eq!(pruner.prune().unwrap(), 2);
+    assert_eq!(pruner.metrics.skipped_checkpoints.get(), 2);
+    assert_eq!(pruner.metrics.pruned_checkpoint.get(), 2);
+    assert_eq!(
+        authority_state.db().objects_pruned_checkpoint().unwrap(),
+        Some(2)
+    );
+
+    // Nothing is left to prune or skip
+    assert_eq!(pruner.prune().unwrap(), 0);
+    assert_eq!(pruner.metrics.skipped_checkpoints.get(), 2);
+}
@@ -0,0 +1,335 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use sui_types::base_types::{dbg_addr, ExecutionDigests};
+use sui_types::crypto::{get_key_pair, AuthorityKeyPair, KeypairTraits};
+
+use super::*;
+use crate::authority::authority_tests::{init_state_for_transfers, TransferTestState};
+use crate::authority::AuthorityState;
+use crate::checkpoints::causal_order_effects::TestCausalOrderNoop;
+
+struct EmptyNode {
+    store: AuthorityStore,
+    committee_store: CommitteeStore,
+    checkpoint_store: Mutex<CheckpointStore>,
+}
+
+fn open_empty_node(path: &Path, authority_state: &AuthorityState) -> EmptyNode {
+    let committee = authority_state.committee_store().get_latest_committee();
+    EmptyNode {
+        store: AuthorityStore::open(&path.join("store"), None),
+        committee_store: CommitteeStore::new(path.join("epochs"), &committee, None),
+        checkpoint_store: Mutex::new(
+            CheckpointStore::open(
+                &path.join("checkpoints"),
+                None,
+                &committee,
+                authority_state.name,
+                authority_state.secret.clone(),
+                false,
+            )
+            .unwrap(),
+        ),
+    }
+}
+
+/// Transfers `object_id`, returning the digests of the transaction and its effects.
+async fn transfer(state: &TransferTestState, object_id: ObjectID) -> ExecutionDigests {
+    let effects = state.transfer(object_id).await;
+    ExecutionDigests::new(effects.transaction_digest, effects.digest())
+}
+
+/// Stores and certifies the checkpoint `seq` holding the transaction `digests`, numbered `seq` in
+/// the local sequence of the authority.
+fn certify_checkpoint(authority_state: &AuthorityState, seq: u64, digests: ExecutionDigests) {
+    let committee = authority_state.committee_store().get_latest_committee();
+    let checkpoints = authority_state.checkpoints();
+    let mut checkpoints = checkpoints.lock();
+    checkpoints
+        .handle_internal_batch(seq + 1, &[(seq, digests)])
+        .unwrap();
+    checkpoints
+        .sign_new_checkpoint(0, seq, [digests].iter(), TestCausalOrderNoop, None)
+        .unwrap();
+    let signed = match checkpoints.latest_stored_checkpoint() {
+        Some(AuthenticatedCheckpoint::Signed(signed)) => signed,
+        _ => panic!("Unexpected checkpoint"),
+    };
+    let certified = CertifiedCheckpointSummary::aggregate(vec![signed], &committee).unwrap();
+    checkpoints
+        .promote_signed_checkpoint_to_cert(&certified, &committee)
+        .unwrap();
+}
+
+fn live_objects(authority_state: &AuthorityState) -> Vec<Object> {
+    let store = authority_state.db();
+    store
+        .iter_live_object_set()
+        .map(|(object_id, version, _)| {
+            store
+                .get_object_by_key(&object_id, version)
+                .unwrap()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Rewrites the records following the header of `snapshot` with `rewrite`.
+fn tamper(
+    snapshot: &[u8],
+    mut rewrite: impl FnMut(StateSnapshotRecord) -> Vec<StateSnapshotRecord>,
+) -> Vec<u8> {
+    let mut reader = snapshot;
+    let header = read_state_snapshot_header(&mut reader).unwrap();
+    let mut tampered = STATE_SNAPSHOT_MAGIC.to_vec();
+    write_record(&mut tampered, &header).unwrap();
+    loop {
+        let record: StateSnapshotRecord = read_record(&mut reader).unwrap();
+        let end = matches!(record, StateSnapshotRecord::End { .. });
+        for record in rewrite(record) {
+            write_record(&mut tampered, &record).unwrap();
+        }
+        if end {
+            return tampered;
+        }
+    }
+}
+
+fn owner_index_entry(object: &Object) -> StateSnapshotRecord {
+    let object_ref = object.compute_object_reference();
+    StateSnapshotRecord::OwnerIndexEntry(
+        object.owner,
+        object_ref.0,
+        ObjectInfo::new(&object_ref, object),
+    )
+}
+
+/// Replaces the object of the ID of `replacement` and its owner index entry.
+fn replace_object(
+    replacement: Object,
+) -> impl FnMut(StateSnapshotRecord) -> Vec<StateSnapshotRecord> {
+    move |record| match record {
+        StateSnapshotRecord::Object(object) if object.id() == replacement.id() => vec![
+            StateSnapshotRecord::Object(replacement.clone()),
+            owner_index_entry(&replacement),
+        ],
+        StateSnapshotRecord::OwnerIndexEntry(_, object_id, _) if object_id == replacement.id() => {
+            vec![]
+        }
+        record => vec![record],
+    }
+}
+
+/// A state with three objects, the first two of which are transferred in checkpoints 0 and 1,
+/// with its genesis objects.
+async fn checkpointed_state() -> (TransferTestState, Vec<Object>, [ExecutionDigests; 2]) {
+    let state = init_state_for_transfers(3).await;
+    let genesis_objects = live_objects(&state.authority_state);
+    let digests_1 = transfer(&state, state.object_ids[0]).await;
+    certify_checkpoint(&state.authority_state, 0, digests_1);
+    let digests_2 = transfer(&state, state.object_ids[1]).await;
+    certify_checkpoint(&state.authority_state, 1, digests_2);
+    (state, genesis_objects, [digests_1, digests_2])
+}
+
+/// Exports a snapshot of `state` at its latest certified checkpoint.
+fn export(state: &TransferTestState) -> (StateSnapshotSummary, Vec<u8>) {
+    let authority_state = &state.authority_state;
+    let mut snapshot = Vec::new();
+    let summary = export_state_snapshot(
+        &authority_state.db(),
+        &authority_state.checkpoints().lock().tables,
+        authority_state.committee_store(),
+        None,
+        &mut snapshot,
+    )
+    .unwrap();
+    (summary, snapshot)
+}
+
+async fn import(
+    snapshot: &[u8],
+    authority_state: &AuthorityState,
+    genesis_objects: &[Object],
+    path: &Path,
+) -> (EmptyNode, SuiResult<StateSnapshotSummary>) {
+    let committee = authority_state.committee_store().get_latest_committee();
+    let node = open_empty_node(path, authority_state);
+    let result = import_state_snapshot(
+        snapshot,
+        &committee,
+        genesis_objects,
+        &node.store,
+        &node.committee_store,
+        &node.checkpoint_store,
+    )
+    .await;
+    (node, result)
+}
+
+#[tokio::test]
+async fn test_export_and_import_state_snapshot() {
+    let (state, genesis_objects, [digests_1, digests_2]) = checkpointed_state().await;
+    let authority_state = &state.authority_state;
+    let object_refs = [
+        state.latest_ref(state.object_ids[0]).await,
+        state.latest_ref(state.object_ids[1]).await,
+        state.latest_ref(state.object_ids[2]).await,
+        state.latest_ref(state.gas_object_id).await,
+    ];
+
+    // The third transfer is executed after the checkpoints
+    let digests_3 = transfer(&state, state.object_ids[2]).await;
+    authority_state
+        .checkpoints()
+        .lock()
+        .handle_internal_batch(3, &[(2, digests_3)])
+        .unwrap();
+
+    let (exported, snapshot) = export(&state);
+    assert_eq!(exported.checkpoint, 1);
+    assert_eq!(exported.epoch, 0);
+    assert_eq!(exported.effects, 2);
+    let header = read_state_snapshot_header(snapshot.as_slice()).unwrap();
+    assert_eq!(header.contents.iter().collect::<Vec<_>>(), vec![&digests_2]);
+
+    // A snapshot does not verify against another genesis committee
+    let (_, other_key): (_, AuthorityKeyPair) = get_key_pair();
+    let other_committee =
+        Committee::new(0, BTreeMap::from([(other_key.public().into(), 1)])).unwrap();
+    assert!(verify_state_snapshot_header(&header, &other_committee).is_err());
+
+    // A truncated snapshot is rejected
+    let truncated_dir = tempfile::tempdir().unwrap();
+    let (_, result) = import(
+        &snapshot[..snapshot.len() - 1],
+        authority_state,
+        &genesis_objects,
+        truncated_dir.path(),
+    )
+    .await;
+    assert!(matches!(result, Err(SuiError::InvalidStateSnapshot(_))));
+
+    let dir = tempfile::tempdir().unwrap();
+    let (node, imported) = import(&snapshot, authority_state, &genesis_objects, dir.path()).await;
+    assert_eq!(imported.unwrap(), exported);
+
+    // The objects are at their version of the checkpoint
+    for object_ref in object_refs {
+        let object = node.store.get_object(&object_ref.0).unwrap().unwrap();
+        assert_eq!(object.compute_object_reference(), object_ref);
+        assert_eq!(
+            node.store.get_latest_parent_entry(object_ref.0).unwrap(),
+            Some((object_ref, object.previous_transaction))
+        );
+    }
+    assert_eq!(
+        node.store
+            .get_owner_objects(Owner::AddressOwner(state.sender))
+            .unwrap()
+            .len(),
+        2
+    );
+    assert!(node.store.effects_exists(&digests_1.transaction).unwrap());
+    assert!(node.store.effects_exists(&digests_2.transaction).unwrap());
+    assert!(!node.store.effects_exists(&digests_3.transaction).unwrap());
+
+    let mut checkpoint_store = node.checkpoint_store.lock();
+    assert_eq!(
+        checkpoint_store
+            .latest_certified_checkpoint()
+            .map(|checkpoint| checkpoint.sequence_number()),
+        Some(1)
+    );
+    assert_eq!(checkpoint_store.next_checkpoint(), 2);
+    drop(checkpoint_store);
+
+    // Only an empty store can be bootstrapped
+    let committee = authority_state.committee_store().get_latest_committee();
+    let result = import_state_snapshot(
+        snapshot.as_slice(),
+        &committee,
+        &genesis_objects,
+        &node.store,
+        &node.committee_store,
+        &node.checkpoint_store,
+    )
+    .await;
+    assert!(matches!(result, Err(SuiError::InvalidStateSnapshot(_))));
+}
+
+#[tokio::test]
+async fn test_import_tampered_state_snapshot() {
+    let (state, genesis_objects, [digests_1, ..]) = checkpointed_state().await;
+    let authority_state = &state.authority_state;
+    let object_id = state.object_ids[0];
+    let (_, snapshot) = export(&state);
+    let attacker = dbg_addr(3);
+
+    let mut stolen = authority_state
+        .get_object(&object_id)
+        .await
+        .unwrap()
+        .unwrap();
+    stolen.owner = Owner::AddressOwner(attacker);
+    let stolen_ref = stolen.compute_object_reference();
+
+    // An object that its previous transaction did not write
+    let tampered = tamper(&snapshot, replace_object(stolen.clone()));
+
+    // An object passed off as a genesis object
+    let forged = Object::with_id_owner_for_testing(object_id, attacker);
+    let forged_genesis = tamper(&snapshot, replace_object(forged));
+
+    // Effects signed by the authority but in no certified checkpoint
+    let mut replace = replace_object(stolen);
+    let forged_effects = tamper(&snapshot, |record| match record {
+        StateSnapshotRecord::Effects(signed_effects)
+            if signed_effects.effects.transaction_digest == digests_1.transaction =>
+        {
+            let mut effects = signed_effects.effects;
+            for (written_ref, owner) in &mut effects.mutated {
+                if written_ref.0 == object_id {
+                    *written_ref = stolen_ref;
+                    *owner = Owner::AddressOwner(attacker);
+                }
+            }
+            vec![StateSnapshotRecord::Effects(effects.to_sign_effects(
+                0,
+                &authority_state.name,
+                &*authority_state.secret,
+            ))]
+        }
+        record => replace(record),
+    });
+
+    for tampered in [tampered, forged_genesis, forged_effects] {
+        let dir = tempfile::tempdir().unwrap();
+        let (node, result) = import(&tampered, authority_state, &genesis_objects, dir.path()).await;
+        assert!(matches!(result, Err(SuiError::InvalidStateSnapshot(_))));
+        assert!(node
+            .checkpoint_store
+            .lock()
+            .latest_certified_checkpoint()
+            .is_none());
+    }
+
+    // The untampered snapshot imports
+    let dir = tempfile::tempdir().unwrap();
+    let (_, result) = import(&snapshot, authority_state, &genesis_objects, dir.path()).await;
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_oversized_record_is_rejected() {
+    let mut snapshot = STATE_SNAPSHOT_MAGIC.to_vec();
+    snapshot.extend_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        read_state_snapshot_header(snapshot.as_slice()),
+        Err(SuiError::InvalidStateSnapshot(_))
+    ));
+}
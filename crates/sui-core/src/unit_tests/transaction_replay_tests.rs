@@ -0,0 +1,104 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use sui_types::crypto::{get_key_pair, AccountKeyPair};
+use typed_store::Map;
+
+use super::*;
+use crate::authority::authority_tests::{
+    create_move_object, init_state_with_ids_and_object_basics,
+};
+
+#[tokio::test]
+async fn test_replay_transaction() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, package_ref) =
+        init_state_with_ids_and_object_basics([(sender, gas_object_id)]).await;
+    let effects = create_move_object(
+        &package_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+    let digest = effects.transaction_digest;
+    // Execute a second transaction on the same gas object, so that replaying the first one
+    // needs the previous version of the gas object.
+    create_move_object(
+        &package_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+
+    let store = authority_state.db();
+    let replay = replay_transaction(store.as_ref(), &digest).await.unwrap();
+    assert!(replay.effects_match(), "{:?}", replay.differences);
+    assert_eq!(replay.replayed_effects, effects);
+    assert!(replay.execution_error.is_none());
+    assert!(replay.packages.contains(&package_ref));
+
+    // Effects that differ from the ones of the execution
+    let mut signed_effects = store
+        .perpetual_tables
+        .effects
+        .get(&digest)
+        .unwrap()
+        .unwrap();
+    signed_effects.effects.gas_used.computation_cost += 1;
+    store
+        .perpetual_tables
+        .effects
+        .insert(&digest, &signed_effects)
+        .unwrap();
+    let replay = replay_transaction(store.as_ref(), &digest).await.unwrap();
+    assert_eq!(replay.differences, vec!["gas_used".to_string()]);
+    assert_eq!(replay.replayed_effects, effects);
+}
+
+#[tokio::test]
+async fn test_replay_with_the_storage_gas_price_of_the_epoch() {
+    let (sender, sender_key): (_, AccountKeyPair) = get_key_pair();
+    let gas_object_id = ObjectID::random();
+    let (authority_state, package_ref) =
+        init_state_with_ids_and_object_basics([(sender, gas_object_id)]).await;
+    let effects = create_move_object(
+        &package_ref,
+        &authority_state,
+        &gas_object_id,
+        &sender,
+        &sender_key,
+    )
+    .await
+    .unwrap();
+
+    // A later epoch raises the storage gas price
+    let store = authority_state.db();
+    let mut object = store
+        .get_object(&SUI_SYSTEM_STATE_OBJECT_ID)
+        .unwrap()
+        .unwrap();
+    let mut system_state = parse_system_state(&object).unwrap();
+    system_state.epoch += 1;
+    system_state.parameters.storage_gas_price += 1;
+    object
+        .data
+        .try_as_move_mut()
+        .unwrap()
+        .update_contents_and_increment_version(bcs::to_bytes(&system_state).unwrap());
+    store
+        .insert_object_direct(object.compute_object_reference(), &object)
+        .await
+        .unwrap();
+
+    let replay = replay_transaction(store.as_ref(), &effects.transaction_digest)
+        .await
+        .unwrap();
+    assert!(replay.effects_match(), "{:?}", replay.differences);
+}
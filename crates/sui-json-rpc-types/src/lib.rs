@@ -25,19 +25,23 @@ use serde::ser::Error;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value;
-use serde_with::serde_as;
+use serde_with::{serde_as, DisplayFromStr};
 use tracing::warn;
 
 use sui_json::SuiJsonValue;
 use sui_types::base_types::{
-    ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber, SuiAddress, TransactionDigest,
-    TransactionEffectsDigest,
+    AuthorityName, ExecutionDigests, ObjectDigest, ObjectID, ObjectInfo, ObjectRef, SequenceNumber,
+    SuiAddress, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::batch::TxSequenceNumber;
+use sui_types::coin::Coin;
+use sui_types::committee::{EpochId, StakeUnit};
+use sui_types::crypto::{
+    AuthorityStrongQuorumSignInfo, AuthorityWeakQuorumSignInfo, SignableBytes, Signature,
 };
-use sui_types::committee::EpochId;
-use sui_types::crypto::{AuthorityStrongQuorumSignInfo, SignableBytes, Signature};
 use sui_types::error::SuiError;
 use sui_types::event::{Event, TransferType};
-use sui_types::event::{EventEnvelope, EventType};
+use sui_types::event::{EventEnvelope, EventID, EventType};
 use sui_types::filter::{EventFilter, TransactionFilter};
 use sui_types::gas::GasCostSummary;
 use sui_types::gas_coin::GasCoin;
@@ -46,12 +50,16 @@ use sui_types::messages::{
     ExecutionStatus, InputObjectKind, MoveModulePublish, ObjectArg, Pay, SingleTransactionKind,
     TransactionData, TransactionEffects, TransactionKind,
 };
-use sui_types::messages_checkpoint::CheckpointSequenceNumber;
+use sui_types::messages_checkpoint::{
+    CertifiedCheckpointSummary, CheckpointContents, CheckpointSequenceNumber,
+};
 use sui_types::move_package::{disassemble_modules, MovePackage};
 use sui_types::object::{
     Data, MoveObject, Object, ObjectFormatOptions, ObjectRead, Owner, PastObjectRead,
 };
+use sui_types::storage::{DeleteKind, WriteKind};
 use sui_types::sui_serde::{Base64, Encoding};
+use sui_types::temporary_store::InnerTemporaryStore;
 use sui_types::{parse_sui_struct_tag, parse_sui_type_tag};
 
 #[cfg(test)]
@@ -61,6 +69,12 @@ mod rpc_types_tests;
 pub type SuiMoveTypeParameterIndex = u16;
 pub type TransactionsPage = Page<TransactionDigest, TransactionDigest>;
 
+pub type EventPage = Page<SuiEventEnvelope, EventID>;
+
+pub type CoinPage = Page<SuiCoin, ObjectID>;
+
+pub type ObjectsPage = Page<SuiObjectInfo, ObjectID>;
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub enum SuiMoveAbility {
     Copy,
@@ -313,6 +327,15 @@ pub struct SuiTransactionResponse {
     pub parsed_data: Option<SuiParsedTransactionResponse>,
 }
 
+/// A transaction streamed by the transaction subscription, along with its sequence number on
+/// the node, which can be used to resume the subscription.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct SuiTransactionStreamResponse {
+    pub seq: TxSequenceNumber,
+    #[serde(flatten)]
+    pub response: SuiTransactionResponse,
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 pub enum SuiParsedTransactionResponse {
     Publish(SuiParsedPublishResponse),
@@ -946,6 +969,23 @@ impl Display for SuiParsedPublishResponse {
 pub type GetObjectDataResponse = SuiObjectRead<SuiParsedData>;
 pub type GetRawObjectDataResponse = SuiObjectRead<SuiRawData>;
 
+/// Result of one item of a multi-get request. Items fail independently of each other.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(tag = "status", content = "details")]
+pub enum MultiGetResult<T> {
+    Ok(T),
+    Error(String),
+}
+
+impl<T, E: Display> From<Result<T, E>> for MultiGetResult<T> {
+    fn from(result: Result<T, E>) -> Self {
+        match result {
+            Ok(item) => Self::Ok(item),
+            Err(e) => Self::Error(e.to_string()),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
 #[serde(tag = "status", content = "details", rename = "ObjectRead")]
 pub enum SuiObjectRead<T: SuiData> {
@@ -1007,6 +1047,8 @@ pub enum SuiPastObjectRead<T: SuiData> {
     ObjectDeleted(SuiObjectRef),
     /// The object exists but not found with this version
     VersionNotFound(ObjectID, SequenceNumber),
+    /// The object existed with this version, but it was superseded and pruned from the store
+    VersionPruned(ObjectID, SequenceNumber),
     /// The asked object version is higher than the latest
     VersionTooHigh {
         object_id: ObjectID,
@@ -1028,6 +1070,10 @@ impl<T: SuiData> SuiPastObjectRead<T> {
                 object_id: *id,
                 version: *seq_num,
             }),
+            Self::VersionPruned(id, seq_num) => Err(SuiError::ObjectVersionPruned {
+                object_id: *id,
+                version: *seq_num,
+            }),
             Self::VersionTooHigh {
                 object_id,
                 asked_version,
@@ -1051,6 +1097,9 @@ impl<T: SuiData> SuiPastObjectRead<T> {
             Self::VersionNotFound(object_id, version) => {
                 Err(SuiError::ObjectVersionNotFound { object_id, version })
             }
+            Self::VersionPruned(object_id, version) => {
+                Err(SuiError::ObjectVersionPruned { object_id, version })
+            }
             Self::VersionTooHigh {
                 object_id,
                 asked_version,
@@ -1079,6 +1128,9 @@ impl<T: SuiData> TryFrom<PastObjectRead> for SuiPastObjectRead<T> {
             PastObjectRead::VersionNotFound(id, seq_num) => {
                 Ok(SuiPastObjectRead::VersionNotFound(id, seq_num))
             }
+            PastObjectRead::VersionPruned(id, seq_num) => {
+                Ok(SuiPastObjectRead::VersionPruned(id, seq_num))
+            }
             PastObjectRead::VersionTooHigh {
                 object_id,
                 asked_version,
@@ -1858,6 +1910,12 @@ pub struct SuiEventEnvelope {
     pub timestamp: u64,
     /// Transaction digest of associated transaction, if any
     pub tx_digest: Option<TransactionDigest>,
+    /// Sequential event ID, ie (transaction seq number, event seq number).
+    /// 1) Serves as a unique event ID for each fullnode
+    /// 2) Also serves to sequence events for the purposes of pagination and querying.
+    ///    A higher id is an event seen later by that fullnode.
+    /// This ID is the "cursor" for event querying.
+    pub id: EventID,
     /// Specific event type
     pub event: SuiEvent,
 }
@@ -2008,6 +2066,7 @@ impl PartialEq<SuiEventEnvelope> for EventEnvelope {
     fn eq(&self, other: &SuiEventEnvelope) -> bool {
         self.timestamp == other.timestamp
             && self.tx_digest == other.tx_digest
+            && self.id() == other.id
             && self.event == other.event
     }
 }
@@ -2212,6 +2271,247 @@ impl From<ObjectInfo> for SuiObjectInfo {
     }
 }
 
+/// Total balance of the `Coin<T>` objects of one coin type `T` owned by an address.
+#[serde_as]
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    pub coin_type: String,
+    pub coin_object_count: usize,
+    /// Serialized as a string, as it can exceed the precision of JSON numbers.
+    #[schemars(with = "String")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub total_balance: u128,
+}
+
+impl Balance {
+    /// Sums the balances of the `Coin<T>` objects among `objects` by coin type `T`, other objects
+    /// being skipped. The balances are sorted by coin type.
+    pub fn from_coins<'a>(
+        objects: impl IntoIterator<Item = &'a Object>,
+    ) -> Result<Vec<Self>, SuiError> {
+        let mut balances: BTreeMap<TypeTag, (usize, u128)> = BTreeMap::new();
+        for object in objects {
+            let value = match Coin::extract_balance_if_coin(object)? {
+                Some(value) => value,
+                None => continue,
+            };
+            let coin_type = match object.data.type_().and_then(|tag| tag.type_params.first()) {
+                Some(coin_type) => coin_type.clone(),
+                None => continue,
+            };
+            let (count, total) = balances.entry(coin_type).or_default();
+            *count += 1;
+            *total += value as u128;
+        }
+        Ok(balances
+            .into_iter()
+            .map(|(coin_type, (coin_object_count, total_balance))| Balance {
+                coin_type: coin_type.to_string(),
+                coin_object_count,
+                total_balance,
+            })
+            .collect())
+    }
+
+    /// The balance of an owner without coins of type `coin_type`.
+    pub fn zero(coin_type: String) -> Self {
+        Balance {
+            coin_type,
+            coin_object_count: 0,
+            total_balance: 0,
+        }
+    }
+}
+
+/// Reference and balance of a `Coin<T>` object.
+#[derive(Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SuiCoin {
+    pub coin_type: String,
+    pub coin_object_id: ObjectID,
+    pub version: SequenceNumber,
+    pub digest: ObjectDigest,
+    pub balance: u64,
+}
+
+impl SuiCoin {
+    pub fn object_ref(&self) -> ObjectRef {
+        (self.coin_object_id, self.version, self.digest)
+    }
+}
+
+impl TryFrom<&Object> for SuiCoin {
+    type Error = anyhow::Error;
+
+    fn try_from(object: &Object) -> Result<Self, Self::Error> {
+        let coin_type = object
+            .data
+            .type_()
+            .and_then(|tag| tag.type_params.first())
+            .ok_or_else(|| anyhow::anyhow!("Object {} is not a coin", object.id()))?;
+        let balance = Coin::extract_balance_if_coin(object)?
+            .ok_or_else(|| anyhow::anyhow!("Object {} is not a coin", object.id()))?;
+        let (coin_object_id, version, digest) = object.compute_object_reference();
+        Ok(Self {
+            coin_type: coin_type.to_string(),
+            coin_object_id,
+            version,
+            digest,
+            balance,
+        })
+    }
+}
+
+/// Effects of a dry-run transaction, together with the balance and object changes the
+/// transaction would make if executed.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "DryRunTransactionResponse", rename_all = "camelCase")]
+pub struct SuiDryRunTransactionResponse {
+    #[serde(flatten)]
+    pub effects: SuiTransactionEffects,
+    pub balance_changes: Vec<BalanceChange>,
+    pub object_changes: Vec<SuiObjectChange>,
+}
+
+impl SuiDryRunTransactionResponse {
+    pub fn new(
+        effects: SuiTransactionEffects,
+        store: &InnerTemporaryStore,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            effects,
+            balance_changes: BalanceChange::from_store(store)?,
+            object_changes: SuiObjectChange::from_store(store),
+        })
+    }
+}
+
+/// Net change of the balance of the `Coin<T>` objects of one coin type `T` owned by an owner.
+#[serde_as]
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceChange {
+    pub owner: Owner,
+    pub coin_type: String,
+    /// Serialized as a string, as it can exceed the precision of JSON numbers.
+    #[schemars(with = "String")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount: i128,
+}
+
+impl BalanceChange {
+    /// Compare the coins among the input objects of a transaction with the coins it wrote.
+    /// Owners whose balance of a coin type is unchanged are omitted.
+    fn from_store(store: &InnerTemporaryStore) -> Result<Vec<Self>, anyhow::Error> {
+        let mut amounts: BTreeMap<(Owner, TypeTag), i128> = BTreeMap::new();
+        let changed = store.written.keys().chain(store.deleted.keys());
+        for object in changed.filter_map(|id| store.objects.get(id)) {
+            if let Some((coin_type, value)) = Self::coin_balance(object)? {
+                *amounts.entry((object.owner, coin_type)).or_default() -= value as i128;
+            }
+        }
+        for (_, object, _) in store.written.values() {
+            if let Some((coin_type, value)) = Self::coin_balance(object)? {
+                *amounts.entry((object.owner, coin_type)).or_default() += value as i128;
+            }
+        }
+        Ok(amounts
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0)
+            .map(|((owner, coin_type), amount)| Self {
+                owner,
+                coin_type: coin_type.to_string(),
+                amount,
+            })
+            .collect())
+    }
+
+    fn coin_balance(object: &Object) -> Result<Option<(TypeTag, u64)>, anyhow::Error> {
+        let value = match Coin::extract_balance_if_coin(object)? {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        Ok(object
+            .data
+            .type_()
+            .and_then(|tag| tag.type_params.first())
+            .map(|coin_type| (coin_type.clone(), value)))
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectChangeKind")]
+pub enum SuiObjectChangeKind {
+    Created,
+    /// Modified in place, without changing its owner.
+    Mutated,
+    /// Modified and given a new owner.
+    Transferred,
+    Unwrapped,
+    Wrapped,
+    Deleted,
+}
+
+/// A change made by a transaction to one object, with its owner before and after.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "ObjectChange", rename_all = "camelCase")]
+pub struct SuiObjectChange {
+    pub kind: SuiObjectChangeKind,
+    pub object_id: ObjectID,
+    pub object_type: Option<String>,
+    /// Version of the object after the transaction.
+    pub version: SequenceNumber,
+    pub owner_before: Option<Owner>,
+    pub owner_after: Option<Owner>,
+}
+
+impl SuiObjectChange {
+    fn from_store(store: &InnerTemporaryStore) -> Vec<Self> {
+        let object_type = |object: &Object| match object.data.type_() {
+            Some(tag) => tag.to_string(),
+            None => "Package".to_string(),
+        };
+        let written = store.written.iter().map(|(id, (obj_ref, object, kind))| {
+            let before = store.objects.get(id);
+            let kind = match kind {
+                WriteKind::Create => SuiObjectChangeKind::Created,
+                WriteKind::Unwrap => SuiObjectChangeKind::Unwrapped,
+                WriteKind::Mutate => match before {
+                    Some(before) if before.owner != object.owner => {
+                        SuiObjectChangeKind::Transferred
+                    }
+                    _ => SuiObjectChangeKind::Mutated,
+                },
+            };
+            Self {
+                kind,
+                object_id: *id,
+                object_type: Some(object_type(object)),
+                version: obj_ref.1,
+                owner_before: before.map(|before| before.owner),
+                owner_after: Some(object.owner),
+            }
+        });
+        let deleted = store.deleted.iter().map(|(id, (version, kind))| {
+            let before = store.objects.get(id);
+            let kind = match kind {
+                DeleteKind::Wrap => SuiObjectChangeKind::Wrapped,
+                DeleteKind::Normal | DeleteKind::UnwrapThenDelete => SuiObjectChangeKind::Deleted,
+            };
+            Self {
+                kind,
+                object_id: *id,
+                object_type: before.map(object_type),
+                version: *version,
+                owner_before: before.map(|before| before.owner),
+                owner_after: None,
+            }
+        });
+        written.chain(deleted).collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ObjectExistsResponse {
@@ -2296,6 +2596,7 @@ pub enum SuiEventFilter {
         value: Value,
     },
     SenderAddress(SuiAddress),
+    Recipient(Owner),
     EventType(EventType),
     ObjectId(ObjectID),
     All(Vec<SuiEventFilter>),
@@ -2318,6 +2619,7 @@ impl TryInto<EventFilter> for SuiEventFilter {
             }
             MoveEventField { path, value } => EventFilter::MoveEventField { path, value },
             SenderAddress(address) => EventFilter::SenderAddress(address),
+            Recipient(recipient) => EventFilter::Recipient(recipient),
             ObjectId(id) => EventFilter::ObjectId(id),
             All(filters) => EventFilter::MatchAll(
                 filters
@@ -2374,3 +2676,54 @@ pub struct Page<T, C> {
     pub data: Vec<T>,
     pub next_cursor: Option<C>,
 }
+
+/// A checkpoint summary certified by a quorum of validators.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CheckpointSummary", rename_all = "camelCase")]
+pub struct SuiCheckpointSummary {
+    pub epoch: EpochId,
+    pub sequence_number: CheckpointSequenceNumber,
+    /// digest of this checkpoint summary, referenced by the next checkpoint's `previousDigest`
+    pub digest: Base64,
+    pub content_digest: Base64,
+    pub previous_digest: Option<Base64>,
+    /// committee of the next epoch, if this is the last checkpoint of the epoch
+    pub next_epoch_committee: Option<Vec<(AuthorityName, StakeUnit)>>,
+    /// authority signature information signed by the quorum of the validators.
+    pub auth_sign_info: AuthorityWeakQuorumSignInfo,
+}
+
+impl From<CertifiedCheckpointSummary> for SuiCheckpointSummary {
+    fn from(checkpoint: CertifiedCheckpointSummary) -> Self {
+        let digest = Base64::from_bytes(&checkpoint.summary.digest());
+        let summary = checkpoint.summary;
+        Self {
+            epoch: summary.epoch,
+            sequence_number: summary.sequence_number,
+            digest,
+            content_digest: Base64::from_bytes(&summary.content_digest),
+            previous_digest: summary
+                .previous_digest
+                .map(|digest| Base64::from_bytes(&digest)),
+            next_epoch_committee: summary.next_epoch_committee,
+            auth_sign_info: checkpoint.auth_signature,
+        }
+    }
+}
+
+/// The transactions included in a checkpoint, in checkpoint order.
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema)]
+#[serde(rename = "CheckpointContents", rename_all = "camelCase")]
+pub struct SuiCheckpointContents {
+    pub sequence_number: CheckpointSequenceNumber,
+    pub transactions: Vec<ExecutionDigests>,
+}
+
+impl SuiCheckpointContents {
+    pub fn new(sequence_number: CheckpointSequenceNumber, contents: &CheckpointContents) -> Self {
+        Self {
+            sequence_number,
+            transactions: contents.iter().cloned().collect(),
+        }
+    }
+}
@@ -11,12 +11,12 @@ use move_core_types::value::{MoveStruct, MoveValue};
 
 use sui_types::base_types::SequenceNumber;
 use sui_types::base_types::{ObjectID, SuiAddress};
-use sui_types::gas_coin::GasCoin;
-use sui_types::object::MoveObject;
+use sui_types::gas_coin::{GasCoin, GAS};
+use sui_types::object::{MoveObject, Object};
 use sui_types::sui_serde::Base64;
 use sui_types::{MOVE_STDLIB_ADDRESS, SUI_FRAMEWORK_ADDRESS};
 
-use crate::{SuiMoveStruct, SuiMoveValue};
+use crate::{Balance, SuiMoveStruct, SuiMoveValue};
 
 #[test]
 fn test_move_value_to_sui_bytearray() {
@@ -159,3 +159,30 @@ fn test_serde() {
         )
     }
 }
+
+#[test]
+fn test_balance_from_coins() {
+    let owner = SuiAddress::random_for_testing_only();
+    let coins = [
+        Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, u64::MAX),
+        Object::with_id_owner_gas_for_testing(ObjectID::random(), owner, 1),
+    ];
+    let balances = Balance::from_coins(&coins).unwrap();
+    let total_balance = u64::MAX as u128 + 1;
+    assert_eq!(
+        balances,
+        vec![Balance {
+            coin_type: GAS::type_().to_string(),
+            coin_object_count: 2,
+            total_balance,
+        }]
+    );
+
+    // Balances exceed the precision of JSON numbers
+    let json = serde_json::to_value(&balances[0]).unwrap();
+    assert_eq!(json["totalBalance"], total_balance.to_string());
+    assert_eq!(
+        serde_json::from_value::<Balance>(json).unwrap(),
+        balances[0]
+    );
+}
@@ -8,21 +8,25 @@ use jsonrpsee_proc_macros::rpc;
 
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, GetRawObjectDataResponse,
-    MoveFunctionArgType, RPCTransactionRequestParams, SuiEventEnvelope, SuiEventFilter,
-    SuiExecuteTransactionResponse, SuiGasCostSummary, SuiMoveNormalizedFunction,
-    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
-    SuiTransactionFilter, SuiTransactionResponse, SuiTypeTag, TransactionBytes, TransactionsPage,
+    Balance, CoinPage, EventPage, GetObjectDataResponse, GetPastObjectDataResponse,
+    GetRawObjectDataResponse, MoveFunctionArgType, MultiGetResult, ObjectsPage,
+    RPCTransactionRequestParams, SuiCheckpointContents, SuiCheckpointSummary,
+    SuiDryRunTransactionResponse, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse,
+    SuiGasCostSummary, SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct,
+    SuiObjectInfo, SuiTransactionFilter, SuiTransactionResponse, SuiTransactionStreamResponse,
+    SuiTypeTag, TransactionBytes, TransactionsPage,
 };
 use sui_open_rpc_macros::open_rpc;
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::committee::EpochId;
 use sui_types::crypto::SignatureScheme;
+use sui_types::event::EventID;
 use sui_types::messages::CommitteeInfoResponse;
 use sui_types::messages::ExecuteTransactionRequestType;
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::object::Owner;
-use sui_types::query::{Ordering, TransactionQuery};
+use sui_types::query::{EventQuery, Ordering, TransactionQuery};
 use sui_types::sui_serde::Base64;
 
 /// Maximum number of events returned in an event query.
@@ -31,6 +35,15 @@ use sui_types::sui_serde::Base64;
 /// for document purposes.
 pub const MAX_RESULT_SIZE: usize = 4096;
 
+/// Maximum number of events returned in one page of an event query.
+/// Kept below EVENT_STORE_QUERY_MAX_LIMIT in `sui-storage` crate, as one extra event
+/// is read from the event store to produce the next page cursor.
+pub const EVENT_QUERY_MAX_LIMIT: usize = 100;
+
+/// Maximum number of items in one multi-get request served by the Gateway.
+/// Full nodes read this limit from `json-rpc-multi-get-max-batch-size` in the node config.
+pub const MULTI_GET_MAX_BATCH_SIZE: usize = 50;
+
 #[open_rpc(namespace = "sui", tag = "Gateway Transaction Execution API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait RpcGatewayApi {
@@ -109,6 +122,42 @@ pub trait RpcReadApi {
         /// the ID of the queried object
         object_id: ObjectID,
     ) -> RpcResult<GetObjectDataResponse>;
+
+    /// Return the total balance and coin object count of the `Coin<T>` objects of one coin type owned by an address.
+    #[method(name = "getBalance")]
+    async fn get_balance(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the coin type `T` of the queried `Coin<T>` objects, e.g. `0x2::sui::SUI`, default to `0x2::sui::SUI` if not specified
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance>;
+
+    /// Return the total balance and coin object count of the `Coin<T>` objects owned by an address, for each coin type `T`.
+    #[method(name = "getAllBalances")]
+    async fn get_all_balances(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+    ) -> RpcResult<Vec<Balance>>;
+
+    /// Return the object information for a batch of objects, in the order of the given IDs.
+    /// A failure to read one object does not fail the other items of the batch.
+    #[method(name = "multiGetObjects")]
+    async fn multi_get_objects(
+        &self,
+        /// the IDs of the queried objects, up to the server's maximum batch size
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<MultiGetResult<GetObjectDataResponse>>>;
+
+    /// Return the transaction response objects for a batch of transactions, in the order of the given digests.
+    /// A failure to read one transaction does not fail the other items of the batch.
+    #[method(name = "multiGetTransactions")]
+    async fn multi_get_transactions(
+        &self,
+        /// the digests of the queried transactions, up to the server's maximum batch size
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<MultiGetResult<SuiTransactionResponse>>>;
 }
 
 #[open_rpc(namespace = "sui", tag = "Full Node API")]
@@ -121,7 +170,7 @@ pub trait RpcFullNodeReadApi {
         sig_scheme: SignatureScheme,
         signature: Base64,
         pub_key: Base64,
-    ) -> RpcResult<SuiTransactionEffects>;
+    ) -> RpcResult<SuiDryRunTransactionResponse>;
 
     /// Return the argument types of a Move function,
     /// based on normalized Type.
@@ -180,6 +229,66 @@ pub trait RpcFullNodeReadApi {
         order: Ordering,
     ) -> RpcResult<TransactionsPage>;
 
+    /// Return the `Coin<T>` objects of one coin type owned by an address, with their balances, ordered by object ID.
+    #[method(name = "getCoins")]
+    async fn get_coins(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the coin type `T` of the queried `Coin<T>` objects, e.g. `0x2::sui::SUI`, default to `0x2::sui::SUI` if not specified
+        coin_type: Option<String>,
+        /// Optional paging cursor, the object ID of the first coin of the page
+        cursor: Option<ObjectID>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage>;
+
+    /// Return the live objects of a Move struct type owned by an address, ordered by object ID.
+    #[method(name = "getObjectsOwnedByAddressAndType")]
+    async fn get_objects_owned_by_address_and_type(
+        &self,
+        /// the owner's Sui address
+        owner: SuiAddress,
+        /// the Move struct type of the queried objects, e.g. `0x2::devnet_nft::DevNetNFT`
+        struct_type: String,
+        /// Optional paging cursor, the object ID of the first object of the page
+        cursor: Option<ObjectID>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
+    /// Return the live objects of a Move struct type, whatever their owner, ordered by object ID.
+    #[method(name = "getObjectsByType")]
+    async fn get_objects_by_type(
+        &self,
+        /// the Move struct type of the queried objects, including its type arguments, e.g. `0x2::coin::Coin<0x2::sui::SUI>`
+        struct_type: String,
+        /// Optional paging cursor, the object ID of the first object of the page
+        cursor: Option<ObjectID>,
+        /// Maximum item returned per page
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage>;
+
+    /// Return the sequence number of the latest certified checkpoint known to the server.
+    #[method(name = "getLatestCheckpointSequenceNumber")]
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber>;
+
+    /// Return the certified summary of a checkpoint, including the signatures of the validator quorum.
+    #[method(name = "getCheckpointSummary")]
+    async fn get_checkpoint_summary(
+        &self,
+        /// the sequence number of the queried checkpoint
+        seq: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiCheckpointSummary>;
+
+    /// Return the transaction and effects digests included in a checkpoint, in checkpoint order.
+    #[method(name = "getCheckpointContents")]
+    async fn get_checkpoint_contents(
+        &self,
+        /// the sequence number of the queried checkpoint
+        seq: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiCheckpointContents>;
+
     /// Note there is no software-level guarantee/SLA that objects with past versions
     /// can be retrieved by this API, even if the object and version exists/existed.
     /// The result may vary across nodes depending on their pruning policies.
@@ -369,31 +478,64 @@ pub trait RpcBcsApi {
 #[open_rpc(namespace = "sui", tag = "Transaction Subscription")]
 #[rpc(server, client, namespace = "sui")]
 pub trait TransactionStreamingApi {
-    /// Subscribe to a stream of Sui event
-    #[subscription(name = "subscribeTransaction", item = SuiTransactionResponse)]
+    /// Subscribe to a stream of Sui transaction, optionally replaying past transactions first
+    #[subscription(name = "subscribeTransaction", item = SuiTransactionStreamResponse)]
     fn subscribe_transaction(
         &self,
         /// the filter criteria of the transaction stream.
         filter: SuiTransactionFilter,
+        /// optional sequence number of the first transaction to deliver. Executed transactions from it onwards are replayed before live transactions are streamed.
+        start_seq: Option<TxSequenceNumber>,
     );
 }
 
 #[open_rpc(namespace = "sui", tag = "Event Subscription")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventStreamingApi {
-    /// Subscribe to a stream of Sui event
+    /// Subscribe to a stream of Sui event, optionally resuming from a past event
     #[subscription(name = "subscribeEvent", item = SuiEventEnvelope)]
     fn subscribe_event(
         &self,
         /// the filter criteria of the event stream, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
         filter: SuiEventFilter,
+        /// optional cursor, the ID of the first event to deliver. Stored events from the cursor onwards are replayed before live events are streamed.
+        cursor: Option<EventID>,
     );
 }
 
 #[open_rpc(namespace = "sui", tag = "Event Read API")]
 #[rpc(server, client, namespace = "sui")]
 pub trait EventReadApi {
+    /// Return list of events for a specified query criteria.
+    #[method(name = "getEvents")]
+    async fn get_events(
+        &self,
+        /// the event query criteria.
+        query: EventQuery,
+        /// optional paging cursor, the ID of the first event to return
+        cursor: Option<EventID>,
+        /// maximum number of items per page, default to EVENT_QUERY_MAX_LIMIT if not specified.
+        limit: Option<usize>,
+        /// event query ordering
+        order: Ordering,
+    ) -> RpcResult<EventPage>;
+
+    /// Return list of events matching an event filter, which may combine several criteria.
+    #[method(name = "queryEvents")]
+    async fn query_events(
+        &self,
+        /// the filter criteria of the events, see the [Sui docs](https://docs.sui.io/build/pubsub#event-filters) for detailed examples.
+        filter: SuiEventFilter,
+        /// optional paging cursor, the ID of the first event to return
+        cursor: Option<EventID>,
+        /// maximum number of items per page, default to EVENT_QUERY_MAX_LIMIT if not specified.
+        limit: Option<usize>,
+        /// event query ordering
+        order: Ordering,
+    ) -> RpcResult<EventPage>;
+
     /// Return events emitted by the given transaction.
+    /// Deprecated, use `sui_getEvents` with the `Transaction` query.
     #[method(name = "getEventsByTransaction")]
     async fn get_events_by_transaction(
         &self,
@@ -403,7 +545,8 @@ pub trait EventReadApi {
         count: usize,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events emitted in a specified Move module
+    /// Return events emitted in a specified Move module, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `MoveModule` query.
     #[method(name = "getEventsByModule")]
     async fn get_events_by_transaction_module(
         &self,
@@ -419,7 +562,8 @@ pub trait EventReadApi {
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events with the given move event struct name
+    /// Return events with the given move event struct name, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `MoveEvent` query.
     #[method(name = "getEventsByMoveEventStructName")]
     async fn get_events_by_move_event_struct_name(
         &self,
@@ -433,7 +577,8 @@ pub trait EventReadApi {
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events associated with the given sender.
+    /// Return events associated with the given sender, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `Sender` query.
     #[method(name = "getEventsBySender")]
     async fn get_events_by_sender(
         &self,
@@ -447,7 +592,8 @@ pub trait EventReadApi {
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events associated with the given recipient
+    /// Return events associated with the given recipient, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `Recipient` query.
     #[method(name = "getEventsByRecipient")]
     async fn get_events_by_recipient(
         &self,
@@ -461,7 +607,8 @@ pub trait EventReadApi {
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events associated with the given object
+    /// Return events associated with the given object, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `Object` query.
     #[method(name = "getEventsByObject")]
     async fn get_events_by_object(
         &self,
@@ -475,7 +622,8 @@ pub trait EventReadApi {
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>>;
 
-    /// Return events emitted in [start_time, end_time) interval
+    /// Return events emitted in [start_time, end_time) interval, in descending time.
+    /// Deprecated, use `sui_getEvents` with the `TimeRange` query.
     #[method(name = "getEventsByTimeRange")]
     async fn get_events_by_timerange(
         &self,
@@ -2,25 +2,27 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::api::EventReadApiServer;
 use crate::api::EventStreamingApiServer;
+use crate::api::EVENT_QUERY_MAX_LIMIT;
 use crate::streaming_api::spawn_subscription;
 use crate::SuiRpcModule;
+use anyhow::anyhow;
 use async_trait::async_trait;
 use futures::StreamExt;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee::types::SubscriptionResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 use jsonrpsee_core::server::rpc_module::SubscriptionSink;
-use move_core_types::account_address::AccountAddress;
-use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::ModuleId;
-use std::str::FromStr;
+use std::cmp;
 use std::sync::Arc;
 use sui_core::authority::AuthorityState;
 use sui_core::event_handler::EventHandler;
-use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope, SuiEventFilter};
+use sui_json_rpc_types::{EventPage, SuiEvent, SuiEventEnvelope, SuiEventFilter};
 use sui_open_rpc::Module;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::event::EventID;
+use sui_types::filter::EventFilter;
 use sui_types::object::Owner;
+use sui_types::query::{EventQuery, Ordering};
 use tracing::warn;
 
 pub struct EventStreamingApiImpl {
@@ -43,6 +45,7 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
         &self,
         mut sink: SubscriptionSink,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> SubscriptionResult {
         let filter = match filter.try_into() {
             Ok(filter) => filter,
@@ -53,6 +56,12 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
             }
         };
 
+        if let Some(cursor) = cursor {
+            let stream = self.event_handler.subscribe_from(filter, cursor);
+            spawn_subscription(sink, Box::pin(stream));
+            return Ok(());
+        }
+
         let state = self.state.clone();
         let stream = self.event_handler.subscribe(filter);
         let stream = stream.map(move |e| {
@@ -60,6 +69,7 @@ impl EventStreamingApiServer for EventStreamingApiImpl {
             event.map(|event| SuiEventEnvelope {
                 timestamp: e.timestamp,
                 tx_digest: e.tx_digest,
+                id: e.id(),
                 event,
             })
         });
@@ -94,16 +104,54 @@ impl EventReadApiImpl {
     }
 }
 
-#[allow(unused)]
 #[async_trait]
 impl EventReadApiServer for EventReadApiImpl {
+    async fn get_events(
+        &self,
+        query: EventQuery,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        order: Ordering,
+    ) -> RpcResult<EventPage> {
+        let limit = page_limit(limit)?;
+        let descending = order == Ordering::Descending;
+
+        // Retrieve 1 extra item for next cursor
+        let data = self
+            .state
+            .get_events(query, cursor, limit + 1, descending)
+            .await?;
+        Ok(to_event_page(data, limit))
+    }
+
+    async fn query_events(
+        &self,
+        filter: SuiEventFilter,
+        cursor: Option<EventID>,
+        limit: Option<usize>,
+        order: Ordering,
+    ) -> RpcResult<EventPage> {
+        let limit = page_limit(limit)?;
+        let descending = order == Ordering::Descending;
+        let filter: EventFilter = filter.try_into()?;
+
+        // Retrieve 1 extra item for next cursor
+        let data = self
+            .state
+            .query_events(filter, cursor, limit + 1, descending)
+            .await?;
+        Ok(to_event_page(data, limit))
+    }
+
     async fn get_events_by_transaction(
         &self,
         digest: TransactionDigest,
         count: usize,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self.state.get_events_by_transaction(digest, count).await?;
-        Ok(events)
+        Ok(self
+            .state
+            .get_events(EventQuery::Transaction(digest), None, count, false)
+            .await?)
     }
 
     async fn get_events_by_transaction_module(
@@ -114,16 +162,12 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let module_id = ModuleId::new(
-            AccountAddress::from(package),
-            Identifier::from_str(&module)?,
-        );
-
-        let events = self
-            .state
-            .get_events_by_transaction_module(&module_id, start_time, end_time, count)
-            .await?;
-        Ok(events)
+        let filter = SuiEventFilter::All(vec![
+            SuiEventFilter::Package(package),
+            SuiEventFilter::Module(module),
+        ]);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
     }
 
     async fn get_events_by_move_event_struct_name(
@@ -133,16 +177,9 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self
-            .state
-            .get_events_by_move_event_struct_name(
-                &move_event_struct_name,
-                start_time,
-                end_time,
-                count,
-            )
-            .await?;
-        Ok(events)
+        let filter = SuiEventFilter::MoveEventType(move_event_struct_name);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
     }
 
     async fn get_events_by_sender(
@@ -152,11 +189,9 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self
-            .state
-            .get_events_by_sender(&sender, start_time, end_time, count)
-            .await?;
-        Ok(events)
+        let filter = SuiEventFilter::SenderAddress(sender);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
     }
 
     async fn get_events_by_recipient(
@@ -166,11 +201,9 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self
-            .state
-            .get_events_by_recipient(&recipient, start_time, end_time, count)
-            .await?;
-        Ok(events)
+        let filter = SuiEventFilter::Recipient(recipient);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
     }
 
     async fn get_events_by_object(
@@ -180,11 +213,9 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self
-            .state
-            .get_events_by_object(&object, start_time, end_time, count)
-            .await?;
-        Ok(events)
+        let filter = SuiEventFilter::ObjectId(object);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
     }
 
     async fn get_events_by_timerange(
@@ -193,14 +224,48 @@ impl EventReadApiServer for EventReadApiImpl {
         start_time: u64,
         end_time: u64,
     ) -> RpcResult<Vec<SuiEventEnvelope>> {
-        let events = self
+        let filter = SuiEventFilter::All(vec![]);
+        self.get_events_in_time_range(filter, count, start_time, end_time)
+            .await
+    }
+}
+
+impl EventReadApiImpl {
+    /// Returns at most `count` events matching `filter` and emitted within [start_time, end_time),
+    /// in ascending time, for the deprecated `sui_getEventsBy*` methods.
+    async fn get_events_in_time_range(
+        &self,
+        filter: SuiEventFilter,
+        count: usize,
+        start_time: u64,
+        end_time: u64,
+    ) -> RpcResult<Vec<SuiEventEnvelope>> {
+        let filter: EventFilter = filter.try_into()?;
+        Ok(self
             .state
-            .get_events_by_timerange(start_time, end_time, count)
-            .await?;
-        Ok(events)
+            .get_events_in_time_range(filter, start_time, end_time, count)
+            .await?)
     }
 }
 
+fn page_limit(limit: Option<usize>) -> Result<usize, anyhow::Error> {
+    let limit = cmp::min(
+        limit.unwrap_or(EVENT_QUERY_MAX_LIMIT),
+        EVENT_QUERY_MAX_LIMIT,
+    );
+    if limit == 0 {
+        return Err(anyhow!("Page result limit must be larger then 0."));
+    }
+    Ok(limit)
+}
+
+/// Splits `limit + 1` events into a page of `limit` events and the cursor of the next page.
+fn to_event_page(mut data: Vec<SuiEventEnvelope>, limit: usize) -> EventPage {
+    let next_cursor = data.get(limit).map(|event| event.id);
+    data.truncate(limit);
+    EventPage { data, next_cursor }
+}
+
 impl SuiRpcModule for EventReadApiImpl {
     fn rpc(self) -> RpcModule<Self> {
         self.into_rpc()
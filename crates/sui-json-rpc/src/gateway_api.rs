@@ -3,22 +3,29 @@
 
 use crate::api::{
     RpcGatewayApiServer, RpcReadApiServer, RpcTransactionBuilderServer, WalletSyncApiServer,
+    MULTI_GET_MAX_BATCH_SIZE,
 };
 use crate::SuiRpcModule;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use jsonrpsee_core::server::rpc_module::RpcModule;
+use move_core_types::language_storage::StructTag;
 use signature::Signature;
 use sui_core::gateway_state::GatewayClient;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, RPCTransactionRequestParams, SuiObjectInfo, SuiTransactionResponse,
-    SuiTypeTag, TransactionBytes,
+    Balance, GetObjectDataResponse, GetRawObjectDataResponse, MultiGetResult,
+    RPCTransactionRequestParams, SuiObjectInfo, SuiTransactionResponse, SuiTypeTag,
+    TransactionBytes,
 };
 use sui_open_rpc::Module;
 use sui_types::batch::TxSequenceNumber;
+use sui_types::coin::Coin;
 use sui_types::crypto::SignatureScheme;
+use sui_types::gas_coin::GAS;
+use sui_types::object::Object;
+use sui_types::parse_sui_struct_tag;
 use sui_types::sui_serde::Base64;
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
@@ -60,6 +67,34 @@ impl GatewayReadApiImpl {
     pub fn new(client: GatewayClient) -> Self {
         Self { client }
     }
+
+    async fn get_balances(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<&StructTag>,
+    ) -> Result<Vec<Balance>, anyhow::Error> {
+        let coin_struct = coin_type.map(|coin_type| Coin::type_(coin_type.clone()));
+        let mut coins = Vec::new();
+        for info in self.client.get_objects_owned_by_address(owner).await? {
+            let tag = match parse_sui_struct_tag(&info.type_) {
+                Ok(tag) => tag,
+                Err(_) => continue,
+            };
+            let is_match = match &coin_struct {
+                Some(coin_struct) => &tag == coin_struct,
+                None => Coin::is_coin(&tag),
+            };
+            if !is_match {
+                continue;
+            }
+            let object: Object = match self.client.get_raw_object(info.object_id).await? {
+                GetRawObjectDataResponse::Exists(object) => object.try_into()?,
+                _ => continue,
+            };
+            coins.push(object);
+        }
+        Balance::from_coins(&coins).map_err(|e| anyhow!("{e}"))
+    }
 }
 impl TransactionBuilderImpl {
     pub fn new(client: GatewayClient) -> Self {
@@ -159,6 +194,56 @@ impl RpcReadApiServer for GatewayReadApiImpl {
     ) -> RpcResult<Vec<TransactionDigest>> {
         Ok(self.client.get_transactions_in_range(start, end)?)
     }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type = match coin_type {
+            Some(coin_type) => parse_sui_struct_tag(&coin_type)?,
+            None => GAS::type_(),
+        };
+        let balance = self.get_balances(owner, Some(&coin_type)).await?.pop();
+        Ok(balance.unwrap_or_else(|| Balance::zero(coin_type.to_string())))
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        Ok(self.get_balances(owner, None).await?)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<MultiGetResult<GetObjectDataResponse>>> {
+        check_batch_size(object_ids.len())?;
+        let mut results = Vec::with_capacity(object_ids.len());
+        for object_id in object_ids {
+            results.push(self.client.get_object(object_id).await.into());
+        }
+        Ok(results)
+    }
+
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<MultiGetResult<SuiTransactionResponse>>> {
+        check_batch_size(digests.len())?;
+        let mut results = Vec::with_capacity(digests.len());
+        for digest in digests {
+            results.push(self.client.get_transaction(digest).await.into());
+        }
+        Ok(results)
+    }
+}
+
+fn check_batch_size(batch_size: usize) -> Result<(), anyhow::Error> {
+    if batch_size > MULTI_GET_MAX_BATCH_SIZE {
+        return Err(anyhow!(
+            "Batch size {batch_size} exceeds the maximum of {MULTI_GET_MAX_BATCH_SIZE}"
+        ));
+    }
+    Ok(())
 }
 
 impl SuiRpcModule for GatewayReadApiImpl {
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::env;
-use std::net::SocketAddr;
+use std::future::Future;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Instant;
 
 pub use jsonrpsee::http_server;
@@ -12,14 +16,16 @@ use jsonrpsee_core::middleware::{Headers, HttpMiddleware, MethodKind, WsMiddlewa
 use jsonrpsee_core::server::access_control::AccessControlBuilder;
 use jsonrpsee_core::server::rpc_module::RpcModule;
 use prometheus::{
-    register_histogram_vec_with_registry, register_int_counter_vec_with_registry, HistogramVec,
-    IntCounterVec,
+    register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+    register_int_gauge_with_registry, HistogramVec, IntCounterVec, IntGauge,
 };
 use tracing::info;
 
+use sui_config::node::JsonRpcRateLimitConfig;
 use sui_open_rpc::{Module, Project};
 
 use crate::http_server::{HttpServerBuilder, HttpServerHandle};
+use crate::rate_limit::{start_rate_limited_proxy, RateLimitedProxyHandle, RateLimiter};
 use crate::ws_server::{WsServerBuilder, WsServerHandle};
 
 pub mod api;
@@ -27,6 +33,7 @@ pub mod bcs_api;
 pub mod estimator_api;
 pub mod event_api;
 pub mod gateway_api;
+pub mod rate_limit;
 pub mod read_api;
 pub mod streaming_api;
 pub mod transaction_builder_api;
@@ -38,10 +45,31 @@ pub enum ServerBuilder<M = ()> {
 }
 
 pub enum ServerHandle {
-    HttpHandler(HttpServerHandle, SocketAddr),
+    HttpHandler(JsonRpcHttpServerHandle, SocketAddr),
     WsHandle(WsServerHandle, SocketAddr),
 }
 
+/// Handle of the HTTP server, resolving when the server stops. The rate limiting proxy in front
+/// of the server, if any, is stopped with the server or when the handle is dropped.
+pub struct JsonRpcHttpServerHandle {
+    server: HttpServerHandle,
+    _proxy: Option<RateLimitedProxyHandle>,
+}
+
+impl JsonRpcHttpServerHandle {
+    pub fn stop(self) -> Result<tokio::task::JoinHandle<()>, jsonrpsee_core::Error> {
+        self.server.stop()
+    }
+}
+
+impl Future for JsonRpcHttpServerHandle {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.server).poll(cx)
+    }
+}
+
 #[derive(Clone)]
 pub enum ApiMetrics {
     JsonRpcMetrics(JsonRpcMetrics),
@@ -49,7 +77,7 @@ pub enum ApiMetrics {
 }
 
 impl ServerHandle {
-    pub fn into_http_server_handle(self) -> Option<HttpServerHandle> {
+    pub fn into_http_server_handle(self) -> Option<JsonRpcHttpServerHandle> {
         match self {
             ServerHandle::HttpHandler(handle, _) => Some(handle),
             _ => None,
@@ -74,6 +102,8 @@ pub struct JsonRpcServerBuilder {
     module: RpcModule<()>,
     server_builder: ServerBuilder<ApiMetrics>,
     rpc_doc: Project,
+    metrics: Option<JsonRpcMetrics>,
+    rate_limit: Option<JsonRpcRateLimitConfig>,
 }
 
 pub fn sui_rpc_doc() -> Project {
@@ -107,20 +137,21 @@ impl JsonRpcServerBuilder {
         .build();
         info!(?acl);
 
-        let server_builder = if use_websocket {
-            ServerBuilder::WsBuilder(
+        let (server_builder, metrics) = if use_websocket {
+            let server_builder = ServerBuilder::WsBuilder(
                 WsServerBuilder::default()
                     .set_access_control(acl)
                     .set_middleware(ApiMetrics::WebsocketMetrics(WebsocketMetrics {})),
-            )
+            );
+            (server_builder, None)
         } else {
-            ServerBuilder::HttpBuilder(
+            let metrics = JsonRpcMetrics::new(prometheus_registry);
+            let server_builder = ServerBuilder::HttpBuilder(
                 HttpServerBuilder::default()
                     .set_access_control(acl)
-                    .set_middleware(ApiMetrics::JsonRpcMetrics(JsonRpcMetrics::new(
-                        prometheus_registry,
-                    ))),
-            )
+                    .set_middleware(ApiMetrics::JsonRpcMetrics(metrics.clone())),
+            );
+            (server_builder, Some(metrics))
         };
 
         let module = RpcModule::new(());
@@ -129,6 +160,8 @@ impl JsonRpcServerBuilder {
             module,
             server_builder,
             rpc_doc: sui_rpc_doc(),
+            metrics,
+            rate_limit: None,
         })
     }
 
@@ -151,9 +184,17 @@ impl JsonRpcServerBuilder {
             module,
             server_builder,
             rpc_doc: sui_rpc_doc(),
+            metrics: None,
+            rate_limit: None,
         })
     }
 
+    /// Limit the rate of calls of every client IP. Only applies to the HTTP server, which is
+    /// then fronted by a rate limiting proxy. Starting the server fails if the limits are invalid.
+    pub fn set_rate_limit(&mut self, rate_limit: JsonRpcRateLimitConfig) {
+        self.rate_limit = Some(rate_limit);
+    }
+
     pub fn register_module<T: SuiRpcModule>(&mut self, module: T) -> Result<(), anyhow::Error> {
         self.rpc_doc.add_module(T::rpc_doc_module());
         Ok(self.module.merge(module.rpc())?)
@@ -167,12 +208,37 @@ impl JsonRpcServerBuilder {
             .register_method("rpc.discover", move |_, _| Ok(self.rpc_doc.clone()))?;
         let methods_names = self.module.method_names().collect::<Vec<_>>();
         let (handle, server_name) = match self.server_builder {
-            ServerBuilder::HttpBuilder(http_builder) => {
-                let server = http_builder.build(listen_address).await?;
-                let addr = server.local_addr()?;
-                let handle = server.start(self.module)?;
-                (ServerHandle::HttpHandler(handle, addr), "JSON-RPC")
-            }
+            ServerBuilder::HttpBuilder(http_builder) => match self.rate_limit {
+                Some(rate_limit) => {
+                    let known_methods = methods_names.iter().map(|name| name.to_string()).collect();
+                    let limiter =
+                        Arc::new(RateLimiter::new(rate_limit, self.metrics, known_methods)?);
+                    let server = http_builder
+                        .build(SocketAddr::new(Ipv4Addr::LOCALHOST.into(), 0))
+                        .await?;
+                    let upstream = server.local_addr()?;
+                    let server = server.start(self.module)?;
+                    let (addr, proxy) =
+                        start_rate_limited_proxy(listen_address, upstream, limiter)?;
+                    let handle = JsonRpcHttpServerHandle {
+                        server,
+                        _proxy: Some(proxy),
+                    };
+                    (
+                        ServerHandle::HttpHandler(handle, addr),
+                        "Rate limited JSON-RPC",
+                    )
+                }
+                None => {
+                    let server = http_builder.build(listen_address).await?;
+                    let addr = server.local_addr()?;
+                    let handle = JsonRpcHttpServerHandle {
+                        server: server.start(self.module)?,
+                        _proxy: None,
+                    };
+                    (ServerHandle::HttpHandler(handle, addr), "JSON-RPC")
+                }
+            },
             ServerBuilder::WsBuilder(ws_builder) => {
                 let server = ws_builder.build(listen_address).await?;
                 let addr = server.local_addr()?;
@@ -196,6 +262,10 @@ pub struct JsonRpcMetrics {
     req_latency_by_route: HistogramVec,
     /// Failed requests by route
     errors_by_route: IntCounterVec,
+    /// Requests rejected by the rate limiter, by route
+    throttled_requests_by_route: IntCounterVec,
+    /// Number of client IPs tracked by the rate limiter
+    tracked_clients: IntGauge,
 }
 
 const LATENCY_SEC_BUCKETS: &[f64] = &[
@@ -227,6 +297,19 @@ impl JsonRpcMetrics {
                 registry,
             )
             .unwrap(),
+            throttled_requests_by_route: register_int_counter_vec_with_registry!(
+                "rpc_throttled_requests_by_route",
+                "Number of requests rejected by the rate limiter by route",
+                &["route"],
+                registry,
+            )
+            .unwrap(),
+            tracked_clients: register_int_gauge_with_registry!(
+                "rpc_rate_limiter_tracked_clients",
+                "Number of client IPs tracked by the rate limiter",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
@@ -249,6 +332,7 @@ impl HttpMiddleware for ApiMetrics {
             requests_by_route,
             req_latency_by_route,
             errors_by_route,
+            ..
         }) = self
         {
             requests_by_route.with_label_values(&[name]).inc();
@@ -0,0 +1,481 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Per-client admission control for the JSON-RPC HTTP server.
+//!
+//! jsonrpsee middlewares can observe calls but cannot reject them, and this jsonrpsee version
+//! has no tower layers, so rate limiting is done by a thin HTTP front that owns the public listen
+//! address. It charges every call of a request to the token bucket of the client, and either
+//! answers with JSON-RPC errors or forwards the request untouched to the jsonrpsee server
+//! listening on a local address. Admitted requests therefore take an extra loopback hop.
+//!
+//! Only the HTTP server is limited: WebSocket subscriptions are long lived and are not charged.
+
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::Arc;
+use std::time::Instant;
+
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{HeaderValue, CONTENT_TYPE};
+use hyper::server::conn::AddrStream;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Client, Request, Response, StatusCode, Uri};
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+use tokio::task::JoinHandle;
+use tracing::{debug, error};
+
+use sui_config::node::JsonRpcRateLimitConfig;
+
+use crate::JsonRpcMetrics;
+
+/// JSON-RPC error code of calls rejected by the rate limiter.
+pub const RATE_LIMITED_ERROR_CODE: i64 = -32005;
+
+/// Largest request body accepted, same as the jsonrpsee server default.
+const MAX_REQUEST_BODY_SIZE: usize = 10 * 1024 * 1024;
+
+/// The bucket of the least recently seen client is dropped once this many clients are tracked.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(burst_size: f64, now: Instant) -> Self {
+        Self {
+            tokens: burst_size,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, tokens_per_second: f64, burst_size: f64, now: Instant) {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * tokens_per_second).min(burst_size);
+        self.last_refill = now;
+    }
+
+    fn try_acquire(&mut self, cost: f64) -> bool {
+        if self.tokens < cost {
+            return false;
+        }
+        self.tokens -= cost;
+        true
+    }
+}
+
+pub struct RateLimiter {
+    config: JsonRpcRateLimitConfig,
+    buckets: Mutex<LruCache<IpAddr, TokenBucket>>,
+    metrics: Option<JsonRpcMetrics>,
+    /// Methods served by the server, other method names are not used as metric labels.
+    known_methods: HashSet<String>,
+}
+
+impl RateLimiter {
+    pub fn new(
+        config: JsonRpcRateLimitConfig,
+        metrics: Option<JsonRpcMetrics>,
+        known_methods: HashSet<String>,
+    ) -> Result<Self, anyhow::Error> {
+        config.validate()?;
+        Ok(Self {
+            config,
+            buckets: Mutex::new(LruCache::new(MAX_TRACKED_CLIENTS)),
+            metrics,
+            known_methods,
+        })
+    }
+
+    /// Charge the calls to `methods` to the bucket of `client`, returning false if the client
+    /// has too few tokens left. Calls of a batch are admitted or rejected together.
+    /// IPv6 clients share the bucket of their /64 network, which is usually a single host.
+    pub fn try_acquire(&self, client: IpAddr, methods: &[&str], now: Instant) -> bool {
+        let client = bucket_key(client);
+        let cost: u64 = methods
+            .iter()
+            .map(|method| self.config.method_cost(method) as u64)
+            .sum();
+        let tokens_per_second = self.config.tokens_per_second as f64;
+        let burst_size = self.config.burst_size as f64;
+
+        let mut buckets = self.buckets.lock();
+        let admitted = match buckets.get_mut(&client) {
+            Some(bucket) => {
+                bucket.refill(tokens_per_second, burst_size, now);
+                bucket.try_acquire(cost as f64)
+            }
+            None => {
+                let mut bucket = TokenBucket::new(burst_size, now);
+                let admitted = bucket.try_acquire(cost as f64);
+                buckets.put(client, bucket);
+                admitted
+            }
+        };
+
+        if let Some(metrics) = &self.metrics {
+            metrics.tracked_clients.set(buckets.len() as i64);
+            if !admitted {
+                for method in methods {
+                    let route = if self.known_methods.contains(*method) {
+                        method
+                    } else {
+                        "unknown"
+                    };
+                    metrics
+                        .throttled_requests_by_route
+                        .with_label_values(&[route])
+                        .inc();
+                }
+            }
+        }
+        admitted
+    }
+}
+
+fn bucket_key(client: IpAddr) -> IpAddr {
+    match client {
+        IpAddr::V4(_) => client,
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => IpAddr::V4(ip),
+            None => {
+                let mut segments = ip.segments();
+                segments[4..].fill(0);
+                IpAddr::V6(Ipv6Addr::from(segments))
+            }
+        },
+    }
+}
+
+/// Task serving the rate limiting proxy, aborted when dropped.
+pub(crate) struct RateLimitedProxyHandle(JoinHandle<()>);
+
+impl Drop for RateLimitedProxyHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Serve `listen_address`, forwarding the requests admitted by `limiter` to `upstream`.
+pub(crate) fn start_rate_limited_proxy(
+    listen_address: SocketAddr,
+    upstream: SocketAddr,
+    limiter: Arc<RateLimiter>,
+) -> Result<(SocketAddr, RateLimitedProxyHandle), anyhow::Error> {
+    let client = Client::new();
+    let make_service = make_service_fn(move |conn: &AddrStream| {
+        let remote_ip = conn.remote_addr().ip();
+        let limiter = limiter.clone();
+        let client = client.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                proxy_request(
+                    request,
+                    remote_ip,
+                    upstream,
+                    limiter.clone(),
+                    client.clone(),
+                )
+            }))
+        }
+    });
+    let server = hyper::Server::try_bind(&listen_address)?.serve(make_service);
+    let local_addr = server.local_addr();
+    let handle = tokio::spawn(async move {
+        if let Err(e) = server.await {
+            error!("JSON-RPC rate limiting proxy failed: {e}");
+        }
+    });
+    Ok((local_addr, RateLimitedProxyHandle(handle)))
+}
+
+async fn proxy_request(
+    request: Request<Body>,
+    remote_ip: IpAddr,
+    upstream: SocketAddr,
+    limiter: Arc<RateLimiter>,
+    client: Client<hyper::client::HttpConnector>,
+) -> Result<Response<Body>, hyper::Error> {
+    let (mut parts, body) = request.into_parts();
+    let body = match read_body(body).await? {
+        Some(body) => body,
+        None => {
+            return Ok(Response::builder()
+                .status(StatusCode::PAYLOAD_TOO_LARGE)
+                .body(Body::empty())
+                .expect("Response with a valid status should build"))
+        }
+    };
+
+    let calls = parse_calls(&body);
+    let methods: Vec<&str> = calls.iter().map(|(method, _)| method.as_str()).collect();
+    // Requests that are not valid JSON-RPC are charged as one call, jsonrpsee answers them.
+    let charged = if methods.is_empty() {
+        vec![""]
+    } else {
+        methods
+    };
+    if !limiter.try_acquire(remote_ip, &charged, Instant::now()) {
+        debug!(?remote_ip, "Rejecting rate limited JSON-RPC request");
+        return Ok(rate_limited_response(&body, &calls));
+    }
+
+    let path_and_query = parts
+        .uri
+        .path_and_query()
+        .map(|p| p.as_str())
+        .unwrap_or("/");
+    parts.uri = Uri::builder()
+        .scheme("http")
+        .authority(upstream.to_string())
+        .path_and_query(path_and_query)
+        .build()
+        .expect("Upstream socket address and request path should form a valid URI");
+    client
+        .request(Request::from_parts(parts, Body::from(body)))
+        .await
+}
+
+/// Read the whole request body, or None if it exceeds `MAX_REQUEST_BODY_SIZE`.
+async fn read_body(mut body: Body) -> Result<Option<Bytes>, hyper::Error> {
+    let mut bytes = Vec::new();
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > MAX_REQUEST_BODY_SIZE {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Some(bytes.into()))
+}
+
+/// Extract the method name and id of every call of a single or batch request.
+fn parse_calls(body: &[u8]) -> Vec<(String, Value)> {
+    let call = |value: &Value| {
+        value.get("method").and_then(Value::as_str).map(|method| {
+            (
+                method.to_string(),
+                value.get("id").cloned().unwrap_or(Value::Null),
+            )
+        })
+    };
+    match serde_json::from_slice::<Value>(body) {
+        Ok(Value::Array(calls)) => calls.iter().filter_map(call).collect(),
+        Ok(value) => call(&value).into_iter().collect(),
+        Err(_) => vec![],
+    }
+}
+
+fn rate_limited_response(body: &[u8], calls: &[(String, Value)]) -> Response<Body> {
+    let error = |id: &Value| {
+        json!({
+            "jsonrpc": "2.0",
+            "error": {
+                "code": RATE_LIMITED_ERROR_CODE,
+                "message": "Request rate limit exceeded, try again later",
+            },
+            "id": id,
+        })
+    };
+    let is_batch = matches!(serde_json::from_slice::<Value>(body), Ok(Value::Array(_)));
+    let response = if is_batch {
+        Value::Array(calls.iter().map(|(_, id)| error(id)).collect())
+    } else {
+        error(calls.first().map(|(_, id)| id).unwrap_or(&Value::Null))
+    };
+    let mut response = Response::new(Body::from(response.to_string()));
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+
+    fn config() -> JsonRpcRateLimitConfig {
+        JsonRpcRateLimitConfig {
+            tokens_per_second: 10,
+            burst_size: 20,
+            default_method_cost: 1,
+            method_costs: BTreeMap::from([("sui_getTransactionsInRange".to_string(), 15)]),
+        }
+    }
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(config(), None, HashSet::new()).unwrap()
+    }
+
+    async fn post(addr: SocketAddr, body: &'static str) -> Result<Value, hyper::Error> {
+        let request = Request::post(format!("http://{addr}"))
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        let response = Client::new().request(request).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await?;
+        Ok(serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_token_bucket_per_client() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let client = IpAddr::from([127, 0, 0, 1]);
+        let other_client = IpAddr::from([127, 0, 0, 2]);
+
+        assert!(limiter.try_acquire(client, &["sui_getTransactionsInRange"], now));
+        assert!(!limiter.try_acquire(client, &["sui_getTransactionsInRange"], now));
+        // Cheaper calls still fit in the remaining tokens
+        assert!(limiter.try_acquire(client, &["sui_getObject"; 5], now));
+        assert!(!limiter.try_acquire(client, &["sui_getObject"], now));
+
+        // Other clients have their own bucket
+        assert!(limiter.try_acquire(other_client, &["sui_getObject"; 20], now));
+
+        // Tokens are refilled over time, up to the burst size
+        let later = now + Duration::from_millis(1500);
+        assert!(limiter.try_acquire(client, &["sui_getTransactionsInRange"], later));
+        let much_later = now + Duration::from_secs(3600);
+        assert!(!limiter.try_acquire(client, &["sui_getObject"; 21], much_later));
+        assert!(limiter.try_acquire(client, &["sui_getObject"; 20], much_later));
+    }
+
+    #[test]
+    fn test_burst_size_covers_method_costs() {
+        let mut config = config();
+        config
+            .method_costs
+            .insert("sui_getTransactions".to_string(), 21);
+        assert!(RateLimiter::new(config.clone(), None, HashSet::new()).is_err());
+
+        config.method_costs.clear();
+        config.default_method_cost = 21;
+        assert!(RateLimiter::new(config, None, HashSet::new()).is_err());
+    }
+
+    #[test]
+    fn test_ipv6_clients_share_bucket_by_prefix() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let client: IpAddr = "2001:db8:1:2:3:4:5:6".parse().unwrap();
+        let same_network: IpAddr = "2001:db8:1:2:ffff::1".parse().unwrap();
+        let other_network: IpAddr = "2001:db8:1:3::1".parse().unwrap();
+
+        assert!(limiter.try_acquire(client, &["sui_getObject"; 20], now));
+        assert!(!limiter.try_acquire(same_network, &["sui_getObject"], now));
+        assert!(limiter.try_acquire(other_network, &["sui_getObject"], now));
+
+        // IPv4 clients connecting over IPv6 keep their own bucket
+        let mapped: IpAddr = "::ffff:10.0.0.1".parse().unwrap();
+        let mapped_neighbour: IpAddr = "::ffff:10.0.0.2".parse().unwrap();
+        assert!(limiter.try_acquire(mapped, &["sui_getObject"; 20], now));
+        assert!(limiter.try_acquire(mapped_neighbour, &["sui_getObject"], now));
+        assert!(!limiter.try_acquire(IpAddr::from([10, 0, 0, 1]), &["sui_getObject"], now));
+    }
+
+    #[test]
+    fn test_least_recently_seen_client_is_evicted() {
+        let limiter = limiter();
+        let now = Instant::now();
+        let client = IpAddr::from([127, 0, 0, 1]);
+        assert!(limiter.try_acquire(client, &["sui_getObject"; 20], now));
+
+        let others: Vec<IpAddr> = (1..MAX_TRACKED_CLIENTS as u32)
+            .map(|i| IpAddr::from((10 << 24 | i).to_be_bytes()))
+            .collect();
+        for other in &others {
+            assert!(limiter.try_acquire(*other, &["sui_getObject"], now));
+        }
+        // The throttled client is seen again, so the next new client evicts another one.
+        assert!(!limiter.try_acquire(client, &["sui_getObject"], now));
+        assert!(limiter.try_acquire(IpAddr::from([127, 0, 0, 2]), &["sui_getObject"], now));
+
+        assert_eq!(limiter.buckets.lock().len(), MAX_TRACKED_CLIENTS);
+        assert!(!limiter.try_acquire(client, &["sui_getObject"], now));
+        assert!(!limiter.buckets.lock().contains(&others[0]));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_server() {
+        let mut builder =
+            crate::JsonRpcServerBuilder::new_without_metrics_for_testing(false).unwrap();
+        builder
+            .module
+            .register_method("test_ping", |_, _| Ok("pong"))
+            .unwrap();
+        builder.set_rate_limit(JsonRpcRateLimitConfig {
+            tokens_per_second: 1,
+            burst_size: 2,
+            default_method_cost: 1,
+            method_costs: BTreeMap::new(),
+        });
+        let handle = builder
+            .start(SocketAddr::new(IpAddr::from([127, 0, 0, 1]), 0))
+            .await
+            .unwrap();
+        let addr = *handle.local_addr();
+
+        let ping = r#"{"jsonrpc":"2.0","method":"test_ping","params":[],"id":1}"#;
+        let response = post(addr, ping).await.unwrap();
+        assert_eq!(response["result"], json!("pong"));
+
+        // A batch is rejected as a whole when the bucket cannot pay for all of its calls.
+        let batch = r#"[{"jsonrpc":"2.0","method":"test_ping","params":[],"id":2},
+            {"jsonrpc":"2.0","method":"test_ping","params":[],"id":3}]"#;
+        let response = post(addr, batch).await.unwrap();
+        assert_eq!(
+            response,
+            json!([2, 3]
+                .iter()
+                .map(|id| json!({
+                    "jsonrpc": "2.0",
+                    "error": {
+                        "code": RATE_LIMITED_ERROR_CODE,
+                        "message": "Request rate limit exceeded, try again later",
+                    },
+                    "id": id,
+                }))
+                .collect::<Vec<_>>())
+        );
+
+        let response = post(addr, ping).await.unwrap();
+        assert_eq!(response["result"], json!("pong"));
+        let response = post(addr, ping).await.unwrap();
+        assert_eq!(response["error"]["code"], json!(RATE_LIMITED_ERROR_CODE));
+        assert_eq!(response["id"], json!(1));
+
+        // Stopping the server also stops the proxy in front of it.
+        handle.into_http_server_handle().unwrap().stop().unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(post(addr, ping).await.is_err());
+    }
+
+    #[test]
+    fn test_parse_calls() {
+        let single = br#"{"jsonrpc":"2.0","method":"sui_getObject","params":[],"id":1}"#;
+        assert_eq!(
+            parse_calls(single),
+            vec![("sui_getObject".to_string(), json!(1))]
+        );
+
+        let batch =
+            br#"[{"jsonrpc":"2.0","method":"a","id":"x"},{"jsonrpc":"2.0","method":"b","id":2}]"#;
+        assert_eq!(
+            parse_calls(batch),
+            vec![("a".to_string(), json!("x")), ("b".to_string(), json!(2))]
+        );
+
+        assert!(parse_calls(b"not json").is_empty());
+    }
+}
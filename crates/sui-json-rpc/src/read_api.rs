@@ -1,6 +1,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use std::cmp;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
@@ -14,21 +15,27 @@ use signature::Signature;
 
 use sui_core::authority::AuthorityState;
 use sui_json_rpc_types::{
-    GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType, ObjectValueKind, Page,
-    SuiMoveNormalizedFunction, SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo,
-    SuiTransactionEffects, SuiTransactionResponse, TransactionsPage,
+    Balance, CoinPage, GetObjectDataResponse, GetPastObjectDataResponse, MoveFunctionArgType,
+    MultiGetResult, ObjectValueKind, ObjectsPage, Page, SuiCheckpointContents,
+    SuiCheckpointSummary, SuiCoin, SuiDryRunTransactionResponse, SuiMoveNormalizedFunction,
+    SuiMoveNormalizedModule, SuiMoveNormalizedStruct, SuiObjectInfo, SuiTransactionEffects,
+    SuiTransactionResponse, TransactionsPage,
 };
 use sui_open_rpc::Module;
 use sui_types::base_types::SequenceNumber;
-use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectID, ObjectInfo, SuiAddress, TransactionDigest};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::committee::EpochId;
 use sui_types::crypto::{SignableBytes, SignatureScheme};
+use sui_types::gas_coin::GAS;
 use sui_types::messages::{
-    CommitteeInfoRequest, CommitteeInfoResponse, Transaction, TransactionData,
+    CertifiedTransaction, CommitteeInfoRequest, CommitteeInfoResponse, Transaction,
+    TransactionData, TransactionEffects,
 };
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 use sui_types::move_package::normalize_modules;
 use sui_types::object::{Data, ObjectRead, Owner};
+use sui_types::parse_sui_struct_tag;
 use sui_types::query::{Ordering, TransactionQuery};
 use sui_types::sui_serde::Base64;
 
@@ -40,6 +47,7 @@ use crate::SuiRpcModule;
 // Fullnodes.
 pub struct ReadApi {
     pub state: Arc<AuthorityState>,
+    pub multi_get_max_batch_size: usize,
 }
 
 pub struct FullNodeApi {
@@ -53,8 +61,35 @@ impl FullNodeApi {
 }
 
 impl ReadApi {
-    pub fn new(state: Arc<AuthorityState>) -> Self {
-        Self { state }
+    pub fn new(state: Arc<AuthorityState>, multi_get_max_batch_size: usize) -> Self {
+        Self {
+            state,
+            multi_get_max_batch_size,
+        }
+    }
+
+    fn check_batch_size(&self, batch_size: usize) -> Result<(), anyhow::Error> {
+        if batch_size > self.multi_get_max_batch_size {
+            return Err(anyhow!(
+                "Batch size {batch_size} exceeds the maximum of {}",
+                self.multi_get_max_batch_size
+            ));
+        }
+        Ok(())
+    }
+
+    async fn to_transaction_response(
+        &self,
+        digest: &TransactionDigest,
+        cert: CertifiedTransaction,
+        effects: TransactionEffects,
+    ) -> Result<SuiTransactionResponse, anyhow::Error> {
+        Ok(SuiTransactionResponse {
+            certificate: cert.try_into()?,
+            effects: SuiTransactionEffects::try_from(effects, self.state.module_cache.as_ref())?,
+            timestamp_ms: self.state.get_timestamp_ms(digest).await?,
+            parsed_data: None,
+        })
     }
 }
 
@@ -117,12 +152,73 @@ impl RpcReadApiServer for ReadApi {
         digest: TransactionDigest,
     ) -> RpcResult<SuiTransactionResponse> {
         let (cert, effects) = self.state.get_transaction(digest).await?;
-        Ok(SuiTransactionResponse {
-            certificate: cert.try_into()?,
-            effects: SuiTransactionEffects::try_from(effects, self.state.module_cache.as_ref())?,
-            timestamp_ms: self.state.get_timestamp_ms(&digest).await?,
-            parsed_data: None,
-        })
+        Ok(self.to_transaction_response(&digest, cert, effects).await?)
+    }
+
+    async fn get_balance(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+    ) -> RpcResult<Balance> {
+        let coin_type = match coin_type {
+            Some(coin_type) => parse_sui_struct_tag(&coin_type)?,
+            None => GAS::type_(),
+        };
+        let balance = self
+            .state
+            .get_balances(owner, Some(&coin_type))
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .pop();
+        Ok(balance.unwrap_or_else(|| Balance::zero(coin_type.to_string())))
+    }
+
+    async fn get_all_balances(&self, owner: SuiAddress) -> RpcResult<Vec<Balance>> {
+        Ok(self
+            .state
+            .get_balances(owner, None)
+            .await
+            .map_err(|e| anyhow!("{e}"))?)
+    }
+
+    async fn multi_get_objects(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> RpcResult<Vec<MultiGetResult<GetObjectDataResponse>>> {
+        self.check_batch_size(object_ids.len())?;
+        Ok(self
+            .state
+            .multi_get_object_read(&object_ids)
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .into_iter()
+            .map(|read| {
+                read.map_err(anyhow::Error::from)
+                    .and_then(GetObjectDataResponse::try_from)
+                    .into()
+            })
+            .collect())
+    }
+
+    async fn multi_get_transactions(
+        &self,
+        digests: Vec<TransactionDigest>,
+    ) -> RpcResult<Vec<MultiGetResult<SuiTransactionResponse>>> {
+        self.check_batch_size(digests.len())?;
+        let transactions = self
+            .state
+            .multi_get_transactions(&digests)
+            .await
+            .map_err(|e| anyhow!("{e}"))?;
+        let mut results = Vec::with_capacity(digests.len());
+        for (digest, tx) in digests.iter().zip(transactions) {
+            let response = match tx {
+                Ok((cert, effects)) => self.to_transaction_response(digest, cert, effects).await,
+                Err(e) => Err(e.into()),
+            };
+            results.push(response.into());
+        }
+        Ok(results)
     }
 }
 
@@ -144,7 +240,7 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         sig_scheme: SignatureScheme,
         signature: Base64,
         pub_key: Base64,
-    ) -> RpcResult<SuiTransactionEffects> {
+    ) -> RpcResult<SuiDryRunTransactionResponse> {
         let data = TransactionData::from_signable_bytes(&tx_bytes.to_vec()?)?;
         let flag = vec![sig_scheme.flag()];
         let signature =
@@ -288,6 +384,105 @@ impl RpcFullNodeReadApiServer for FullNodeApi {
         Ok(Page { data, next_cursor })
     }
 
+    async fn get_coins(
+        &self,
+        owner: SuiAddress,
+        coin_type: Option<String>,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<CoinPage> {
+        let coin_type = match coin_type {
+            Some(coin_type) => parse_sui_struct_tag(&coin_type)?,
+            None => GAS::type_(),
+        };
+        let limit = cmp::min(limit.unwrap_or(MAX_RESULT_SIZE), MAX_RESULT_SIZE);
+        if limit == 0 {
+            Err(anyhow!("Page result limit must be larger then 0."))?;
+        }
+
+        // Retrieve 1 extra item for next cursor
+        let mut data = self
+            .state
+            .get_owned_coins(owner, Some(&coin_type), cursor, Some(limit + 1))
+            .await
+            .map_err(|e| anyhow!("{e}"))?
+            .iter()
+            .map(SuiCoin::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // extract next cursor
+        let next_cursor = data.get(limit).map(|coin| coin.coin_object_id);
+        data.truncate(limit);
+        Ok(Page { data, next_cursor })
+    }
+
+    async fn get_objects_owned_by_address_and_type(
+        &self,
+        owner: SuiAddress,
+        struct_type: String,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let struct_type = parse_sui_struct_tag(&struct_type)?;
+        let limit = objects_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let data = self
+            .state
+            .get_objects_by_owner_and_type(
+                Owner::AddressOwner(owner),
+                &struct_type,
+                cursor,
+                limit + 1,
+            )
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(objects_page(data, limit))
+    }
+
+    async fn get_objects_by_type(
+        &self,
+        struct_type: String,
+        cursor: Option<ObjectID>,
+        limit: Option<usize>,
+    ) -> RpcResult<ObjectsPage> {
+        let struct_type = parse_sui_struct_tag(&struct_type)?;
+        let limit = objects_page_limit(limit)?;
+        // Retrieve 1 extra item for next cursor
+        let data = self
+            .state
+            .get_objects_by_type(&struct_type, cursor, limit + 1)
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(objects_page(data, limit))
+    }
+
+    async fn get_latest_checkpoint_sequence_number(&self) -> RpcResult<CheckpointSequenceNumber> {
+        Ok(self
+            .state
+            .get_latest_checkpoint_sequence_number()
+            .map_err(|e| anyhow!("{e}"))?)
+    }
+
+    async fn get_checkpoint_summary(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiCheckpointSummary> {
+        Ok(self
+            .state
+            .get_checkpoint_summary(seq)
+            .map_err(|e| anyhow!("{e}"))?
+            .into())
+    }
+
+    async fn get_checkpoint_contents(
+        &self,
+        seq: CheckpointSequenceNumber,
+    ) -> RpcResult<SuiCheckpointContents> {
+        let contents = self
+            .state
+            .get_checkpoint_contents(seq)
+            .map_err(|e| anyhow!("{e}"))?;
+        Ok(SuiCheckpointContents::new(seq, &contents))
+    }
+
     async fn try_get_past_object(
         &self,
         object_id: ObjectID,
@@ -351,3 +546,20 @@ pub async fn get_move_modules_by_package(
         _ => Err(anyhow!("Package object does not exist with ID {}", package)),
     }?)
 }
+
+fn objects_page_limit(limit: Option<usize>) -> RpcResult<usize> {
+    let limit = cmp::min(limit.unwrap_or(MAX_RESULT_SIZE), MAX_RESULT_SIZE);
+    if limit == 0 {
+        Err(anyhow!("Page result limit must be larger then 0."))?;
+    }
+    Ok(limit)
+}
+
+/// Builds a page of at most `limit` objects out of `limit + 1` retrieved objects, the extra one
+/// being the next cursor.
+fn objects_page(objects: Vec<ObjectInfo>, limit: usize) -> ObjectsPage {
+    let mut data: Vec<SuiObjectInfo> = objects.into_iter().map(SuiObjectInfo::from).collect();
+    let next_cursor = data.get(limit).map(|info| info.object_id);
+    data.truncate(limit);
+    Page { data, next_cursor }
+}
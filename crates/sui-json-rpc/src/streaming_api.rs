@@ -17,7 +17,10 @@ use sui_json_rpc_types::SuiCertifiedTransaction;
 use sui_json_rpc_types::SuiTransactionEffects;
 use sui_json_rpc_types::SuiTransactionFilter;
 use sui_json_rpc_types::SuiTransactionResponse;
+use sui_json_rpc_types::SuiTransactionStreamResponse;
 use sui_open_rpc::Module;
+use sui_types::batch::TxSequenceNumber;
+use sui_types::error::SuiError;
 use sui_types::filter::TransactionFilter;
 use tracing::warn;
 
@@ -41,14 +44,26 @@ impl TransactionStreamingApiServer for TransactionStreamingApiImpl {
         &self,
         sink: SubscriptionSink,
         filter: SuiTransactionFilter,
+        start_seq: Option<TxSequenceNumber>,
     ) -> SubscriptionResult {
         let filter: TransactionFilter = filter.into();
 
+        let stream = match start_seq {
+            Some(start_seq) => self
+                .transaction_streamer
+                .subscribe_from(self.state.clone(), filter, start_seq)
+                .left_stream(),
+            None => self
+                .transaction_streamer
+                .subscribe(filter)
+                .map(Ok::<_, SuiError>)
+                .right_stream(),
+        };
         let state = self.state.clone();
-        let stream = self.transaction_streamer.subscribe(filter);
-        let stream = stream.then(move |(tx_cert, signed_effects)| {
+        let stream = stream.then(move |tx| {
             let state_clone = state.clone();
             async move {
+                let (seq, (tx_cert, signed_effects)) = tx?;
                 let sui_tx_cert = SuiCertifiedTransaction::try_from(tx_cert)?;
                 let sui_tx_effects = SuiTransactionEffects::try_from(
                     signed_effects.effects,
@@ -56,11 +71,14 @@ impl TransactionStreamingApiServer for TransactionStreamingApiImpl {
                 )?;
                 let digest = sui_tx_cert.transaction_digest;
                 let ts = state_clone.get_timestamp_ms(&digest).await.unwrap_or(None);
-                Ok::<SuiTransactionResponse, anyhow::Error>(SuiTransactionResponse {
-                    certificate: sui_tx_cert,
-                    effects: sui_tx_effects,
-                    timestamp_ms: ts,
-                    parsed_data: None,
+                Ok::<SuiTransactionStreamResponse, anyhow::Error>(SuiTransactionStreamResponse {
+                    seq,
+                    response: SuiTransactionResponse {
+                        certificate: sui_tx_cert,
+                        effects: sui_tx_effects,
+                        timestamp_ms: ts,
+                        parsed_data: None,
+                    },
                 })
             }
         });
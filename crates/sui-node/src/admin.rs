@@ -8,18 +8,41 @@ use axum::{
     Router,
 };
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::PathBuf;
+use std::sync::Arc;
+use sui_core::db_backup::DbBackups;
+use sui_core::object_pruner::ObjectPrunerSwitch;
 use telemetry_subscribers::FilterHandle;
+use tokio::sync::OnceCell;
 use tracing::info;
 
 const LOGGING_ROUTE: &str = "/logging";
+const OBJECT_PRUNING_ROUTE: &str = "/object-pruning";
+const DB_BACKUP_ROUTE: &str = "/db-backup";
 
-pub fn start_admin_server(port: u16, filter_handle: FilterHandle) {
+/// Set once the node has started, if it runs an object pruner.
+pub type ObjectPrunerSwitchCell = Arc<OnceCell<ObjectPrunerSwitch>>;
+
+/// Set once the node has started.
+pub type DbBackupsCell = Arc<OnceCell<DbBackups>>;
+
+pub fn start_admin_server(
+    port: u16,
+    filter_handle: FilterHandle,
+    object_pruner_switch: ObjectPrunerSwitchCell,
+    db_backups: DbBackupsCell,
+) {
     let filter = filter_handle.get().unwrap();
 
     let app = Router::new()
         .route(LOGGING_ROUTE, get(get_filter))
         .route(LOGGING_ROUTE, post(set_filter))
-        .layer(Extension(filter_handle));
+        .route(OBJECT_PRUNING_ROUTE, get(get_object_pruning))
+        .route(OBJECT_PRUNING_ROUTE, post(set_object_pruning))
+        .route(DB_BACKUP_ROUTE, post(create_db_backup))
+        .layer(Extension(filter_handle))
+        .layer(Extension(object_pruner_switch))
+        .layer(Extension(db_backups));
 
     let socket_address = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port);
     info!(
@@ -55,3 +78,72 @@ async fn set_filter(
         Err(err) => (StatusCode::BAD_REQUEST, err.to_string()),
     }
 }
+
+async fn get_object_pruning(
+    Extension(object_pruner_switch): Extension<ObjectPrunerSwitchCell>,
+) -> (StatusCode, String) {
+    match object_pruner_switch.get() {
+        Some(switch) if switch.is_enabled() => (StatusCode::OK, "enabled".into()),
+        Some(_) => (StatusCode::OK, "disabled".into()),
+        None => (
+            StatusCode::NOT_FOUND,
+            "object pruning is not configured".into(),
+        ),
+    }
+}
+
+async fn set_object_pruning(
+    Extension(object_pruner_switch): Extension<ObjectPrunerSwitchCell>,
+    enabled: String,
+) -> (StatusCode, String) {
+    let switch = match object_pruner_switch.get() {
+        Some(switch) => switch,
+        None => {
+            return (
+                StatusCode::NOT_FOUND,
+                "object pruning is not configured".into(),
+            )
+        }
+    };
+    match enabled.trim() {
+        "true" | "enable" => switch.set_enabled(true),
+        "false" | "disable" => switch.set_enabled(false),
+        other => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("expected true or false, got {other:?}"),
+            )
+        }
+    }
+    (StatusCode::OK, "".into())
+}
+
+/// Creates a backup in the directory given in the body, or by default in a new directory of the
+/// backups directory of the node, and responds with its location and size.
+async fn create_db_backup(
+    Extension(db_backups): Extension<DbBackupsCell>,
+    path: String,
+) -> (StatusCode, String) {
+    let db_backups = match db_backups.get() {
+        Some(db_backups) => db_backups,
+        None => {
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                "the node has not started yet".into(),
+            )
+        }
+    };
+    let path = Some(path.trim())
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from);
+    match db_backups.create(path).await {
+        Ok(backup) => {
+            info!(path =? backup.path, size_bytes = backup.size_bytes, "DB backup created");
+            (
+                StatusCode::OK,
+                format!("{}\n{} bytes", backup.path.display(), backup.size_bytes),
+            )
+        }
+        Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()),
+    }
+}
@@ -12,18 +12,29 @@ use narwhal_network::metrics::MetricsMakeCallbackHandler;
 use narwhal_network::metrics::{NetworkConnectionMetrics, NetworkMetrics};
 use parking_lot::Mutex;
 use prometheus::Registry;
+use std::fs::{self, File};
+use std::io::BufReader;
 use std::option::Option::None;
+use std::path::Path;
 use std::time::Instant;
 use std::{sync::Arc, time::Duration};
+use sui_config::genesis::Genesis;
+use sui_config::node::EventStoreBackend;
 use sui_config::NodeConfig;
 use sui_core::authority_active::checkpoint_driver::CheckpointMetrics;
 use sui_core::authority_aggregator::{AuthAggMetrics, AuthorityAggregator};
 use sui_core::authority_server::ValidatorService;
+use sui_core::db_backup::DbBackups;
+use sui_core::db_stats_reporter::DbStatsReporter;
+use sui_core::event_store_pruner::EventStorePruner;
+use sui_core::event_store_rebuilder::rebuild_event_store;
+use sui_core::object_pruner::{ObjectPruner, ObjectPrunerSwitch};
 use sui_core::safe_client::SafeClientMetrics;
+use sui_core::state_snapshot::import_state_snapshot;
 use sui_core::transaction_orchestrator::TransactiondOrchestrator;
 use sui_core::transaction_streamer::TransactionStreamer;
 use sui_core::{
-    authority::{AuthorityState, AuthorityStore},
+    authority::{AuthorityState, AuthorityStore, StableSyncAuthoritySigner},
     authority_active::{gossip::GossipMetrics, ActiveAuthority},
     authority_client::{
         make_network_authority_client_sets_from_genesis,
@@ -37,7 +48,7 @@ use sui_json_rpc::transaction_builder_api::FullNodeTransactionBuilderApi;
 use sui_network::api::ValidatorServer;
 use sui_network::default_mysten_network_config;
 use sui_storage::{
-    event_store::{EventStoreType, SqlEventStore},
+    event_store::{EventStoreType, RocksEventStore, SqlEventStore},
     node_sync_store::NodeSyncStore,
     IndexStore,
 };
@@ -51,11 +62,11 @@ use sui_core::authority_client::NetworkAuthorityClientMetrics;
 use sui_core::epoch::committee_store::CommitteeStore;
 use sui_json_rpc::event_api::EventReadApiImpl;
 use sui_json_rpc::event_api::EventStreamingApiImpl;
-use sui_json_rpc::http_server::HttpServerHandle;
 use sui_json_rpc::read_api::FullNodeApi;
 use sui_json_rpc::read_api::ReadApi;
 use sui_json_rpc::transaction_execution_api::FullNodeTransactionExecutionApi;
 use sui_json_rpc::ws_server::WsServerHandle;
+use sui_json_rpc::JsonRpcHttpServerHandle;
 use sui_json_rpc::JsonRpcServerBuilder;
 use sui_types::crypto::KeypairTraits;
 
@@ -67,13 +78,18 @@ pub use handle::SuiNodeHandle;
 
 pub struct SuiNode {
     grpc_server: tokio::task::JoinHandle<Result<()>>,
-    _json_rpc_service: Option<HttpServerHandle>,
+    _json_rpc_service: Option<JsonRpcHttpServerHandle>,
     _ws_subscription_service: Option<WsServerHandle>,
     _batch_subsystem_handle: tokio::task::JoinHandle<Result<()>>,
     _post_processing_subsystem_handle: Option<tokio::task::JoinHandle<Result<()>>>,
     _gossip_handle: Option<tokio::task::JoinHandle<()>>,
     _execute_driver_handle: tokio::task::JoinHandle<()>,
     _checkpoint_process_handle: Option<tokio::task::JoinHandle<()>>,
+    _event_store_pruner_handle: Option<tokio::task::JoinHandle<()>>,
+    _object_pruner_handle: Option<tokio::task::JoinHandle<()>>,
+    _db_stats_reporter_handle: tokio::task::JoinHandle<()>,
+    object_pruner_switch: Option<ObjectPrunerSwitch>,
+    db_backups: DbBackups,
     state: Arc<AuthorityState>,
     active: Arc<ActiveAuthority<NetworkAuthorityClient>>,
     transaction_orchestrator: Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
@@ -99,6 +115,9 @@ impl SuiNode {
 
         let secret = Arc::pin(config.protocol_key_pair().copy());
         let committee = genesis.committee()?;
+        if let Some(snapshot_path) = &config.bootstrap_snapshot_path {
+            bootstrap_from_state_snapshot(config, genesis, secret.clone(), snapshot_path).await?;
+        }
         let store = Arc::new(AuthorityStore::open(&config.db_path().join("store"), None));
         let committee_store = Arc::new(CommitteeStore::new(
             config.db_path().join("epochs"),
@@ -126,14 +145,30 @@ impl SuiNode {
         };
 
         let event_store = if config.enable_event_processing {
-            let path = config.db_path().join("events.db");
-            let db = SqlEventStore::new_from_file(&path).await?;
-            db.initialize().await?;
-            Some(Arc::new(EventStoreType::SqlEventStore(db)))
+            let path = config.event_store_backend.default_path(config.db_path());
+            let event_store = match config.event_store_backend {
+                EventStoreBackend::Sqlite => {
+                    let db = SqlEventStore::new_from_file(&path).await?;
+                    db.initialize().await?;
+                    EventStoreType::SqlEventStore(db)
+                }
+                EventStoreBackend::Rocksdb => {
+                    EventStoreType::RocksEventStore(RocksEventStore::open(&path)?)
+                }
+            };
+            Some(Arc::new(event_store))
         } else {
             None
         };
 
+        let event_store_pruner_handle = match (&event_store, &config.event_store_retention) {
+            (Some(event_store), Some(retention)) => Some(
+                EventStorePruner::new(event_store.clone(), retention, &prometheus_registry)?
+                    .spawn(),
+            ),
+            _ => None,
+        };
+
         let (tx_reconfigure_consensus, rx_reconfigure_consensus) = channel(100);
 
         let transaction_streamer = config
@@ -163,6 +198,32 @@ impl SuiNode {
             )
             .await,
         );
+
+        if config.rebuild_event_store_on_startup {
+            if let Some(event_handler) = &state.event_handler {
+                info!("Rebuilding the event store from the authority store");
+                let progress = rebuild_event_store(
+                    &state.db(),
+                    index_store.as_deref(),
+                    event_handler,
+                    |progress| {
+                        info!(
+                            next_seq = progress.next_seq,
+                            end_seq = progress.end_seq,
+                            events = progress.events,
+                            "Rebuilding the event store"
+                        )
+                    },
+                )
+                .await?;
+                info!(
+                    transactions = progress.transactions,
+                    events = progress.events,
+                    "Rebuilt the event store"
+                );
+            }
+        }
+
         let net_config = default_mysten_network_config();
 
         let sui_system_state = state.get_sui_system_state_object().await?;
@@ -270,6 +331,23 @@ impl SuiNode {
             None
         };
 
+        let (object_pruner_handle, object_pruner_switch) = match &config.object_pruning {
+            Some(object_pruning) => {
+                let pruner = ObjectPruner::new(
+                    state.db(),
+                    state.checkpoints(),
+                    object_pruning,
+                    &prometheus_registry,
+                )?;
+                let switch = pruner.switch();
+                (Some(pruner.spawn()), Some(switch))
+            }
+            None => (None, None),
+        };
+
+        let db_stats_reporter_handle =
+            DbStatsReporter::new(state.clone(), index_store.clone(), &prometheus_registry).spawn();
+
         let registry = prometheus_registry.clone();
         let validator_service = if config.consensus_config().is_some() {
             Some(
@@ -357,6 +435,11 @@ impl SuiNode {
             _gossip_handle: gossip_handle,
             _execute_driver_handle: execute_driver_handle,
             _checkpoint_process_handle: checkpoint_process_handle,
+            _event_store_pruner_handle: event_store_pruner_handle,
+            _object_pruner_handle: object_pruner_handle,
+            _db_stats_reporter_handle: db_stats_reporter_handle,
+            object_pruner_switch,
+            db_backups: DbBackups::new(state.clone(), config.db_path().join("backups")),
             _batch_subsystem_handle: batch_subsystem_handle,
             _post_processing_subsystem_handle: post_processing_subsystem_handle,
             state,
@@ -378,6 +461,17 @@ impl SuiNode {
         self.state.clone()
     }
 
+    /// Switches the object pruner on and off, if `object-pruning` is configured.
+    pub fn object_pruner_switch(&self) -> Option<ObjectPrunerSwitch> {
+        self.object_pruner_switch.clone()
+    }
+
+    /// Creates online backups of the databases of the node, in `backups` in the db path by
+    /// default.
+    pub fn db_backups(&self) -> DbBackups {
+        self.db_backups.clone()
+    }
+
     pub fn active(&self) -> &Arc<ActiveAuthority<NetworkAuthorityClient>> {
         &self.active
     }
@@ -406,12 +500,75 @@ impl SuiNode {
     }
 }
 
+/// Stores of the database that are filled from a state snapshot.
+const SNAPSHOT_STORES: [&str; 3] = ["store", "epochs", "checkpoints"];
+
+/// Fills the stores of a node with an empty database from a state snapshot. The snapshot is
+/// imported into a staging directory whose stores are moved into the database once the import is
+/// complete, so that an interrupted import is started over instead of leaving a partial state.
+async fn bootstrap_from_state_snapshot(
+    config: &NodeConfig,
+    genesis: &Genesis,
+    secret: StableSyncAuthoritySigner,
+    snapshot_path: &Path,
+) -> Result<()> {
+    let db_path = config.db_path();
+    let staging_path = db_path.join("snapshot_import");
+    let complete_path = staging_path.join("COMPLETE");
+    if !complete_path.exists() {
+        if db_path.join("store").exists() {
+            info!("The database is not empty, not bootstrapping it from the state snapshot");
+            return Ok(());
+        }
+        if staging_path.exists() {
+            warn!("Discarding an interrupted state snapshot import");
+            fs::remove_dir_all(&staging_path)?;
+        }
+        info!(path = ?snapshot_path, "Bootstrapping the database from a state snapshot");
+        let committee = genesis.committee()?;
+        // The stores are closed at the end of the block, before being moved.
+        {
+            let store = AuthorityStore::open(&staging_path.join("store"), None);
+            let committee_store =
+                CommitteeStore::new(staging_path.join("epochs"), &committee, None);
+            let checkpoint_store = Mutex::new(CheckpointStore::open(
+                &staging_path.join("checkpoints"),
+                None,
+                &committee,
+                config.protocol_public_key(),
+                secret,
+                config.enable_reconfig,
+            )?);
+            let snapshot = BufReader::new(File::open(snapshot_path)?);
+            import_state_snapshot(
+                snapshot,
+                &committee,
+                genesis.objects(),
+                &store,
+                &committee_store,
+                &checkpoint_store,
+            )
+            .await?;
+        }
+        File::create(&complete_path)?.sync_all()?;
+    }
+    // Stores already moved by an interrupted start are skipped.
+    for name in SNAPSHOT_STORES {
+        let staged_path = staging_path.join(name);
+        if staged_path.exists() {
+            fs::rename(&staged_path, db_path.join(name))?;
+        }
+    }
+    fs::remove_dir_all(&staging_path)?;
+    Ok(())
+}
+
 pub async fn build_http_servers(
     state: Arc<AuthorityState>,
     transaction_orchestrator: &Option<Arc<TransactiondOrchestrator<NetworkAuthorityClient>>>,
     config: &NodeConfig,
     prometheus_registry: &Registry,
-) -> Result<(Option<HttpServerHandle>, Option<WsServerHandle>)> {
+) -> Result<(Option<JsonRpcHttpServerHandle>, Option<WsServerHandle>)> {
     // Validators do not expose these APIs
     if config.consensus_config().is_some() {
         return Ok((None, None));
@@ -425,8 +582,14 @@ pub async fn build_http_servers(
     }
 
     let mut server = JsonRpcServerBuilder::new(false, prometheus_registry)?;
+    if let Some(rate_limit) = &config.json_rpc_rate_limit {
+        server.set_rate_limit(rate_limit.clone());
+    }
 
-    server.register_module(ReadApi::new(state.clone()))?;
+    server.register_module(ReadApi::new(
+        state.clone(),
+        config.json_rpc_multi_get_max_batch_size,
+    ))?;
     server.register_module(FullNodeApi::new(state.clone()))?;
     server.register_module(BcsApiImpl::new(state.clone()))?;
     server.register_module(FullNodeTransactionBuilderApi::new(state.clone()))?;
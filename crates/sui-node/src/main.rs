@@ -130,9 +130,24 @@ async fn main() -> Result<()> {
         }
     });
 
-    sui_node::admin::start_admin_server(config.admin_interface_port, filter_handle);
+    let object_pruner_switch = sui_node::admin::ObjectPrunerSwitchCell::default();
+    let db_backups = sui_node::admin::DbBackupsCell::default();
+    sui_node::admin::start_admin_server(
+        config.admin_interface_port,
+        filter_handle,
+        object_pruner_switch.clone(),
+        db_backups.clone(),
+    );
 
     let node = sui_node::SuiNode::start(&config, prometheus_registry).await?;
+    if let Some(switch) = node.object_pruner_switch() {
+        object_pruner_switch
+            .set(switch)
+            .expect("object pruner switch is only set once");
+    }
+    db_backups
+        .set(node.db_backups())
+        .expect("db backups are only set once");
     node.wait().await?;
 
     Ok(())
@@ -16,11 +16,11 @@ use sui::client_commands::EXAMPLE_NFT_URL;
 use sui_core::test_utils::to_sender_signed_transaction;
 use sui_json::SuiJsonValue;
 use sui_json_rpc_types::{
-    MoveCallParams, OwnedObjectRef, RPCTransactionRequestParams, SuiCertifiedTransaction, SuiData,
-    SuiEvent, SuiEventEnvelope, SuiExecutionStatus, SuiGasCostSummary, SuiObject, SuiObjectRead,
-    SuiObjectRef, SuiParsedData, SuiPastObjectRead, SuiRawData, SuiRawMoveObject,
-    SuiTransactionData, SuiTransactionEffects, SuiTransactionResponse, TransactionBytes,
-    TransactionsPage, TransferObjectParams,
+    EventPage, MoveCallParams, OwnedObjectRef, RPCTransactionRequestParams,
+    SuiCertifiedTransaction, SuiData, SuiEvent, SuiEventEnvelope, SuiEventFilter,
+    SuiExecutionStatus, SuiGasCostSummary, SuiObject, SuiObjectRead, SuiObjectRef, SuiParsedData,
+    SuiPastObjectRead, SuiRawData, SuiRawMoveObject, SuiTransactionData, SuiTransactionEffects,
+    SuiTransactionResponse, TransactionBytes, TransactionsPage, TransferObjectParams,
 };
 use sui_open_rpc::ExamplePairing;
 use sui_types::base_types::{
@@ -28,7 +28,7 @@ use sui_types::base_types::{
 };
 use sui_types::crypto::{get_key_pair_from_rng, AccountKeyPair, Signature};
 use sui_types::crypto::{AuthorityQuorumSignInfo, SuiSignature};
-use sui_types::event::TransferType;
+use sui_types::event::{EventID, TransferType};
 use sui_types::gas_coin::GasCoin;
 use sui_types::messages::{
     CallArg, ExecuteTransactionRequestType, MoveCall, SingleTransactionKind, Transaction,
@@ -36,7 +36,7 @@ use sui_types::messages::{
 };
 use sui_types::object::Owner;
 use sui_types::query::Ordering;
-use sui_types::query::TransactionQuery;
+use sui_types::query::{EventQuery, TransactionQuery};
 use sui_types::sui_serde::Base64;
 use sui_types::SUI_FRAMEWORK_OBJECT_ID;
 
@@ -78,13 +78,8 @@ impl RpcExampleProvider {
             self.get_total_transaction_number(),
             self.get_transaction(),
             self.get_transactions(),
-            self.get_events_by_transaction(),
-            self.get_events_by_object(),
-            self.get_events_by_sender(),
-            self.get_events_by_recipient(),
-            self.get_events_by_move_event_struct_name(),
-            self.get_events_by_transaction_module(),
-            self.get_events_by_timerange(),
+            self.get_events(),
+            self.query_events(),
         ]
         .into_iter()
         .map(|example| (example.function_name, example.examples))
@@ -449,6 +444,7 @@ impl RpcExampleProvider {
         let events = vec![SuiEventEnvelope {
             timestamp: std::time::Instant::now().elapsed().as_secs(),
             tx_digest: Some(*tx_digest),
+            id: EventID::from((10, 0)),
             event: sui_event.clone(),
         }];
         let result = SuiTransactionResponse {
@@ -499,145 +495,132 @@ impl RpcExampleProvider {
         (data2, signature, recipient, obj_id, result, events)
     }
 
-    fn get_events_by_transaction(&mut self) -> Examples {
-        let (_, _, _, _, result, events) = self.get_transfer_data_response();
-        Examples::new(
-            "sui_getEventsByTransaction",
-            vec![ExamplePairing::new(
-                "Return the Events emitted by a transaction",
-                vec![
-                    (
-                        "digest",
-                        json!(result.certificate.transaction_digest.clone()),
-                    ),
-                    ("count", json!(2)),
-                ],
-                json!(events),
-            )],
-        )
-    }
-
-    fn get_events_by_sender(&mut self) -> Examples {
+    fn get_events(&mut self) -> Examples {
         let ts = std::time::Instant::now().elapsed().as_secs();
-        let (tx_data, _, _, _, _, events) = self.get_transfer_data_response();
-        Examples::new(
-            "sui_getEventsBySender",
-            vec![ExamplePairing::new(
-                "Return the Events associated with the given sender",
-                vec![
-                    ("sender", json!(tx_data.signer())),
-                    ("count", json!(2)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
-                ],
-                json!(events),
-            )],
-        )
-    }
+        let (tx_data, signature, recipient, obj_id, result, events) =
+            self.get_transfer_data_response();
+        let tx_digest = *Transaction::new(tx_data.clone(), signature).digest();
+        let page = EventPage {
+            data: events,
+            next_cursor: Some(EventID::from((11, 0))),
+        };
+        let move_event_page = EventPage {
+            data: vec![SuiEventEnvelope {
+                timestamp: ts,
+                tx_digest: Some(tx_digest),
+                id: EventID::from((20, 1)),
+                event: SuiEvent::MoveEvent {
+                    package_id: ObjectID::from_hex_literal("0x2").unwrap(),
+                    transaction_module: String::from("devnet_nft"),
+                    sender: SuiAddress::from_str("0x9421e7ad826ba13aca8ae41316644f06759b4506")
+                        .unwrap(),
+                    type_: String::from("0x2::devnet_nft::MintNFTEvent"),
+                    fields: None,
+                    bcs: vec![],
+                },
+            }],
+            next_cursor: None,
+        };
 
-    fn get_events_by_recipient(&mut self) -> Examples {
-        let ts = std::time::Instant::now().elapsed().as_secs();
-        let (_, _, recipient, _, _, events) = self.get_transfer_data_response();
-        Examples::new(
-            "sui_getEventsByRecipient",
-            vec![ExamplePairing::new(
+        let queries = vec![
+            (
+                "Return the Events emitted by a transaction",
+                EventQuery::Transaction(result.certificate.transaction_digest),
+                &page,
+            ),
+            (
+                "Return the Events associated with the given sender",
+                EventQuery::Sender(tx_data.signer()),
+                &page,
+            ),
+            (
                 "Return the Events associated with the given recipient",
-                vec![
-                    ("recipient", json!(Owner::AddressOwner(recipient))),
-                    ("count", json!(2)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
-                ],
-                json!(events),
-            )],
-        )
-    }
-
-    fn get_events_by_object(&mut self) -> Examples {
-        let ts = std::time::Instant::now().elapsed().as_secs();
-        let (_, _, _, obj_id, _, events) = self.get_transfer_data_response();
-        Examples::new(
-            "sui_getEventsByObject",
-            vec![ExamplePairing::new(
+                EventQuery::Recipient(Owner::AddressOwner(recipient)),
+                &page,
+            ),
+            (
                 "Return the Events associated with the given object",
-                vec![
-                    ("object", json!(obj_id)),
-                    ("count", json!(2)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
-                ],
-                json!(events),
-            )],
-        )
-    }
+                EventQuery::Object(obj_id),
+                &page,
+            ),
+            (
+                "Return the Events emitted in [start_time, end_time) interval",
+                EventQuery::TimeRange {
+                    start_time: ts,
+                    end_time: ts + 10,
+                },
+                &page,
+            ),
+            (
+                "Return the Events emitted in a specified Move module",
+                EventQuery::MoveModule {
+                    package: ObjectID::from_hex_literal("0x2").unwrap(),
+                    module: "devnet_nft".to_string(),
+                },
+                &move_event_page,
+            ),
+            (
+                "Return the Events with the given move event struct name",
+                EventQuery::MoveEvent("0x2::devnet_nft::MintNFTEvent".to_string()),
+                &move_event_page,
+            ),
+        ];
 
-    fn get_events_by_timerange(&mut self) -> Examples {
-        let ts = std::time::Instant::now().elapsed().as_secs();
-        let (_, _, _, _, _, events) = self.get_transfer_data_response();
         Examples::new(
-            "sui_getEventsByTimeRange",
-            vec![ExamplePairing::new(
-                "Return the Events emitted in [start_time, end_time) interval",
-                vec![
-                    ("count", json!(2)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
-                ],
-                json!(events),
-            )],
+            "sui_getEvents",
+            queries
+                .into_iter()
+                .map(|(description, query, page)| {
+                    ExamplePairing::new(
+                        description,
+                        vec![
+                            ("query", json!(query)),
+                            ("cursor", json!(EventID::from((10, 0)))),
+                            ("limit", json!(100)),
+                            ("order", json!(Ordering::Ascending)),
+                        ],
+                        json!(page),
+                    )
+                })
+                .collect(),
         )
     }
 
-    fn get_events_by_move_event_struct_name(&mut self) -> Examples {
-        let ts = std::time::Instant::now().elapsed().as_secs();
-        let (data, signature, _, _, _, _) = self.get_transfer_data_response();
-        let tx = Transaction::new(data, signature);
-
-        let event = SuiEventEnvelope {
-            timestamp: ts,
-            tx_digest: Some(*tx.digest()),
-            event: SuiEvent::MoveEvent {
-                package_id: ObjectID::from_hex_literal("0x2").unwrap(),
-                transaction_module: String::from("devnet_nft"),
-                sender: SuiAddress::from_str("0x9421e7ad826ba13aca8ae41316644f06759b4506").unwrap(),
-                type_: String::from("0x2::devnet_nft::MintNFTEvent"),
-                fields: None,
-                bcs: vec![],
-            },
+    fn query_events(&mut self) -> Examples {
+        let sender = SuiAddress::from(ObjectID::new(self.rng.gen()));
+        let tx_digest = TransactionDigest::new(self.rng.gen());
+        let page = EventPage {
+            data: vec![SuiEventEnvelope {
+                timestamp: std::time::Instant::now().elapsed().as_secs(),
+                tx_digest: Some(tx_digest),
+                id: EventID::from((20, 1)),
+                event: SuiEvent::MoveEvent {
+                    package_id: SUI_FRAMEWORK_OBJECT_ID,
+                    transaction_module: String::from("devnet_nft"),
+                    sender,
+                    type_: String::from("0x2::devnet_nft::MintNFTEvent"),
+                    fields: None,
+                    bcs: vec![],
+                },
+            }],
+            next_cursor: None,
         };
-        Examples::new(
-            "sui_getEventsByMoveEventStructName",
-            vec![ExamplePairing::new(
-                "Return the Events with the given move event struct name",
-                vec![
-                    (
-                        "move_event_struct_name",
-                        json!("0x2::devnet_nft::MintNFTEvent"),
-                    ),
-                    ("count", json!(5)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
-                ],
-                json!(vec![event]),
-            )],
-        )
-    }
+        let filter = SuiEventFilter::All(vec![
+            SuiEventFilter::MoveEventType("0x2::devnet_nft::MintNFTEvent".to_string()),
+            SuiEventFilter::SenderAddress(sender),
+        ]);
 
-    fn get_events_by_transaction_module(&mut self) -> Examples {
-        let ts = std::time::Instant::now().elapsed().as_secs();
-        let (_, _, _, _, _, events) = self.get_transfer_data_response();
         Examples::new(
-            "sui_getEventsByModule",
+            "sui_queryEvents",
             vec![ExamplePairing::new(
-                "Return the Events emitted in a specified Move module",
+                "Return the MintNFTEvents emitted by the given sender",
                 vec![
-                    ("package", json!(ObjectID::from_hex_literal("0x2").unwrap())),
-                    ("module", json!("devnet_nft")),
-                    ("count", json!(5)),
-                    ("start_time", json!(ts)),
-                    ("end_time", json!(ts + 10)),
+                    ("filter", json!(filter)),
+                    ("cursor", json!(EventID::from((10, 0)))),
+                    ("limit", json!(100)),
+                    ("order", json!(Ordering::Ascending)),
                 ],
-                json!(events),
+                json!(page),
             )],
         )
     }
@@ -12,6 +12,7 @@ use sui_json_rpc::transaction_execution_api::FullNodeTransactionExecutionApi;
 
 use crate::examples::RpcExampleProvider;
 use sui_json_rpc::api::EventStreamingApiOpenRpc;
+use sui_json_rpc::api::TransactionStreamingApiOpenRpc;
 use sui_json_rpc::bcs_api::BcsApiImpl;
 use sui_json_rpc::read_api::{FullNodeApi, ReadApi};
 use sui_json_rpc::sui_rpc_doc;
@@ -47,6 +48,7 @@ async fn main() {
     open_rpc.add_module(FullNodeApi::rpc_doc_module());
     open_rpc.add_module(BcsApiImpl::rpc_doc_module());
     open_rpc.add_module(EventStreamingApiOpenRpc::module_doc());
+    open_rpc.add_module(TransactionStreamingApiOpenRpc::module_doc());
     open_rpc.add_module(EventReadApiOpenRpc::module_doc());
     open_rpc.add_module(FullNodeTransactionExecutionApi::rpc_doc_module());
     open_rpc.add_module(FullNodeTransactionBuilderApi::rpc_doc_module());
@@ -38,6 +38,7 @@ use sui_json_rpc_types::{
 use sui_transaction_builder::{DataReader, TransactionBuilder};
 pub use sui_types as types;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::event::EventID;
 use sui_types::messages::Transaction;
 use sui_types::query::{Ordering, TransactionQuery};
 use types::base_types::SequenceNumber;
@@ -338,14 +339,17 @@ impl FullNodeApi {
 pub struct EventApi(Arc<SuiClientApi>);
 
 impl EventApi {
+    /// Subscribes to events matching `filter`. If a `cursor` is given, stored events from the
+    /// cursor onwards are replayed first, which allows resuming an interrupted subscription.
     pub async fn subscribe_event(
         &self,
         filter: SuiEventFilter,
+        cursor: Option<EventID>,
     ) -> anyhow::Result<impl Stream<Item = Result<SuiEventEnvelope, anyhow::Error>>> {
         match &*self.0 {
             SuiClientApi::Rpc(RpcClient { ws: Some(c), .. }) => {
                 let subscription: Subscription<SuiEventEnvelope> =
-                    c.subscribe_event(filter).await?;
+                    c.subscribe_event(filter, cursor).await?;
                 Ok(subscription.map(|item| Ok(item?)))
             }
             _ => Err(anyhow!("Subscription only supported by WebSocket client.")),
@@ -0,0 +1,65 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics of the column families of the RocksDB databases holding the tables of the stores,
+//! to tell which tables take up disk and memory.
+
+use rocksdb::{DBWithThreadMode, MultiThreaded, Options};
+use serde::{Deserialize, Serialize};
+use sui_types::error::SuiResult;
+use typed_store::rocks::{DBMap, TypedStoreError};
+
+const ESTIMATED_KEYS: &str = "rocksdb.estimate-num-keys";
+const LIVE_SST_SIZE: &str = "rocksdb.live-sst-files-size";
+const MEMTABLE_SIZE: &str = "rocksdb.cur-size-all-mem-tables";
+const PENDING_COMPACTION_BYTES: &str = "rocksdb.estimate-pending-compaction-bytes";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnFamilyStats {
+    /// The name of the column family, which is the name of its table.
+    pub name: String,
+    pub estimated_keys: u64,
+    pub live_sst_size: u64,
+    pub memtable_size: u64,
+    pub pending_compaction_bytes: u64,
+}
+
+/// Returns the statistics of every column family of `db`, but the unused `default` one.
+pub fn column_family_stats(
+    db: &DBWithThreadMode<MultiThreaded>,
+) -> Result<Vec<ColumnFamilyStats>, TypedStoreError> {
+    let names = DBWithThreadMode::<MultiThreaded>::list_cf(&Options::default(), db.path())
+        .map_err(rocksdb_error)?;
+    let mut stats = Vec::new();
+    for name in names {
+        if name == "default" {
+            continue;
+        }
+        let cf = match db.cf_handle(&name) {
+            Some(cf) => cf,
+            None => continue,
+        };
+        let property = |property: &str| {
+            db.property_int_value_cf(&cf, property)
+                .map(|value| value.unwrap_or(0))
+                .map_err(rocksdb_error)
+        };
+        stats.push(ColumnFamilyStats {
+            estimated_keys: property(ESTIMATED_KEYS)?,
+            live_sst_size: property(LIVE_SST_SIZE)?,
+            memtable_size: property(MEMTABLE_SIZE)?,
+            pending_compaction_bytes: property(PENDING_COMPACTION_BYTES)?,
+            name,
+        });
+    }
+    Ok(stats)
+}
+
+/// Returns the statistics of every column family of the database holding `table`.
+pub fn db_stats<K, V>(table: &DBMap<K, V>) -> SuiResult<Vec<ColumnFamilyStats>> {
+    Ok(column_family_stats(&table.rocksdb)?)
+}
+
+fn rocksdb_error(e: rocksdb::Error) -> TypedStoreError {
+    TypedStoreError::RocksDBError(e.to_string())
+}
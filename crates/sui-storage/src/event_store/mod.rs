@@ -18,22 +18,31 @@ use futures::prelude::stream::BoxStream;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::value::MoveValue;
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::collections::BTreeMap;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
 use sui_json_rpc_types::{SuiEvent, SuiEventEnvelope};
 use sui_types::base_types::{ObjectID, SequenceNumber, SuiAddress, TransactionDigest};
 use sui_types::error::SuiError;
 use sui_types::error::SuiError::{StorageCorruptedFieldError, StorageMissingFieldError};
-use sui_types::event::{Event, TransferType};
-use sui_types::event::{EventEnvelope, EventType};
+use sui_types::event::{Event, TransferType, TransferTypeVariants};
+use sui_types::event::{EventEnvelope, EventID, EventType};
+use sui_types::filter::EventFilter;
 use sui_types::object::Owner;
 use tokio_stream::StreamExt;
+use tracing::{debug, warn};
 
+pub mod rocks;
 pub mod sql;
 pub mod test_utils;
+pub use rocks::RocksEventStore;
 pub use sql::SqlEventStore;
 
+#[cfg(test)]
+mod store_tests;
+
 use flexstr::SharedStr;
 
 /// Maximum number of events one can ask for right now
@@ -43,10 +52,15 @@ pub const TRANSFER_TYPE_KEY: &str = "xfer_type";
 pub const OBJECT_VERSION_KEY: &str = "obj_ver";
 pub const AMOUNT_KEY: &str = "amount";
 
+/// Fields beyond this many are not indexed, so that large vectors don't bloat the index.
+const MAX_INDEXED_FIELDS_PER_EVENT: usize = 256;
+
 /// One event pulled out from the EventStore
 #[allow(unused)]
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct StoredEvent {
+    /// Unique ID of the event, made of the tx sequence number and the event's index within the tx
+    id: EventID,
     /// UTC timestamp in milliseconds
     timestamp: u64,
     /// Not present for non-transaction System events (eg EpochChange)
@@ -83,6 +97,10 @@ pub struct StoredEvent {
 }
 
 impl StoredEvent {
+    pub fn id(&self) -> EventID {
+        self.id
+    }
+
     pub fn into_move_event(self) -> Result<SuiEvent, anyhow::Error> {
         let package_id = self.package_id()?;
         let transaction_module = self.transaction_module()?;
@@ -274,6 +292,7 @@ impl StoredEvent {
 impl TryInto<SuiEventEnvelope> for StoredEvent {
     type Error = anyhow::Error;
     fn try_into(self) -> Result<SuiEventEnvelope, Self::Error> {
+        let id = self.id;
         let timestamp = self.timestamp;
         let tx_digest = self.tx_digest;
         let event_type_str = self.event_type.as_str();
@@ -295,6 +314,7 @@ impl TryInto<SuiEventEnvelope> for StoredEvent {
         Ok(SuiEventEnvelope {
             timestamp,
             tx_digest,
+            id,
             event,
         })
     }
@@ -313,6 +333,20 @@ pub enum EventValue {
     Json(Value),
 }
 
+/// Which events to prune from an EventStore, see `EventStore::prune`.
+/// Pruning by age assumes that event timestamps grow with sequence numbers. They are the node's
+/// clock when executing transactions in sequence order, so only a clock stepping back breaks
+/// this, and then events around the step may be pruned a little before or after their max age.
+#[derive(Clone, Debug, Default)]
+pub struct RetentionPolicy {
+    /// Events with a timestamp (in ms) below this are pruned.
+    pub min_timestamp_ms: Option<u64>,
+    /// Only the newest `max_event_count` events are kept.
+    pub max_event_count: Option<u64>,
+    /// Events of a given type with a timestamp (in ms) below the given one are pruned.
+    pub min_timestamp_ms_by_type: Vec<(EventType, u64)>,
+}
+
 /// An EventStore supports event ingestion and flexible event querying
 /// One can think of events as logs.  They represent a log of what is happening to Sui.
 /// Thus, all different kinds of events fit on a timeline, and one should be able to query for
@@ -327,84 +361,147 @@ pub trait EventStore {
     /// Returns Ok(rows_affected).
     async fn add_events(&self, events: &[EventEnvelope]) -> Result<u64, SuiError>;
 
-    /// Returns at most `limit` events emitted by a given
-    /// transaction, sorted in order emitted.
+    /// Returns at most `limit` events starting from `cursor` (inclusive),
+    /// sorted by event ID in ascending order, or descending if `descending` is set.
+    /// A `None` cursor starts from the first (or last, if descending) event.
+    async fn all_events(
+        &self,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Returns at most `limit` events emitted by a given transaction
+    /// starting from `cursor`, sorted in the order emitted, or reversed if `descending` is set.
     async fn events_by_transaction(
         &self,
         digest: TransactionDigest,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Returns at most `limit` events of a certain EventType
-    /// (e.g. `TransferObject`) within [start_time, end_time),
-    /// sorted in in ascending time.
+    /// (e.g. `TransferObject`) starting from `cursor`, sorted by event ID.
     async fn events_by_type(
         &self,
-        start_time: u64,
-        end_time: u64,
         event_type: EventType,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
-    /// Returns at most `limit` events emitted in a certain Module ID during
-    /// [start_time, end_time), sorted in ascending time.
+    /// Returns at most `limit` events emitted in a certain Module ID
+    /// starting from `cursor`, sorted by event ID.
     async fn events_by_module_id(
         &self,
-        start_time: u64,
-        end_time: u64,
         module: &ModuleId,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Returns at most `limit` events with the move event struct name
-    /// (e.g. `0x2::devnet_nft::MintNFTEvent`) emitted
-    /// during [start_time, end_time), sorted in ascending time.
+    /// (e.g. `0x2::devnet_nft::MintNFTEvent`) starting from `cursor`, sorted by event ID.
     async fn events_by_move_event_struct_name(
         &self,
-        start_time: u64,
-        end_time: u64,
         move_event_struct_name: &str,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Returns at most `limit` events with the move event struct name whose field at `path`,
+    /// a JSON pointer such as `/creator`, equals `value`, starting from `cursor`, sorted by event ID.
+    /// Only scalar fields can be matched.
+    async fn events_by_move_event_field(
+        &self,
+        move_event_struct_name: &str,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Returns at most `limit` events associated with a certain sender
-    /// emitted during [start_time, end_time), sorted in ascending time.
+    /// starting from `cursor`, sorted by event ID.
     async fn events_by_sender(
         &self,
-        start_time: u64,
-        end_time: u64,
         sender: &SuiAddress,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Returns at most `limit` events associated with a certain recipient
-    /// emitted during [start_time, end_time), sorted in ascending time.
+    /// starting from `cursor`, sorted by event ID.
     async fn events_by_recipient(
         &self,
-        start_time: u64,
-        end_time: u64,
         recipient: &Owner,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Returns at most `limit` events associated with a certain object id
-    /// emitted during [start_time, end_time), sorted in ascending time.
+    /// starting from `cursor`, sorted by event ID.
     async fn events_by_object(
         &self,
-        start_time: u64,
-        end_time: u64,
         object: &ObjectID,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError>;
 
     /// Generic event iterator that returns events emitted between
-    /// [start_time, end_time), sorted in ascending time.
+    /// [start_time, end_time) starting from `cursor`, sorted by event ID.
     async fn event_iterator(
         &self,
         start_time: u64,
         end_time: u64,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Returns at most `limit` events matching `filter` and emitted between
+    /// [start_time, end_time), sorted by timestamp in ascending order.
+    /// This serves the deprecated time-ranged queries, which are not paginated.
+    async fn events_in_time_range(
+        &self,
+        filter: &EventFilter,
+        start_time: u64,
+        end_time: u64,
         limit: usize,
     ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Returns at most `limit` events matching an arbitrary EventFilter tree,
+    /// starting from `cursor` and sorted by event ID.
+    async fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError>;
+
+    /// Deletes the events falling out of `policy`, returning the number of events deleted.
+    /// Events are pruned up to a sequence number, so that the events left are the newest ones.
+    /// Queries with a cursor below the pruned sequence numbers, or ascending without a cursor,
+    /// fail with `SuiError::EventsPruned`. Time-ranged queries starting before the oldest event
+    /// left fail with `SuiError::EventTimeRangePruned`.
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<u64, SuiError>;
+
+    /// Returns the space freed by pruning to the file system, incrementally if the store allows.
+    async fn vacuum(&self) -> Result<(), SuiError>;
+
+    /// Returns the sequence number from which events of all types are retained.
+    fn oldest_retained_seq_num(&self) -> u64;
+
+    /// Returns the highest sequence number of the events added so far, events of lower sequence
+    /// numbers are skipped by `add_events`.
+    fn current_seq_num(&self) -> u64;
 }
 
 /// EventStoreType contains different implementations of EventStores, but implements the EventStore trait.
@@ -412,6 +509,191 @@ pub trait EventStore {
 #[enum_dispatch(EventStore)]
 pub enum EventStoreType {
     SqlEventStore,
+    RocksEventStore,
+}
+
+/// Sequence numbers below which events have been pruned, for all event types and by event type.
+#[derive(Default)]
+struct PruningWatermarks {
+    all: AtomicU64,
+    by_type: RwLock<BTreeMap<u16, u64>>,
+}
+
+impl PruningWatermarks {
+    /// Raises the watermark of `event_type`, or of all types if not set, to `seq_num`.
+    fn set(&self, event_type: Option<u16>, seq_num: u64) {
+        match event_type {
+            None => {
+                self.all.fetch_max(seq_num, Ordering::AcqRel);
+            }
+            Some(event_type) => {
+                let mut by_type = self.by_type.write().unwrap();
+                let pruned = by_type.entry(event_type).or_default();
+                *pruned = (*pruned).max(seq_num);
+            }
+        }
+    }
+
+    /// Returns the sequence number below which events of `event_type`, or of every type if not
+    /// set, were pruned. Unlike `oldest_retained_seq_num`, the watermarks of single types are not
+    /// taken into account without a type.
+    fn pruned_below(&self, event_type: Option<EventType>) -> u64 {
+        let all = self.all.load(Ordering::Acquire);
+        match event_type {
+            Some(event_type) => {
+                let by_type = self.by_type.read().unwrap();
+                all.max(by_type.get(&(event_type as u16)).copied().unwrap_or(0))
+            }
+            None => all,
+        }
+    }
+
+    /// Returns the sequence number from which all events of `event_type`, or of all types if not
+    /// set, are retained.
+    fn oldest_retained_seq_num(&self, event_type: Option<EventType>) -> u64 {
+        let by_type = self.by_type.read().unwrap();
+        let pruned_by_type = match event_type {
+            Some(event_type) => by_type.get(&(event_type as u16)).copied(),
+            None => by_type.values().max().copied(),
+        };
+        self.all
+            .load(Ordering::Acquire)
+            .max(pruned_by_type.unwrap_or(0))
+    }
+
+    /// Fails queries starting from a cursor below the pruned events of `event_type`, or of all
+    /// types if not set, as their results would silently miss the pruned events. Ascending queries
+    /// without a cursor start from the first event, so they fail once any event was pruned.
+    /// Queries not restricted to a type skip the events of types pruned on their own, as retention
+    /// by type means these events are not wanted.
+    fn check_not_pruned(
+        &self,
+        cursor: Option<EventID>,
+        descending: bool,
+        event_type: Option<EventType>,
+    ) -> Result<(), SuiError> {
+        match cursor {
+            None if !descending => self.check_cursor_not_pruned(Some((0, 0).into()), event_type),
+            _ => self.check_cursor_not_pruned(cursor, event_type),
+        }
+    }
+
+    /// Like `check_not_pruned`, for queries that can only miss pruned events when given a cursor,
+    /// as they are bounded otherwise.
+    fn check_cursor_not_pruned(
+        &self,
+        cursor: Option<EventID>,
+        event_type: Option<EventType>,
+    ) -> Result<(), SuiError> {
+        let oldest_retained_seq_num = self.pruned_below(event_type);
+        match cursor {
+            Some(cursor) if cursor.tx_seq < oldest_retained_seq_num => {
+                Err(SuiError::EventsPruned {
+                    cursor_seq_num: cursor.tx_seq,
+                    oldest_retained_seq_num,
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails queries of the events emitted from `start_time` if events of all types were pruned
+    /// and `start_time` is before `oldest_retained_timestamp`, the timestamp of the oldest event
+    /// left if any, as their results would silently miss the pruned events.
+    fn check_time_range_not_pruned(
+        &self,
+        start_time: u64,
+        oldest_retained_timestamp: Option<u64>,
+    ) -> Result<(), SuiError> {
+        let oldest_retained_seq_num = self.pruned_below(None);
+        if oldest_retained_seq_num > 0
+            && oldest_retained_timestamp.map_or(true, |timestamp| start_time < timestamp)
+        {
+            return Err(SuiError::EventTimeRangePruned {
+                start_time,
+                oldest_retained_seq_num,
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Converts an event's extra fields into a stringified JSON Value, as kept by the stores.
+fn event_fields_json(event: &EventEnvelope) -> String {
+    // For move events, we only store the move_struct_json_value
+    if let Some(json_value) = &event.move_struct_json_value {
+        json_value.to_string()
+    } else {
+        // For non-move-events, extract whatever we can to rebuild the event
+        // and store them
+        let mut fields = BTreeMap::new();
+        if let Some(transfer_type_u16) = event
+            .event
+            .transfer_type()
+            .map(|tt| TransferTypeVariants::from(tt) as u64)
+        {
+            fields.insert(TRANSFER_TYPE_KEY, transfer_type_u16);
+        };
+        if let Some(object_version) = event.event.object_version().map(|ov| ov.value()) {
+            fields.insert(OBJECT_VERSION_KEY, object_version as u64);
+        }
+        if let Some(amount) = event.event.amount() {
+            fields.insert(AMOUNT_KEY, amount);
+        }
+        json!(fields).to_string()
+    }
+}
+
+/// Parses the extra fields stored by `event_fields_json`.
+// TODO: gracefully handle data corruption/incompatibility without panicking
+fn parse_event_fields(fields_text: &str) -> BTreeMap<SharedStr, EventValue> {
+    if fields_text.is_empty() {
+        return BTreeMap::new();
+    }
+    let fields_json = serde_json::from_str(fields_text)
+        .unwrap_or_else(|e| panic!("Could not parse [{}] as JSON: {}", fields_text, e));
+    if let Value::Object(map) = fields_json {
+        map.into_iter()
+            .map(|(k, v)| (SharedStr::from(k), EventValue::Json(v)))
+            .collect()
+    } else {
+        warn!(
+            ?fields_json,
+            "Could not parse JSON as object, should not happen"
+        );
+        BTreeMap::new()
+    }
+}
+
+/// Flattens the scalar fields of a Move event's JSON into (JSON pointer, JSON text) pairs,
+/// e.g. `{"nft": {"name": "A"}}` into `[("/nft/name", "\"A\"")]`.
+fn flatten_event_fields(json: &Value) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut stack = vec![(String::new(), json)];
+    while let Some((path, value)) = stack.pop() {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter().rev() {
+                    let key = key.replace('~', "~0").replace('/', "~1");
+                    stack.push((format!("{path}/{key}"), value));
+                }
+            }
+            Value::Array(values) => {
+                for (i, value) in values.iter().enumerate().rev() {
+                    stack.push((format!("{path}/{i}"), value));
+                }
+            }
+            _ if fields.len() == MAX_INDEXED_FIELDS_PER_EVENT => {
+                debug!(
+                    MAX_INDEXED_FIELDS_PER_EVENT,
+                    "Not indexing the remaining fields of a Move event"
+                );
+                break;
+            }
+            _ => fields.push((path, value.to_string())),
+        }
+    }
+    fields
 }
 
 /// A wrapper around streaming results which makes them easier to deal with
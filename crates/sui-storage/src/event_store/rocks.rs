@@ -0,0 +1,1062 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! RocksDB-based Event Store
+
+use super::*;
+
+use async_trait::async_trait;
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use sui_types::base_types::SuiAddress;
+use sui_types::error::SuiError;
+use sui_types::object::Owner;
+use tracing::{debug, info, instrument};
+use typed_store::rocks::{open_cf, DBBatch, DBMap, TypedStoreError};
+use typed_store::traits::Map;
+
+/// Events are keyed by (seq_num, event_num), which is also their EventID.
+type EventKey = (u64, u64);
+
+/// Number of events deleted per write batch when pruning.
+const PRUNE_BATCH_SIZE: usize = 10_000;
+
+/// A condition that all events matching a filter satisfy, which can be looked up in an index.
+/// Pairs of conditions are looked up together when an index is keyed by both.
+enum IndexedCondition<'a> {
+    Filter(&'a EventFilter),
+    Module(ObjectID, &'a Identifier),
+    MoveEventField(&'a StructTag, &'a str, &'a Value),
+}
+
+/// An event as kept in the events table. Extra fields are kept as JSON text, like in the SQLite
+/// store, as JSON values can't be deserialized from the table encoding.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct EventRecord {
+    timestamp: u64,
+    tx_digest: Option<TransactionDigest>,
+    event_type: u16,
+    package_id: Option<ObjectID>,
+    module_name: Option<String>,
+    object_id: Option<ObjectID>,
+    fields: String,
+    move_event_name: Option<String>,
+    move_event_contents: Option<Vec<u8>>,
+    sender: Option<SuiAddress>,
+    recipient: Option<Owner>,
+}
+
+impl EventRecord {
+    fn new(event: &EventEnvelope) -> Self {
+        Self {
+            timestamp: event.timestamp,
+            tx_digest: event.tx_digest,
+            event_type: EventType::from(&event.event) as u16,
+            package_id: event.event.package_id(),
+            module_name: event.event.module_name().map(String::from),
+            object_id: event.event.object_id(),
+            fields: event_fields_json(event),
+            move_event_name: event.event.move_event_name(),
+            move_event_contents: event.event.move_event_contents().map(Vec::from),
+            sender: event.event.sender(),
+            recipient: event.event.recipient().cloned(),
+        }
+    }
+
+    fn into_stored_event(self, (seq_num, event_num): EventKey) -> StoredEvent {
+        StoredEvent {
+            id: EventID::from((seq_num, event_num)),
+            timestamp: self.timestamp,
+            tx_digest: self.tx_digest,
+            event_type: SharedStr::from(Event::name_from_ordinal(self.event_type as usize)),
+            package_id: self.package_id,
+            module_name: self.module_name.map(SharedStr::from),
+            function_name: None,
+            object_id: self.object_id,
+            fields: parse_event_fields(&self.fields),
+            move_event_contents: self.move_event_contents,
+            move_event_name: self.move_event_name,
+            sender: self.sender,
+            recipient: self.recipient,
+        }
+    }
+
+    /// Evaluates `filter` against this event, as `EventFilter::matches` does for envelopes.
+    fn matches(&self, filter: &EventFilter) -> bool {
+        match filter {
+            EventFilter::Package(package_id) => self.package_id.as_ref() == Some(package_id),
+            EventFilter::Module(module) => self.module_name.as_deref() == Some(module.as_str()),
+            EventFilter::MoveEventType(struct_tag) => {
+                self.move_event_name.as_ref() == Some(&struct_tag.to_string())
+            }
+            EventFilter::EventType(event_type) => self.event_type == *event_type as u16,
+            EventFilter::MoveEventField { path, value } => {
+                // Only Move events keep their struct JSON in the fields
+                self.move_event_name.is_some()
+                    && serde_json::from_str::<Value>(&self.fields)
+                        .map(|json| json.pointer(path) == Some(value))
+                        .unwrap_or(false)
+            }
+            EventFilter::SenderAddress(sender) => self.sender.as_ref() == Some(sender),
+            EventFilter::Recipient(recipient) => self.recipient.as_ref() == Some(recipient),
+            EventFilter::ObjectId(object_id) => self.object_id.as_ref() == Some(object_id),
+            EventFilter::MatchAll(filters) => filters.iter().all(|f| self.matches(f)),
+            EventFilter::MatchAny(filters) => filters.iter().any(|f| self.matches(f)),
+        }
+    }
+}
+
+/// Tables of the RocksDB event store. Secondary indexes map a key followed by the event key to
+/// nothing, so that the events matching a key are iterated in event ID order.
+struct EventStoreTables {
+    events: DBMap<EventKey, EventRecord>,
+    /// Index from timestamp to events, holding the event type so that events can be pruned by
+    /// type and age without reading them.
+    events_by_timestamp: DBMap<(u64, EventKey), u16>,
+    events_by_transaction: DBMap<(TransactionDigest, EventKey), ()>,
+    events_by_type: DBMap<(u16, EventKey), ()>,
+    /// Index from (package ID, module name) to events.
+    events_by_module: DBMap<((ObjectID, String), EventKey), ()>,
+    events_by_move_event_name: DBMap<(String, EventKey), ()>,
+    /// Index from (move event name, JSON pointer, JSON text) of the scalar fields of Move events.
+    events_by_move_event_field: DBMap<((String, String, String), EventKey), ()>,
+    events_by_sender: DBMap<(SuiAddress, EventKey), ()>,
+    events_by_recipient: DBMap<(Owner, EventKey), ()>,
+    events_by_object: DBMap<(ObjectID, EventKey), ()>,
+    /// Sequence number below which events have been pruned, by event type, where `None` stands
+    /// for events of all types.
+    pruning_watermarks: DBMap<Option<u16>, u64>,
+}
+
+impl EventStoreTables {
+    /// Opens or creates the tables in `path`, each in the column family of its name.
+    fn open(path: &Path) -> Result<Self, TypedStoreError> {
+        let db = open_cf(
+            path,
+            None,
+            &[
+                "events",
+                "events_by_timestamp",
+                "events_by_transaction",
+                "events_by_type",
+                "events_by_module",
+                "events_by_move_event_name",
+                "events_by_move_event_field",
+                "events_by_sender",
+                "events_by_recipient",
+                "events_by_object",
+                "pruning_watermarks",
+            ],
+        )?;
+        Ok(Self {
+            events: DBMap::reopen(&db, Some("events"))?,
+            events_by_timestamp: DBMap::reopen(&db, Some("events_by_timestamp"))?,
+            events_by_transaction: DBMap::reopen(&db, Some("events_by_transaction"))?,
+            events_by_type: DBMap::reopen(&db, Some("events_by_type"))?,
+            events_by_module: DBMap::reopen(&db, Some("events_by_module"))?,
+            events_by_move_event_name: DBMap::reopen(&db, Some("events_by_move_event_name"))?,
+            events_by_move_event_field: DBMap::reopen(&db, Some("events_by_move_event_field"))?,
+            events_by_sender: DBMap::reopen(&db, Some("events_by_sender"))?,
+            events_by_recipient: DBMap::reopen(&db, Some("events_by_recipient"))?,
+            events_by_object: DBMap::reopen(&db, Some("events_by_object"))?,
+            pruning_watermarks: DBMap::reopen(&db, Some("pruning_watermarks"))?,
+        })
+    }
+}
+
+/// RocksDB-based Event Store
+///
+/// ## Data Model
+/// - The events table is keyed by (seq_num, event_num)
+/// - Every indexed attribute has its own column family, keyed by the attribute then the event key
+/// - Events and their index entries are written in a single batch, so concurrent writes don't
+///   block each other like with SQLite
+pub struct RocksEventStore {
+    tables: EventStoreTables,
+    // Sequence number is used to prevent previously ingested events from being ingested again
+    // It acts as a cache, as the last event key is also the highest sequence number.
+    seq_num: AtomicU64,
+    // Acts as a cache of the pruning_watermarks table.
+    watermarks: PruningWatermarks,
+}
+
+impl RocksEventStore {
+    /// Creates or opens a RocksDB event store in the directory `path`.
+    pub fn open(path: &Path) -> Result<Self, SuiError> {
+        let tables = EventStoreTables::open(path)?;
+        let watermarks = PruningWatermarks::default();
+        for (event_type, seq_num) in tables.pruning_watermarks.iter() {
+            watermarks.set(event_type, seq_num);
+        }
+        info!(
+            oldest_retained_seq_num = watermarks.oldest_retained_seq_num(None),
+            "Recovered pruned sequence numbers from event store"
+        );
+        let last_seq_num = tables
+            .events
+            .iter()
+            .skip_to_last()
+            .next()
+            .map(|((seq_num, _), _)| seq_num)
+            .unwrap_or(0);
+        info!(
+            last_seq_num,
+            ?path,
+            "Opened RocksDB EventStore, recovered last sequence number"
+        );
+        Ok(Self {
+            tables,
+            seq_num: AtomicU64::new(last_seq_num),
+            watermarks,
+        })
+    }
+
+    /// Adds the index entries of an event to `batch`, or deletes them if `delete` is set.
+    fn index_event(
+        &self,
+        batch: DBBatch,
+        key: EventKey,
+        record: &EventRecord,
+        delete: bool,
+    ) -> Result<DBBatch, SuiError> {
+        fn update<K: Serialize, V: Serialize>(
+            batch: DBBatch,
+            table: &DBMap<K, V>,
+            entry: Option<(K, V)>,
+            delete: bool,
+        ) -> Result<DBBatch, SuiError> {
+            Ok(match entry {
+                Some((k, _)) if delete => batch.delete_batch(table, std::iter::once(k))?,
+                Some(entry) => batch.insert_batch(table, std::iter::once(entry))?,
+                None => batch,
+            })
+        }
+        let t = &self.tables;
+        let mut batch = update(
+            batch,
+            &t.events_by_timestamp,
+            Some(((record.timestamp, key), record.event_type)),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_transaction,
+            record.tx_digest.map(|digest| ((digest, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_type,
+            Some(((record.event_type, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_module,
+            record
+                .package_id
+                .zip(record.module_name.clone())
+                .map(|module| ((module, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_move_event_name,
+            record.move_event_name.clone().map(|name| ((name, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_sender,
+            record.sender.map(|sender| ((sender, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_recipient,
+            record.recipient.map(|recipient| ((recipient, key), ())),
+            delete,
+        )?;
+        batch = update(
+            batch,
+            &t.events_by_object,
+            record.object_id.map(|object_id| ((object_id, key), ())),
+            delete,
+        )?;
+        if let Some(name) = &record.move_event_name {
+            if let Ok(json) = serde_json::from_str::<Value>(&record.fields) {
+                let fields = flatten_event_fields(&json)
+                    .into_iter()
+                    .map(|(path, value)| (((name.clone(), path, value), key), ()));
+                batch = if delete {
+                    batch.delete_batch(&t.events_by_move_event_field, fields.map(|(k, _)| k))?
+                } else {
+                    batch.insert_batch(&t.events_by_move_event_field, fields)?
+                };
+            }
+        }
+        Ok(batch)
+    }
+
+    /// Returns at most `limit` events from the events table starting from `cursor` (inclusive),
+    /// up to `end` (inclusive) if set, and matching `filter` if set.
+    fn scan_events(
+        &self,
+        cursor: Option<EventID>,
+        end: Option<EventKey>,
+        limit: usize,
+        descending: bool,
+        filter: Option<&EventFilter>,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let events = &self.tables.events;
+        let iter: Box<dyn Iterator<Item = (EventKey, EventRecord)> + '_> = if descending {
+            let start = cursor.map_or((u64::MAX, u64::MAX), |id| (id.tx_seq, id.event_seq));
+            Box::new(events.iter().skip_prior_to(&start)?.reverse())
+        } else {
+            let start = cursor.map_or((0, 0), |id| (id.tx_seq, id.event_seq));
+            Box::new(events.iter().skip_to(&start)?)
+        };
+        Ok(iter
+            .take_while(|(key, _)| Self::before_end(*key, end, descending))
+            .filter(|(_, record)| filter.map_or(true, |filter| record.matches(filter)))
+            .take(limit)
+            .map(|(key, record)| record.into_stored_event(key))
+            .collect())
+    }
+
+    /// Returns at most `limit` events whose key in `index` is `prefix`, starting from `cursor`
+    /// (inclusive), up to `end` (inclusive) if set, and matching `filter` if set.
+    #[allow(clippy::too_many_arguments)]
+    fn scan_index<P>(
+        &self,
+        index: &DBMap<(P, EventKey), ()>,
+        prefix: &P,
+        cursor: Option<EventID>,
+        end: Option<EventKey>,
+        limit: usize,
+        descending: bool,
+        filter: Option<&EventFilter>,
+    ) -> Result<Vec<StoredEvent>, SuiError>
+    where
+        P: Serialize + DeserializeOwned + PartialEq + Clone,
+    {
+        let keys: Box<dyn Iterator<Item = ((P, EventKey), ())> + '_> = if descending {
+            let start = cursor.map_or((u64::MAX, u64::MAX), |id| (id.tx_seq, id.event_seq));
+            Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&(prefix.clone(), start))?
+                    .reverse(),
+            )
+        } else {
+            let start = cursor.map_or((0, 0), |id| (id.tx_seq, id.event_seq));
+            Box::new(index.iter().skip_to(&(prefix.clone(), start))?)
+        };
+        let mut keys = keys
+            .take_while(|((key_prefix, key), _)| {
+                key_prefix == prefix && Self::before_end(*key, end, descending)
+            })
+            .map(|((_, key), _)| key)
+            .peekable();
+        let mut events = Vec::new();
+        while events.len() < limit && keys.peek().is_some() {
+            // Without a filter, exactly the missing number of events is read
+            let chunk: Vec<_> = keys.by_ref().take(limit - events.len()).collect();
+            let records = self.tables.events.multi_get(&chunk)?;
+            for (key, record) in chunk.into_iter().zip(records) {
+                let record = record.ok_or_else(|| {
+                    SuiError::StorageCorruptedFieldError(format!(
+                        "Indexed event {key:?} is missing from the event store"
+                    ))
+                })?;
+                if filter.map_or(true, |filter| record.matches(filter)) {
+                    events.push(record.into_stored_event(key));
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Returns at most `limit` events matching `filter`, up to `end` (inclusive) if set, reading
+    /// them through the index of one of the conditions that every matching event satisfies, if any.
+    fn scan_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventID>,
+        end: Option<EventKey>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let t = &self.tables;
+        let filter_arg = Some(filter);
+        match Self::indexed_condition(filter) {
+            Some(IndexedCondition::Module(package, module)) => self.scan_index(
+                &t.events_by_module,
+                &(package, module.to_string()),
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            Some(IndexedCondition::MoveEventField(struct_tag, path, value)) => self.scan_index(
+                &t.events_by_move_event_field,
+                &(struct_tag.to_string(), path.to_string(), value.to_string()),
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            Some(IndexedCondition::Filter(EventFilter::MoveEventType(struct_tag))) => self
+                .scan_index(
+                    &t.events_by_move_event_name,
+                    &struct_tag.to_string(),
+                    cursor,
+                    end,
+                    limit,
+                    descending,
+                    filter_arg,
+                ),
+            Some(IndexedCondition::Filter(EventFilter::EventType(event_type))) => self.scan_index(
+                &t.events_by_type,
+                &(*event_type as u16),
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            Some(IndexedCondition::Filter(EventFilter::SenderAddress(sender))) => self.scan_index(
+                &t.events_by_sender,
+                sender,
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            Some(IndexedCondition::Filter(EventFilter::Recipient(recipient))) => self.scan_index(
+                &t.events_by_recipient,
+                recipient,
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            Some(IndexedCondition::Filter(EventFilter::ObjectId(object_id))) => self.scan_index(
+                &t.events_by_object,
+                object_id,
+                cursor,
+                end,
+                limit,
+                descending,
+                filter_arg,
+            ),
+            _ => self.scan_events(cursor, end, limit, descending, filter_arg),
+        }
+    }
+
+    /// Fails time-ranged queries from `start_time` that would miss pruned events.
+    fn check_time_range_not_pruned(&self, start_time: u64) -> Result<(), SuiError> {
+        let pruned_below = self.watermarks.pruned_below(None);
+        if pruned_below == 0 {
+            return Ok(());
+        }
+        let timestamp = self
+            .tables
+            .events
+            .iter()
+            .skip_to(&(pruned_below, 0))?
+            .next()
+            .map(|(_, record)| record.timestamp);
+        self.watermarks
+            .check_time_range_not_pruned(start_time, timestamp)
+    }
+
+    /// Returns whether `key` is not past `end`, in the scan direction.
+    fn before_end(key: EventKey, end: Option<EventKey>, descending: bool) -> bool {
+        match end {
+            Some(end) if descending => key >= end,
+            Some(end) => key <= end,
+            None => true,
+        }
+    }
+
+    /// Returns a condition of `filter` that has an index and that all matching events satisfy.
+    /// Within a MatchAll, the Move event field and module indexes are preferred when both of the
+    /// conditions they are keyed by are present, as they narrow down the events the most.
+    fn indexed_condition(filter: &EventFilter) -> Option<IndexedCondition<'_>> {
+        match filter {
+            EventFilter::MoveEventType(_)
+            | EventFilter::EventType(_)
+            | EventFilter::SenderAddress(_)
+            | EventFilter::Recipient(_)
+            | EventFilter::ObjectId(_) => Some(IndexedCondition::Filter(filter)),
+            EventFilter::MatchAll(filters) => {
+                let (mut event_type, mut field, mut package, mut module) = (None, None, None, None);
+                for filter in filters {
+                    match filter {
+                        EventFilter::MoveEventType(struct_tag) => event_type = Some(struct_tag),
+                        // Only scalar fields are flattened into the index
+                        EventFilter::MoveEventField { path, value }
+                            if !value.is_object() && !value.is_array() =>
+                        {
+                            field = Some((path, value))
+                        }
+                        EventFilter::Package(package_id) => package = Some(*package_id),
+                        EventFilter::Module(name) => module = Some(name),
+                        _ => {}
+                    }
+                }
+                if let (Some(struct_tag), Some((path, value))) = (event_type, field) {
+                    return Some(IndexedCondition::MoveEventField(struct_tag, path, value));
+                }
+                if let (Some(package), Some(module)) = (package, module) {
+                    return Some(IndexedCondition::Module(package, module));
+                }
+                filters.iter().find_map(Self::indexed_condition)
+            }
+            _ => None,
+        }
+    }
+
+    /// Rejects filters that the SQLite store would fail to translate, for parity.
+    fn validate_filter(filter: &EventFilter) -> Result<(), SuiError> {
+        match filter {
+            EventFilter::MoveEventField { path, .. }
+                if !path.is_empty() && !path.starts_with('/') =>
+            {
+                Err(SuiError::InvalidEventFilter(format!(
+                    "JSON pointer must start with '/': {path}"
+                )))
+            }
+            EventFilter::MatchAll(filters) | EventFilter::MatchAny(filters) => {
+                filters.iter().try_for_each(Self::validate_filter)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the highest sequence number of the events older than `timestamp_ms`,
+    /// of `event_type` only if set. This is the sequence number of the newest of these events,
+    /// as timestamps grow with sequence numbers (see `RetentionPolicy`).
+    fn max_seq_num_before(
+        &self,
+        timestamp_ms: u64,
+        event_type: Option<EventType>,
+    ) -> Result<Option<u64>, SuiError> {
+        Ok(self
+            .tables
+            .events_by_timestamp
+            .iter()
+            .skip_prior_to(&(timestamp_ms, (0, 0)))?
+            .reverse()
+            .skip_while(|((timestamp, _), _)| *timestamp >= timestamp_ms)
+            .find(|(_, stored_type)| event_type.map_or(true, |t| *stored_type == t as u16))
+            .map(|((_, (seq_num, _)), _)| seq_num))
+    }
+
+    /// Deletes the events below `seq_num`, of `event_type` only if set, returning how many were
+    /// deleted. The new watermark is recorded first, so that queries fail rather than see a
+    /// partially pruned store.
+    fn prune_below(&self, seq_num: u64, event_type: Option<EventType>) -> Result<u64, SuiError> {
+        if seq_num <= self.watermarks.pruned_below(event_type) {
+            return Ok(0);
+        }
+        let watermark_event_type = event_type.map(|event_type| event_type as u16);
+        let current = self
+            .tables
+            .pruning_watermarks
+            .get(&watermark_event_type)?
+            .unwrap_or(0);
+        self.tables
+            .pruning_watermarks
+            .insert(&watermark_event_type, &current.max(seq_num))?;
+        self.watermarks.set(watermark_event_type, seq_num);
+
+        let mut pruned = 0;
+        loop {
+            let keys: Vec<EventKey> = match watermark_event_type {
+                Some(event_type) => self
+                    .tables
+                    .events_by_type
+                    .iter()
+                    .skip_to(&(event_type, (0, 0)))?
+                    .take_while(|((t, (s, _)), _)| *t == event_type && *s < seq_num)
+                    .take(PRUNE_BATCH_SIZE)
+                    .map(|((_, key), _)| key)
+                    .collect(),
+                None => self
+                    .tables
+                    .events
+                    .keys()
+                    .take_while(|(s, _)| *s < seq_num)
+                    .take(PRUNE_BATCH_SIZE)
+                    .collect(),
+            };
+            if keys.is_empty() {
+                break;
+            }
+            let records = self.tables.events.multi_get(&keys)?;
+            let mut batch = self.tables.events.batch();
+            for (key, record) in keys.iter().zip(records) {
+                if let Some(record) = record {
+                    batch = self.index_event(batch, *key, &record, true)?;
+                }
+            }
+            batch = batch.delete_batch(&self.tables.events, keys.iter())?;
+            batch.write()?;
+            pruned += keys.len() as u64;
+        }
+        debug!(seq_num, ?event_type, pruned, "Pruned events");
+        Ok(pruned)
+    }
+}
+
+#[async_trait]
+impl EventStore for RocksEventStore {
+    #[instrument(level = "debug", skip_all, err)]
+    async fn add_events(&self, events: &[EventEnvelope]) -> Result<u64, SuiError> {
+        let initial_seq = self.seq_num.load(Ordering::Acquire);
+        let mut cur_seq = initial_seq;
+        let mut rows_affected: u64 = 0;
+
+        // All events and their index entries are written together
+        let mut batch = self.tables.events.batch();
+        for event in events {
+            // Skip events that have a lower sequence number... which must be same or increasing
+            if event.seq_num < cur_seq {
+                debug!(tx_digest =? event.tx_digest, seq_num = event.seq_num, cur_seq, "Skipping event with lower sequence number than current");
+                continue;
+            }
+            cur_seq = event.seq_num;
+
+            let key = (event.seq_num, event.event_num);
+            if self.tables.events.contains_key(&key)? {
+                debug!(
+                    seq_num = event.seq_num,
+                    event_num = event.event_num,
+                    "Skipping event already stored"
+                );
+                continue;
+            }
+            let record = EventRecord::new(event);
+            batch = self.index_event(batch, key, &record, false)?;
+            batch = batch.insert_batch(&self.tables.events, std::iter::once((key, record)))?;
+            rows_affected += 1;
+        }
+        batch.write()?;
+
+        // CAS is used to detect any concurrency glitches.  Note that we assume a single writer
+        // append model, which is currently true.  In single writer the CAS should never fail.
+        if cur_seq > initial_seq {
+            self.seq_num
+                .compare_exchange(initial_seq, cur_seq, Ordering::Acquire, Ordering::Relaxed)
+                .expect("CAS Failure - event writes are not single threaded");
+        }
+
+        Ok(rows_affected)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn all_events(
+        &self,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        self.scan_events(cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_transaction(
+        &self,
+        digest: TransactionDigest,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        // The events of a transaction are pruned together
+        self.watermarks.check_cursor_not_pruned(cursor, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_transaction;
+        self.scan_index(index, &digest, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_type(
+        &self,
+        event_type: EventType,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(event_type))?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_type;
+        self.scan_index(
+            index,
+            &(event_type as u16),
+            cursor,
+            None,
+            limit,
+            descending,
+            None,
+        )
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_module_id(
+        &self,
+        module: &ModuleId,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let prefix = (ObjectID::from(*module.address()), module.name().to_string());
+        let index = &self.tables.events_by_module;
+        self.scan_index(index, &prefix, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_move_event_struct_name(
+        &self,
+        move_event_struct_name: &str,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(EventType::MoveEvent))?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_move_event_name;
+        let prefix = move_event_struct_name.to_string();
+        self.scan_index(index, &prefix, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_move_event_field(
+        &self,
+        move_event_struct_name: &str,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        if !path.starts_with('/') {
+            return Err(SuiError::InvalidEventFilter(format!(
+                "JSON pointer must start with '/': {path}"
+            )));
+        }
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(EventType::MoveEvent))?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_move_event_field;
+        let prefix = (
+            move_event_struct_name.to_string(),
+            path.to_string(),
+            value.to_string(),
+        );
+        self.scan_index(index, &prefix, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_sender(
+        &self,
+        sender: &SuiAddress,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_sender;
+        self.scan_index(index, sender, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_recipient(
+        &self,
+        recipient: &Owner,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_recipient;
+        self.scan_index(index, recipient, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_object(
+        &self,
+        object: &ObjectID,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        let index = &self.tables.events_by_object;
+        self.scan_index(index, object, cursor, None, limit, descending, None)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn event_iterator(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        self.watermarks.check_cursor_not_pruned(cursor, None)?;
+        self.check_time_range_not_pruned(start_time)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        // Events are read in timestamp order, which is event ID order (see `RetentionPolicy`).
+        // The cursor is found in the time index from the timestamp of its event.
+        let cursor = cursor.map(|id| (id.tx_seq, id.event_seq));
+        let cursor_timestamp = match cursor {
+            Some(key) => self.tables.events.get(&key)?.map(|record| record.timestamp),
+            None => None,
+        };
+        let index = &self.tables.events_by_timestamp;
+        let keys: Box<dyn Iterator<Item = EventKey> + '_> = if descending {
+            let start = match (cursor, cursor_timestamp) {
+                (Some(key), Some(timestamp)) if timestamp < end_time => (timestamp, key),
+                _ => (end_time, (0, 0)),
+            };
+            Box::new(
+                index
+                    .iter()
+                    .skip_prior_to(&start)?
+                    .reverse()
+                    .skip_while(move |((timestamp, _), _)| *timestamp >= end_time)
+                    .take_while(|((timestamp, _), _)| *timestamp >= start_time)
+                    .map(|((_, key), _)| key)
+                    .filter(move |key| cursor.map_or(true, |cursor| *key <= cursor)),
+            )
+        } else {
+            let start = match (cursor, cursor_timestamp) {
+                (Some(key), Some(timestamp)) if timestamp >= start_time => (timestamp, key),
+                _ => (start_time, (0, 0)),
+            };
+            Box::new(
+                index
+                    .iter()
+                    .skip_to(&start)?
+                    .take_while(|((timestamp, _), _)| *timestamp < end_time)
+                    .map(|((_, key), _)| key)
+                    .filter(move |key| cursor.map_or(true, |cursor| *key >= cursor)),
+            )
+        };
+        let keys: Vec<EventKey> = keys.take(limit).collect();
+        let records = self.tables.events.multi_get(&keys)?;
+        keys.into_iter()
+            .zip(records)
+            .map(|(key, record)| {
+                record
+                    .map(|record| record.into_stored_event(key))
+                    .ok_or_else(|| {
+                        SuiError::StorageCorruptedFieldError(format!(
+                            "Indexed event {key:?} is missing from the event store"
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        Self::validate_filter(filter)?;
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        self.scan_filter(filter, cursor, None, limit, descending)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_in_time_range(
+        &self,
+        filter: &EventFilter,
+        start_time: u64,
+        end_time: u64,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        Self::validate_filter(filter)?;
+        self.check_time_range_not_pruned(start_time)?;
+        let limit = limit.min(EVENT_STORE_QUERY_MAX_LIMIT);
+        // Timestamps grow with event IDs (see `RetentionPolicy`), so the events of the time range
+        // are read as the range of event IDs between its first and last events.
+        let index = &self.tables.events_by_timestamp;
+        let first = index.iter().skip_to(&(start_time, (0, 0)))?.next();
+        let last = index
+            .iter()
+            .skip_prior_to(&(end_time, (0, 0)))?
+            .reverse()
+            .find(|((timestamp, _), _)| *timestamp < end_time);
+        let (first, last) = match (first, last) {
+            (Some(((first_timestamp, first), _)), Some(((_, last), _)))
+                if first_timestamp < end_time && first <= last =>
+            {
+                (first, last)
+            }
+            _ => return Ok(vec![]),
+        };
+        let events = self.scan_filter(filter, Some(first.into()), Some(last), limit, false)?;
+        Ok(events
+            .into_iter()
+            .filter(|event| (start_time..end_time).contains(&event.timestamp))
+            .collect())
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<u64, SuiError> {
+        let mut max_pruned_seq_num = None;
+        if let Some(min_timestamp_ms) = policy.min_timestamp_ms {
+            max_pruned_seq_num = self.max_seq_num_before(min_timestamp_ms, None)?;
+        }
+        if let Some(max_event_count) = policy.max_event_count {
+            // Sequence number of the newest event beyond the count. The time index is walked
+            // rather than the events, as it holds the event keys without the event records.
+            let newest_beyond_count = self
+                .tables
+                .events_by_timestamp
+                .iter()
+                .skip_to_last()
+                .reverse()
+                .nth(max_event_count as usize)
+                .map(|((_, (seq_num, _)), _)| seq_num);
+            max_pruned_seq_num = max_pruned_seq_num.max(newest_beyond_count);
+        }
+
+        let mut pruned = 0;
+        if let Some(seq_num) = max_pruned_seq_num {
+            pruned += self.prune_below(seq_num + 1, None)?;
+        }
+        for (event_type, min_timestamp_ms) in &policy.min_timestamp_ms_by_type {
+            if let Some(seq_num) = self.max_seq_num_before(*min_timestamp_ms, Some(*event_type))? {
+                pruned += self.prune_below(seq_num + 1, Some(*event_type))?;
+            }
+        }
+        Ok(pruned)
+    }
+
+    async fn vacuum(&self) -> Result<(), SuiError> {
+        // Space of deleted events is reclaimed by RocksDB background compactions
+        Ok(())
+    }
+
+    fn oldest_retained_seq_num(&self) -> u64 {
+        self.watermarks.oldest_retained_seq_num(None)
+    }
+
+    fn current_seq_num(&self) -> u64 {
+        self.seq_num.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::store_tests::{self, TestEventStore};
+    use super::*;
+
+    #[async_trait]
+    impl TestEventStore for RocksEventStore {
+        async fn open_for_testing(path: &Path) -> Result<Self, SuiError> {
+            RocksEventStore::open(path)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_basic_insert_read() -> Result<(), SuiError> {
+        store_tests::test_basic_insert_read::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_transfers_tx_read() -> Result<(), SuiError> {
+        store_tests::test_transfers_tx_read::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_type() -> Result<(), SuiError> {
+        store_tests::test_query_by_type::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_pagination() -> Result<(), SuiError> {
+        store_tests::test_pagination::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_time_range_pagination() -> Result<(), SuiError> {
+        store_tests::test_time_range_pagination::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_move_events() -> Result<(), SuiError> {
+        store_tests::test_move_events::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_move_event_struct_name() -> Result<(), SuiError> {
+        store_tests::test_query_by_move_event_struct_name::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_sender_recipient_and_object() -> Result<(), SuiError> {
+        store_tests::test_query_by_sender_recipient_and_object::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_filter() -> Result<(), SuiError> {
+        store_tests::test_query_by_filter::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_query_by_move_event_field() -> Result<(), SuiError> {
+        store_tests::test_query_by_move_event_field::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_prune() -> Result<(), SuiError> {
+        store_tests::test_prune::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_u64_conversion() -> Result<(), SuiError> {
+        store_tests::test_u64_conversion::<RocksEventStore>().await
+    }
+
+    #[tokio::test]
+    async fn test_eventstore_seq_num() -> Result<(), SuiError> {
+        store_tests::test_seq_num::<RocksEventStore>().await
+    }
+
+    #[test]
+    fn test_open_error() {
+        // Opening fails, rather than panics, when the store directory cannot be created
+        let dir = tempfile::TempDir::new().unwrap();
+        let file = dir.path().join("not_a_dir");
+        std::fs::write(&file, b"").unwrap();
+        assert!(RocksEventStore::open(&file.join("events")).is_err());
+    }
+}
@@ -6,9 +6,9 @@
 use super::*;
 
 use async_trait::async_trait;
-use serde_json::{json, Value};
+use move_core_types::language_storage::StructTag;
+use serde_json::Value;
 use sqlx::ConnectOptions;
-use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use strum::{EnumMessage, IntoEnumIterator};
@@ -16,11 +16,11 @@ use sui_types::base_types::SuiAddress;
 use sui_types::object::Owner;
 
 use sqlx::{
-    sqlite::{SqliteConnectOptions, SqliteRow},
-    Executor, Row, SqlitePool,
+    sqlite::{SqliteAutoVacuum, SqliteConnectOptions, SqliteRow},
+    Executor, QueryBuilder, Row, Sqlite, SqlitePool, Transaction,
 };
 use sui_types::error::SuiError;
-use sui_types::event::{Event, TransferTypeVariants};
+use sui_types::event::Event;
 use tracing::{debug, info, instrument, log, warn};
 
 /// Sqlite-based Event Store
@@ -35,6 +35,8 @@ pub struct SqlEventStore {
     // Sequence number is used to prevent previously ingested events from being ingested again
     // It acts as a cache, as the seq_num field is also written to the DB.
     seq_num: AtomicU64,
+    // Acts as a cache of the pruning_watermarks table.
+    watermarks: PruningWatermarks,
 }
 
 /// Important for updating Columns:
@@ -72,11 +74,48 @@ enum EventsTableColumns {
     Sender,
     /// recipient TEXT
     Recipient,
+    /// event_num INTEGER
+    EventNum,
 }
 
-const SQL_INSERT_TX: &str = "INSERT INTO events (timestamp, seq_num, tx_digest, event_type, \
+/// Events already stored are ignored, so that the events of a transaction can be added again,
+/// e.g. when resuming an interrupted rebuild of the event store.
+const SQL_INSERT_TX: &str =
+    "INSERT OR IGNORE INTO events (timestamp, seq_num, tx_digest, event_type, \
     package_id, module_name, object_id, fields, move_event_name, contents, sender,  \
-    recipient) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+    recipient, event_num) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+
+/// Scalar fields of Move events, flattened so that events can be looked up by field value.
+/// Fields are addressed by JSON pointer (e.g. `/nft/name`) and values are stored as JSON text.
+const SQL_CREATE_EVENT_FIELDS: &str = "CREATE TABLE IF NOT EXISTS event_fields(\
+    seq_num INTEGER NOT NULL, event_num INTEGER NOT NULL, move_event_name TEXT NOT NULL, \
+    path TEXT NOT NULL, value TEXT NOT NULL)";
+
+const SQL_CREATE_EVENT_FIELDS_INDEX: &str = "CREATE INDEX IF NOT EXISTS event_fields_idx on \
+    event_fields (move_event_name, path, value, seq_num, event_num)";
+
+/// Events stored before the event_num column existed are numbered in insertion order within
+/// their transaction.
+const SQL_BACKFILL_EVENT_NUM: &str = "UPDATE events SET event_num = (SELECT COUNT(*) \
+    FROM events AS previous WHERE previous.seq_num = events.seq_num \
+    AND previous.rowid < events.rowid) WHERE event_num IS NULL";
+
+/// Sequence number below which events have been pruned, by event type, where
+/// `PRUNED_ALL_EVENT_TYPES` stands for events of all types.
+const SQL_CREATE_PRUNING_WATERMARKS: &str = "CREATE TABLE IF NOT EXISTS pruning_watermarks(\
+    event_type INTEGER PRIMARY KEY, seq_num INTEGER NOT NULL)";
+
+const PRUNED_ALL_EVENT_TYPES: i64 = -1;
+
+/// Number of rows deleted per statement when pruning, so that writers are not blocked for long.
+const PRUNE_BATCH_SIZE: i64 = 10_000;
+
+/// Number of free pages returned to the file system at a time by `vacuum`, so that event writes
+/// are not blocked for long.
+const VACUUM_BATCH_PAGES: i64 = 10_000;
+
+/// Value of `PRAGMA auto_vacuum` for incremental vacuuming.
+const AUTO_VACUUM_INCREMENTAL: i64 = 2;
 
 const INDEXED_COLUMNS: &[&str] = &[
     "timestamp",
@@ -100,6 +139,7 @@ impl SqlEventStore {
         Ok(Self {
             pool,
             seq_num: AtomicU64::new(0),
+            watermarks: PruningWatermarks::default(),
         })
     }
 
@@ -108,7 +148,8 @@ impl SqlEventStore {
         // TODO: configure other SQLite options
         let mut options = SqliteConnectOptions::new()
             .filename(db_path)
-            .create_if_missing(true);
+            .create_if_missing(true)
+            .auto_vacuum(SqliteAutoVacuum::Incremental);
         options.log_statements(log::LevelFilter::Off);
         let pool = SqlitePool::connect_with(options)
             .await
@@ -117,6 +158,7 @@ impl SqlEventStore {
         Ok(Self {
             pool,
             seq_num: AtomicU64::new(0),
+            watermarks: PruningWatermarks::default(),
         })
     }
 
@@ -137,6 +179,7 @@ impl SqlEventStore {
             .await
             .map_err(convert_sqlx_err)?;
         info!("SQLite events table is initialized with query {create_sql:?}");
+        let added_columns = self.add_missing_columns().await?;
 
         // Then, create indexes
         for column in INDEXED_COLUMNS {
@@ -154,6 +197,59 @@ impl SqlEventStore {
                 .map_err(convert_sqlx_err)?;
             info!(column, "Index is ready");
         }
+        // Event IDs are unique, and all queries are paginated by them
+        self.pool
+            .execute(
+                "CREATE UNIQUE INDEX IF NOT EXISTS event_id_idx on events (seq_num, event_num)",
+            )
+            .await
+            .map_err(convert_sqlx_err)?;
+        if added_columns.contains(&"event_num") {
+            let result = self
+                .pool
+                .execute(SQL_BACKFILL_EVENT_NUM)
+                .await
+                .map_err(convert_sqlx_err)?;
+            info!(
+                backfilled = result.rows_affected(),
+                "Numbered the events stored before event numbers existed"
+            );
+        }
+        info!("Event ID index is ready");
+
+        let event_fields_exist = self.table_exists("event_fields").await?;
+        self.pool
+            .execute(SQL_CREATE_EVENT_FIELDS)
+            .await
+            .map_err(convert_sqlx_err)?;
+        self.pool
+            .execute(SQL_CREATE_EVENT_FIELDS_INDEX)
+            .await
+            .map_err(convert_sqlx_err)?;
+        if !event_fields_exist {
+            // Databases created before the side table existed need their fields indexed
+            self.backfill_event_fields().await?;
+        }
+        info!("Event fields index is ready");
+
+        self.pool
+            .execute(SQL_CREATE_PRUNING_WATERMARKS)
+            .await
+            .map_err(convert_sqlx_err)?;
+        let watermarks = sqlx::query("SELECT event_type, seq_num FROM pruning_watermarks")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        for row in watermarks {
+            let event_type: i64 = row.get(0);
+            let seq_num: i64 = row.get(1);
+            self.watermarks
+                .set(Self::watermark_event_type(event_type), seq_num as u64);
+        }
+        info!(
+            oldest_retained_seq_num = self.watermarks.oldest_retained_seq_num(None),
+            "Recovered pruned sequence numbers from event store"
+        );
 
         // Setting last sequence number
         let last_seq_num = self.last_seq_num().await?;
@@ -177,6 +273,239 @@ impl SqlEventStore {
         Ok(num_rows as usize)
     }
 
+    async fn table_exists(&self, table: &str) -> Result<bool, SuiError> {
+        let result =
+            sqlx::query("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?")
+                .bind(table)
+                .fetch_one(&self.pool)
+                .await
+                .map_err(convert_sqlx_err)?;
+        let count: i64 = result.get(0);
+        Ok(count > 0)
+    }
+
+    /// Adds the columns appended to `EventsTableColumns` after the events table was created, and
+    /// returns their names.
+    async fn add_missing_columns(&self) -> Result<Vec<&'static str>, SuiError> {
+        let existing: Vec<String> = sqlx::query("SELECT name FROM pragma_table_info('events')")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        let mut added = Vec::new();
+        for column in EventsTableColumns::iter() {
+            let definition = column.get_documentation().unwrap();
+            let name = definition.split_whitespace().next().unwrap();
+            if existing.iter().any(|existing| existing == name) {
+                continue;
+            }
+            self.pool
+                .execute(format!("ALTER TABLE events ADD COLUMN {}", definition).as_str())
+                .await
+                .map_err(convert_sqlx_err)?;
+            info!(column = name, "Added missing column to the events table");
+            added.push(name);
+        }
+        Ok(added)
+    }
+
+    /// Indexes the fields of all Move events already in the events table.
+    async fn backfill_event_fields(&self) -> Result<(), SuiError> {
+        let mut cursor = (-1i64, -1i64);
+        let mut backfilled = 0;
+        loop {
+            let rows = sqlx::query(
+                "SELECT seq_num, event_num, move_event_name, fields FROM events \
+                WHERE move_event_name IS NOT NULL AND (seq_num, event_num) > (?, ?) \
+                ORDER BY seq_num ASC, event_num ASC LIMIT ?",
+            )
+            .bind(cursor.0)
+            .bind(cursor.1)
+            .bind(EVENT_STORE_QUERY_MAX_LIMIT as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+            let last = match rows.last() {
+                Some(row) => (row.get(0), row.get(1)),
+                None => break,
+            };
+            let mut db_tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
+            for row in &rows {
+                let fields_text: &str = row.get(3);
+                let json: Value = match serde_json::from_str(fields_text) {
+                    Ok(json) => json,
+                    Err(e) => {
+                        warn!(
+                            fields_text,
+                            "Could not parse Move event fields as JSON: {e}"
+                        );
+                        continue;
+                    }
+                };
+                Self::insert_event_fields(&mut db_tx, row.get(0), row.get(1), row.get(2), &json)
+                    .await?;
+            }
+            db_tx.commit().await.map_err(convert_sqlx_err)?;
+            backfilled += rows.len();
+            cursor = last;
+        }
+        info!(backfilled, "Indexed fields of existing Move events");
+        Ok(())
+    }
+
+    /// Inserts the flattened fields of one Move event into the event_fields table.
+    async fn insert_event_fields(
+        db_tx: &mut Transaction<'_, Sqlite>,
+        seq_num: i64,
+        event_num: i64,
+        move_event_name: &str,
+        json: &Value,
+    ) -> Result<(), SuiError> {
+        let fields = flatten_event_fields(json);
+        if fields.is_empty() {
+            return Ok(());
+        }
+        let mut builder = QueryBuilder::new(
+            "INSERT INTO event_fields (seq_num, event_num, move_event_name, path, value) ",
+        );
+        builder.push_values(fields, |mut row, (path, value)| {
+            row.push_bind(seq_num)
+                .push_bind(event_num)
+                .push_bind(move_event_name)
+                .push_bind(path)
+                .push_bind(value);
+        });
+        builder
+            .build()
+            .persistent(false)
+            .execute(db_tx)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(())
+    }
+
+    fn watermark_event_type(event_type: i64) -> Option<u16> {
+        if event_type == PRUNED_ALL_EVENT_TYPES {
+            None
+        } else {
+            Some(event_type as u16)
+        }
+    }
+
+    /// Returns the highest sequence number of the events older than `timestamp_ms`,
+    /// of `event_type` only if set.
+    async fn max_seq_num_before(
+        &self,
+        timestamp_ms: u64,
+        event_type: Option<EventType>,
+    ) -> Result<Option<u64>, SuiError> {
+        let row = match event_type {
+            Some(event_type) => sqlx::query(
+                "SELECT MAX(seq_num) FROM events WHERE timestamp < ? AND event_type = ?",
+            )
+            .bind(timestamp_ms as i64)
+            .bind(event_type as u16),
+            None => sqlx::query("SELECT MAX(seq_num) FROM events WHERE timestamp < ?")
+                .bind(timestamp_ms as i64),
+        }
+        .fetch_one(&self.pool)
+        .await
+        .map_err(convert_sqlx_err)?;
+        let seq_num: Option<i64> = row.get(0);
+        Ok(seq_num.map(|seq_num| seq_num as u64))
+    }
+
+    /// Fails time-ranged queries from `start_time` that would miss pruned events.
+    async fn check_time_range_not_pruned(&self, start_time: u64) -> Result<(), SuiError> {
+        let pruned_below = self.watermarks.pruned_below(None);
+        if pruned_below == 0 {
+            return Ok(());
+        }
+        let timestamp: Option<i64> = sqlx::query_scalar(
+            "SELECT timestamp FROM events WHERE seq_num >= ? \
+            ORDER BY seq_num ASC, event_num ASC LIMIT 1",
+        )
+        .bind(pruned_below as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(convert_sqlx_err)?;
+        self.watermarks
+            .check_time_range_not_pruned(start_time, timestamp.map(|timestamp| timestamp as u64))
+    }
+
+    /// Deletes the events below `seq_num`, of `event_type` only if set, returning how many were
+    /// deleted. The new watermark is recorded first, so that queries fail rather than see a
+    /// partially pruned store.
+    async fn prune_below(
+        &self,
+        seq_num: u64,
+        event_type: Option<EventType>,
+    ) -> Result<u64, SuiError> {
+        if seq_num <= self.watermarks.pruned_below(event_type) {
+            return Ok(0);
+        }
+        let watermark_event_type = event_type.map(|event_type| event_type as u16);
+        sqlx::query(
+            "INSERT INTO pruning_watermarks (event_type, seq_num) VALUES (?, ?) \
+            ON CONFLICT(event_type) DO UPDATE SET seq_num = MAX(seq_num, excluded.seq_num)",
+        )
+        .bind(watermark_event_type.map_or(PRUNED_ALL_EVENT_TYPES, i64::from))
+        .bind(seq_num as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(convert_sqlx_err)?;
+        self.watermarks.set(watermark_event_type, seq_num);
+
+        let mut pruned = 0;
+        loop {
+            let query = match event_type {
+                Some(event_type) => sqlx::query(
+                    "DELETE FROM events WHERE rowid IN (SELECT rowid FROM events \
+                    WHERE seq_num < ? AND event_type = ? LIMIT ?)",
+                )
+                .bind(seq_num as i64)
+                .bind(event_type as u16),
+                None => sqlx::query(
+                    "DELETE FROM events WHERE rowid IN (SELECT rowid FROM events \
+                    WHERE seq_num < ? LIMIT ?)",
+                )
+                .bind(seq_num as i64),
+            };
+            let deleted = query
+                .bind(PRUNE_BATCH_SIZE)
+                .execute(&self.pool)
+                .await
+                .map_err(convert_sqlx_err)?
+                .rows_affected();
+            pruned += deleted;
+            if deleted < PRUNE_BATCH_SIZE as u64 {
+                break;
+            }
+        }
+        // Only Move events have indexed fields
+        if matches!(event_type, None | Some(EventType::MoveEvent)) {
+            loop {
+                let deleted = sqlx::query(
+                    "DELETE FROM event_fields WHERE rowid IN (SELECT rowid FROM event_fields \
+                    WHERE seq_num < ? LIMIT ?)",
+                )
+                .bind(seq_num as i64)
+                .bind(PRUNE_BATCH_SIZE)
+                .execute(&self.pool)
+                .await
+                .map_err(convert_sqlx_err)?
+                .rows_affected();
+                if deleted < PRUNE_BATCH_SIZE as u64 {
+                    break;
+                }
+            }
+        }
+        debug!(seq_num, ?event_type, pruned, "Pruned events");
+        Ok(pruned)
+    }
+
     async fn last_seq_num(&self) -> Result<u64, SuiError> {
         let result = sqlx::query("SELECT MAX(seq_num) FROM events")
             .fetch_one(&self.pool)
@@ -229,38 +558,184 @@ impl SqlEventStore {
         }
     }
 
-    // convert an event's extra fields into a stringified JSON Value.
-    fn event_to_json(event: &EventEnvelope) -> String {
-        // For move events, we only store the move_struct_json_value
-        if let Some(json_value) = &event.move_struct_json_value {
-            json_value.to_string()
+    fn cap_limit(limit: usize) -> usize {
+        if limit > EVENT_STORE_QUERY_MAX_LIMIT {
+            EVENT_STORE_QUERY_MAX_LIMIT
         } else {
-            // For non-move-events, extract whatever we can to rebuild the event
-            // and store them
-            let mut fields = BTreeMap::new();
-            if let Some(transfer_type_u16) = event
-                .event
-                .transfer_type()
-                .map(|tt| TransferTypeVariants::from(tt) as u64)
+            limit
+        }
+    }
+
+    /// Returns the (seq_num, event_num) bounds to start a page from.
+    /// Without a cursor the page starts from the first event, or from the last one if descending.
+    fn cursor_bounds(cursor: Option<EventID>, descending: bool) -> (i64, i64) {
+        match cursor {
+            Some(id) => (id.tx_seq as i64, id.event_seq as i64),
+            None if descending => (i64::MAX, i64::MAX),
+            None => (0, 0),
+        }
+    }
+
+    /// Builds a query selecting events matching `condition`, starting from an inclusive
+    /// (seq_num, event_num) cursor and ordered by event ID.
+    /// Bind order: condition parameters, cursor seq_num, cursor event_num, limit.
+    fn paginated_query(condition: &str, descending: bool) -> String {
+        let (cmp, order) = Self::cursor_operators(descending);
+        format!(
+            "SELECT * FROM events WHERE {condition} AND (seq_num, event_num) {cmp} (?, ?) \
+            ORDER BY seq_num {order}, event_num {order} LIMIT ?"
+        )
+    }
+
+    /// Returns the cursor comparison operator and the sort order for a page.
+    fn cursor_operators(descending: bool) -> (&'static str, &'static str) {
+        if descending {
+            ("<=", "DESC")
+        } else {
+            (">=", "ASC")
+        }
+    }
+
+    /// Appends the SQL condition equivalent to `filter` to the query being built.
+    /// All compared values are bound as query parameters.
+    fn push_filter_condition(
+        builder: &mut QueryBuilder<'_, Sqlite>,
+        filter: &EventFilter,
+    ) -> Result<(), SuiError> {
+        Self::push_scoped_filter_condition(builder, filter, None)
+    }
+
+    /// Like `push_filter_condition`, where `event_type` is the Move event type that an enclosing
+    /// MatchAll requires, if any, so that field conditions can look up the event_fields index.
+    fn push_scoped_filter_condition(
+        builder: &mut QueryBuilder<'_, Sqlite>,
+        filter: &EventFilter,
+        event_type: Option<&StructTag>,
+    ) -> Result<(), SuiError> {
+        match filter {
+            EventFilter::Package(package_id) => {
+                builder.push("package_id = ").push_bind(package_id.to_vec());
+            }
+            EventFilter::Module(module) => {
+                builder.push("module_name = ").push_bind(module.to_string());
+            }
+            EventFilter::MoveEventType(struct_tag) => {
+                builder
+                    .push("move_event_name = ")
+                    .push_bind(struct_tag.to_string());
+            }
+            EventFilter::EventType(event_type) => {
+                builder.push("event_type = ").push_bind(*event_type as u16);
+            }
+            // Only scalar fields are flattened into event_fields
+            EventFilter::MoveEventField { path, value }
+                if !value.is_object() && !value.is_array() =>
             {
-                fields.insert(TRANSFER_TYPE_KEY, transfer_type_u16);
-            };
-            if let Some(object_version) = event.event.object_version().map(|ov| ov.value()) {
-                fields.insert(OBJECT_VERSION_KEY, object_version as u64);
+                if !path.starts_with('/') {
+                    return Err(SuiError::InvalidEventFilter(format!(
+                        "JSON pointer must start with '/': {path}"
+                    )));
+                }
+                builder.push("EXISTS (SELECT 1 FROM event_fields f WHERE f.move_event_name = ");
+                match event_type {
+                    Some(struct_tag) => builder.push_bind(struct_tag.to_string()),
+                    None => builder.push("events.move_event_name"),
+                };
+                builder
+                    .push(" AND f.path = ")
+                    .push_bind(path.clone())
+                    .push(" AND f.value = ")
+                    .push_bind(value.to_string())
+                    .push(" AND f.seq_num = events.seq_num AND f.event_num = events.event_num)");
+            }
+            EventFilter::MoveEventField { path, value } => {
+                // Only Move events keep their struct JSON in the fields column
+                builder
+                    .push("(move_event_name IS NOT NULL AND json_extract(fields, ")
+                    .push_bind(Self::json_pointer_to_path(path)?)
+                    .push(") = json_extract(")
+                    .push_bind(value.to_string())
+                    .push(", '$'))");
+            }
+            EventFilter::SenderAddress(sender) => {
+                builder.push("sender = ").push_bind(sender.to_vec());
+            }
+            EventFilter::Recipient(recipient) => {
+                let recipient_str = serde_json::to_string(recipient).map_err(|e| {
+                    SuiError::OwnerFailedToSerialize {
+                        error: (e.to_string()),
+                    }
+                })?;
+                builder.push("recipient = ").push_bind(recipient_str);
             }
-            if let Some(amount) = event.event.amount() {
-                fields.insert(AMOUNT_KEY, amount);
+            EventFilter::ObjectId(object_id) => {
+                builder.push("object_id = ").push_bind(object_id.to_vec());
+            }
+            EventFilter::MatchAll(filters) => {
+                let event_type = filters
+                    .iter()
+                    .find_map(|filter| match filter {
+                        EventFilter::MoveEventType(struct_tag) => Some(struct_tag),
+                        _ => None,
+                    })
+                    .or(event_type);
+                Self::push_filter_list(builder, filters, event_type, " AND ", "TRUE")?
+            }
+            EventFilter::MatchAny(filters) => {
+                Self::push_filter_list(builder, filters, event_type, " OR ", "FALSE")?
             }
-            json!(fields).to_string()
         }
+        Ok(())
     }
 
-    fn cap_limit(limit: usize) -> usize {
-        if limit > EVENT_STORE_QUERY_MAX_LIMIT {
-            EVENT_STORE_QUERY_MAX_LIMIT
-        } else {
-            limit
+    /// Joins the conditions of `filters` with `separator`, or pushes `empty` if there are none,
+    /// so that an empty MatchAll matches everything and an empty MatchAny matches nothing.
+    fn push_filter_list(
+        builder: &mut QueryBuilder<'_, Sqlite>,
+        filters: &[EventFilter],
+        event_type: Option<&StructTag>,
+        separator: &str,
+        empty: &str,
+    ) -> Result<(), SuiError> {
+        if filters.is_empty() {
+            builder.push(empty);
+            return Ok(());
         }
+        builder.push("(");
+        for (i, filter) in filters.iter().enumerate() {
+            if i > 0 {
+                builder.push(separator);
+            }
+            Self::push_scoped_filter_condition(builder, filter, event_type)?;
+        }
+        builder.push(")");
+        Ok(())
+    }
+
+    /// Converts a JSON pointer as used by EventFilter::MoveEventField (e.g. `/nft/0`)
+    /// into a SQLite JSON path (e.g. `$."nft"[0]`).
+    /// Numeric tokens are treated as array indexes, as Move field names can't be numeric.
+    fn json_pointer_to_path(pointer: &str) -> Result<String, SuiError> {
+        let mut path = String::from("$");
+        if pointer.is_empty() {
+            return Ok(path);
+        }
+        let tokens = pointer.strip_prefix('/').ok_or_else(|| {
+            SuiError::InvalidEventFilter(format!("JSON pointer must start with '/': {pointer}"))
+        })?;
+        for token in tokens.split('/') {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            if !token.is_empty() && token.bytes().all(|b| b.is_ascii_digit()) {
+                path.push_str(&format!("[{token}]"));
+            } else if token.contains('"') {
+                return Err(SuiError::InvalidEventFilter(format!(
+                    "Unsupported JSON pointer token: {token}"
+                )));
+            } else {
+                path.push_str(&format!(".\"{token}\""));
+            }
+        }
+        Ok(path)
     }
 }
 
@@ -270,6 +745,8 @@ impl From<SqliteRow> for StoredEvent {
     // TODO: gracefully handle data corruption/incompatibility without panicking
     fn from(row: SqliteRow) -> Self {
         let timestamp: i64 = row.get(EventsTableColumns::Timestamp as usize);
+        let seq_num: i64 = row.get(EventsTableColumns::SeqNum as usize);
+        let event_num: i64 = row.get(EventsTableColumns::EventNum as usize);
         let digest_raw: Option<Vec<u8>> = row.get(EventsTableColumns::TxDigest as usize);
         let tx_digest = digest_raw.map(|bytes| {
             TransactionDigest::new(
@@ -288,23 +765,7 @@ impl From<SqliteRow> for StoredEvent {
         let module_name: Option<String> = row.get(EventsTableColumns::ModuleName as usize);
         let function: Option<String> = row.get(EventsTableColumns::Function as usize);
         let fields_text: &str = row.get(EventsTableColumns::Fields as usize);
-        let fields: BTreeMap<SharedStr, EventValue> = if fields_text.is_empty() {
-            BTreeMap::new()
-        } else {
-            let fields_json = serde_json::from_str(fields_text)
-                .unwrap_or_else(|e| panic!("Could not parse [{}] as JSON: {}", fields_text, e));
-            if let Value::Object(map) = fields_json {
-                map.into_iter()
-                    .map(|(k, v)| (flexstr::SharedStr::from(k), EventValue::Json(v)))
-                    .collect()
-            } else {
-                warn!(
-                    ?fields_json,
-                    "Could not parse JSON as object, should not happen"
-                );
-                BTreeMap::new()
-            }
-        };
+        let fields = parse_event_fields(fields_text);
         let move_event_contents: Option<Vec<u8>> = row.get(EventsTableColumns::Contents as usize);
         let move_event_name: Option<String> = row.get(EventsTableColumns::MoveEventName as usize);
         let sender = SqlEventStore::try_extract_sender_address(&row)
@@ -313,6 +774,7 @@ impl From<SqliteRow> for StoredEvent {
             .expect("Error converting stored recipient address to Owner");
 
         StoredEvent {
+            id: EventID::from((seq_num as u64, event_num as u64)),
             timestamp: timestamp as u64,
             tx_digest,
             event_type: SharedStr::from(Event::name_from_ordinal(event_type as usize)),
@@ -329,29 +791,26 @@ impl From<SqliteRow> for StoredEvent {
     }
 }
 
-const TS_QUERY: &str =
-    "SELECT * FROM events WHERE timestamp >= ? AND timestamp < ? ORDER BY timestamp ASC LIMIT ?";
+const ALL_CONDITION: &str = "TRUE";
+
+const TS_CONDITION: &str = "timestamp >= ? AND timestamp < ?";
 
-const TX_QUERY: &str = "SELECT * FROM events WHERE tx_digest = ? LIMIT ?";
+const TX_CONDITION: &str = "tx_digest = ?";
 
-// ASC is used so there's a way to fetch from last one to NOW
-const QUERY_BY_TYPE: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND event_type = ? ORDER BY timestamp ASC LIMIT ?";
+const TYPE_CONDITION: &str = "event_type = ?";
 
-const QUERY_BY_MODULE: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND package_id = ? AND module_name = ? ORDER BY timestamp ASC LIMIT ?";
+const MODULE_CONDITION: &str = "package_id = ? AND module_name = ?";
 
-const QUERY_BY_MOVE_EVENT_STRUCT_NAME: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND move_event_name = ? ORDER BY timestamp ASC LIMIT ?";
+const MOVE_EVENT_STRUCT_NAME_CONDITION: &str = "move_event_name = ?";
 
-const QUERY_BY_SENDER: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND sender = ? ORDER BY timestamp ASC LIMIT ?";
+const MOVE_EVENT_FIELD_CONDITION: &str = "event_fields.move_event_name = ? AND \
+    event_fields.path = ? AND event_fields.value = ?";
 
-const QUERY_BY_RECIPIENT: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND recipient = ? ORDER BY timestamp ASC LIMIT ?";
+const SENDER_CONDITION: &str = "sender = ?";
 
-const QUERY_BY_OBJECT_ID: &str = "SELECT * FROM events WHERE timestamp >= ? AND \
-    timestamp < ? AND object_id = ? ORDER BY timestamp ASC LIMIT ?";
+const RECIPIENT_CONDITION: &str = "recipient = ?";
+
+const OBJECT_ID_CONDITION: &str = "object_id = ?";
 
 #[async_trait]
 impl EventStore for SqlEventStore {
@@ -380,6 +839,8 @@ impl EventStore for SqlEventStore {
 
             let sender = event.event.sender().map(|sender| sender.to_vec());
             let move_event_name = event.event.move_event_name();
+            // The event and its indexed fields are written together
+            let mut db_tx = self.pool.begin().await.map_err(convert_sqlx_err)?;
 
             // TODO: use batched API?
             let res = insert_tx_q
@@ -390,14 +851,34 @@ impl EventStore for SqlEventStore {
                 .bind(event.event.package_id().map(|pid| pid.to_vec()))
                 .bind(event.event.module_name())
                 .bind(event.event.object_id().map(|id| id.to_vec()))
-                .bind(Self::event_to_json(event))
-                .bind(move_event_name)
+                .bind(event_fields_json(event))
+                .bind(move_event_name.clone())
                 .bind(event.event.move_event_contents())
                 .bind(sender)
                 .bind(event.event.recipient_serialized()?)
-                .execute(&self.pool)
+                .bind(event.event_num as i64)
+                .execute(&mut db_tx)
                 .await
                 .map_err(convert_sqlx_err)?;
+            if res.rows_affected() == 0 {
+                debug!(
+                    seq_num = event.seq_num,
+                    event_num = event.event_num,
+                    "Skipping event already stored"
+                );
+            } else if let (Some(name), Some(json)) =
+                (&move_event_name, &event.move_struct_json_value)
+            {
+                Self::insert_event_fields(
+                    &mut db_tx,
+                    event.seq_num as i64,
+                    event.event_num as i64,
+                    name,
+                    json,
+                )
+                .await?;
+            }
+            db_tx.commit().await.map_err(convert_sqlx_err)?;
             rows_affected += res.rows_affected();
         }
 
@@ -413,16 +894,47 @@ impl EventStore for SqlEventStore {
         Ok(rows_affected)
     }
 
+    #[instrument(level = "debug", skip_all, err)]
+    async fn all_events(
+        &self,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let limit = Self::cap_limit(limit);
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(ALL_CONDITION, descending);
+        let rows = sqlx::query(&query)
+            .persistent(true)
+            .bind(tx_seq)
+            .bind(event_seq)
+            .bind(limit as i64)
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
+
     #[instrument(level = "debug", skip_all, err)]
     async fn events_by_transaction(
         &self,
         digest: TransactionDigest,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let rows = sqlx::query(TX_QUERY)
+        // The events of a transaction are pruned together
+        self.watermarks.check_cursor_not_pruned(cursor, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(TX_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
             .bind(digest.to_bytes())
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -434,17 +946,21 @@ impl EventStore for SqlEventStore {
     #[instrument(level = "debug", skip_all, err)]
     async fn events_by_type(
         &self,
-        start_time: u64,
-        end_time: u64,
         event_type: EventType,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let rows = sqlx::query(QUERY_BY_TYPE)
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(event_type))?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(TYPE_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
             .bind(event_type as u16)
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -454,16 +970,23 @@ impl EventStore for SqlEventStore {
     }
 
     #[instrument(level = "debug", skip_all, err)]
-    async fn event_iterator(
+    async fn events_by_module_id(
         &self,
-        start_time: u64,
-        end_time: u64,
+        module: &ModuleId,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let rows = sqlx::query(TS_QUERY)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(MODULE_CONDITION, descending);
+        let rows = sqlx::query(&query)
+            .persistent(true)
+            .bind(module.address().to_vec())
+            .bind(module.name().to_string())
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -473,20 +996,23 @@ impl EventStore for SqlEventStore {
     }
 
     #[instrument(level = "debug", skip_all, err)]
-    async fn events_by_module_id(
+    async fn events_by_move_event_struct_name(
         &self,
-        start_time: u64,
-        end_time: u64,
-        module: &ModuleId,
+        move_event_struct_name: &str,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let rows = sqlx::query(QUERY_BY_MODULE)
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(EventType::MoveEvent))?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(MOVE_EVENT_STRUCT_NAME_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
-            .bind(module.address().to_vec())
-            .bind(module.name().to_string())
+            .bind(move_event_struct_name)
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -496,20 +1022,39 @@ impl EventStore for SqlEventStore {
     }
 
     #[instrument(level = "debug", skip_all, err)]
-    async fn events_by_move_event_struct_name(
+    async fn events_by_move_event_field(
         &self,
-        start_time: u64,
-        end_time: u64,
         move_event_struct_name: &str,
+        path: &str,
+        value: &Value,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
+        if !path.starts_with('/') {
+            return Err(SuiError::InvalidEventFilter(format!(
+                "JSON pointer must start with '/': {path}"
+            )));
+        }
         let limit = Self::cap_limit(limit);
-        // TODO: duplication: these 10 lines are repetitive (4 times) in this file.
-        let rows = sqlx::query(QUERY_BY_MOVE_EVENT_STRUCT_NAME)
+        self.watermarks
+            .check_not_pruned(cursor, descending, Some(EventType::MoveEvent))?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let (cmp, order) = Self::cursor_operators(descending);
+        let query = format!(
+            "SELECT events.* FROM event_fields JOIN events \
+            ON events.seq_num = event_fields.seq_num AND events.event_num = event_fields.event_num \
+            WHERE {MOVE_EVENT_FIELD_CONDITION} \
+            AND (event_fields.seq_num, event_fields.event_num) {cmp} (?, ?) \
+            ORDER BY event_fields.seq_num {order}, event_fields.event_num {order} LIMIT ?"
+        );
+        let rows = sqlx::query(&query)
             .persistent(true)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
             .bind(move_event_struct_name)
+            .bind(path)
+            .bind(value.to_string())
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -521,18 +1066,20 @@ impl EventStore for SqlEventStore {
     #[instrument(level = "debug", skip_all, err)]
     async fn events_by_sender(
         &self,
-        start_time: u64,
-        end_time: u64,
         sender: &SuiAddress,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let sender_vec = sender.to_vec();
-        let rows = sqlx::query(QUERY_BY_SENDER)
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(SENDER_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
-            .bind(sender_vec)
+            .bind(sender.to_vec())
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -544,21 +1091,24 @@ impl EventStore for SqlEventStore {
     #[instrument(level = "debug", skip_all, err)]
     async fn events_by_recipient(
         &self,
-        start_time: u64,
-        end_time: u64,
         recipient: &Owner,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
         let recipient_str =
             serde_json::to_string(recipient).map_err(|e| SuiError::OwnerFailedToSerialize {
                 error: (e.to_string()),
             })?;
-        let rows = sqlx::query(QUERY_BY_RECIPIENT)
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(RECIPIENT_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
-            .bind(start_time as i64)
-            .bind(end_time as i64)
             .bind(recipient_str)
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -570,19 +1120,48 @@ impl EventStore for SqlEventStore {
     #[instrument(level = "debug", skip_all, err)]
     async fn events_by_object(
         &self,
-        start_time: u64,
-        end_time: u64,
         object: &ObjectID,
+        cursor: Option<EventID>,
         limit: usize,
+        descending: bool,
     ) -> Result<Vec<StoredEvent>, SuiError> {
         let limit = Self::cap_limit(limit);
-        let object_vec = object.to_vec();
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(OBJECT_ID_CONDITION, descending);
+        let rows = sqlx::query(&query)
+            .persistent(true)
+            .bind(object.to_vec())
+            .bind(tx_seq)
+            .bind(event_seq)
+            .bind(limit as i64)
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
 
-        let rows = sqlx::query(QUERY_BY_OBJECT_ID)
+    #[instrument(level = "debug", skip_all, err)]
+    async fn event_iterator(
+        &self,
+        start_time: u64,
+        end_time: u64,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let limit = Self::cap_limit(limit);
+        self.watermarks.check_cursor_not_pruned(cursor, None)?;
+        self.check_time_range_not_pruned(start_time).await?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let query = Self::paginated_query(TS_CONDITION, descending);
+        let rows = sqlx::query(&query)
             .persistent(true)
             .bind(start_time as i64)
             .bind(end_time as i64)
-            .bind(object_vec)
+            .bind(tx_seq)
+            .bind(event_seq)
             .bind(limit as i64)
             .map(StoredEvent::from)
             .fetch_all(&self.pool)
@@ -590,6 +1169,146 @@ impl EventStore for SqlEventStore {
             .map_err(convert_sqlx_err)?;
         Ok(rows)
     }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_by_filter(
+        &self,
+        filter: &EventFilter,
+        cursor: Option<EventID>,
+        limit: usize,
+        descending: bool,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let limit = Self::cap_limit(limit);
+        self.watermarks.check_not_pruned(cursor, descending, None)?;
+        let (tx_seq, event_seq) = Self::cursor_bounds(cursor, descending);
+        let (cmp, order) = Self::cursor_operators(descending);
+        let mut builder = QueryBuilder::new("SELECT * FROM events WHERE ");
+        Self::push_filter_condition(&mut builder, filter)?;
+        builder
+            .push(format!(" AND (seq_num, event_num) {cmp} ("))
+            .push_bind(tx_seq)
+            .push(", ")
+            .push_bind(event_seq)
+            .push(format!(
+                ") ORDER BY seq_num {order}, event_num {order} LIMIT "
+            ))
+            .push_bind(limit as i64);
+        // The query shape depends on the filter, so don't fill the prepared statement cache with it
+        let rows = builder
+            .build()
+            .persistent(false)
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn events_in_time_range(
+        &self,
+        filter: &EventFilter,
+        start_time: u64,
+        end_time: u64,
+        limit: usize,
+    ) -> Result<Vec<StoredEvent>, SuiError> {
+        let limit = Self::cap_limit(limit);
+        self.check_time_range_not_pruned(start_time).await?;
+        let mut builder = QueryBuilder::new("SELECT * FROM events WHERE timestamp >= ");
+        builder
+            .push_bind(start_time as i64)
+            .push(" AND timestamp < ")
+            .push_bind(end_time as i64)
+            .push(" AND ");
+        Self::push_filter_condition(&mut builder, filter)?;
+        builder
+            .push(" ORDER BY timestamp ASC, seq_num ASC, event_num ASC LIMIT ")
+            .push_bind(limit as i64);
+        let rows = builder
+            .build()
+            .persistent(false)
+            .map(StoredEvent::from)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+        Ok(rows)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn prune(&self, policy: &RetentionPolicy) -> Result<u64, SuiError> {
+        let mut max_pruned_seq_num = None;
+        if let Some(min_timestamp_ms) = policy.min_timestamp_ms {
+            max_pruned_seq_num = self.max_seq_num_before(min_timestamp_ms, None).await?;
+        }
+        if let Some(max_event_count) = policy.max_event_count {
+            // Sequence number of the newest event beyond the count
+            let row = sqlx::query(
+                "SELECT seq_num FROM events ORDER BY seq_num DESC, event_num DESC LIMIT 1 OFFSET ?",
+            )
+            .bind(max_event_count as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?;
+            if let Some(row) = row {
+                let seq_num: i64 = row.get(0);
+                max_pruned_seq_num = max_pruned_seq_num.max(Some(seq_num as u64));
+            }
+        }
+
+        let mut pruned = 0;
+        if let Some(seq_num) = max_pruned_seq_num {
+            pruned += self.prune_below(seq_num + 1, None).await?;
+        }
+        for (event_type, min_timestamp_ms) in &policy.min_timestamp_ms_by_type {
+            if let Some(seq_num) = self
+                .max_seq_num_before(*min_timestamp_ms, Some(*event_type))
+                .await?
+            {
+                pruned += self.prune_below(seq_num + 1, Some(*event_type)).await?;
+            }
+        }
+        Ok(pruned)
+    }
+
+    #[instrument(level = "debug", skip_all, err)]
+    async fn vacuum(&self) -> Result<(), SuiError> {
+        let auto_vacuum: i64 = sqlx::query("PRAGMA auto_vacuum")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(convert_sqlx_err)?
+            .get(0);
+        if auto_vacuum != AUTO_VACUUM_INCREMENTAL {
+            // Databases created before incremental vacuuming need a full VACUUM to switch to it
+            info!("Vacuuming the whole event store to enable incremental vacuuming");
+            self.pool
+                .execute("VACUUM")
+                .await
+                .map_err(convert_sqlx_err)?;
+            return Ok(());
+        }
+        loop {
+            let free_pages: i64 = sqlx::query("PRAGMA freelist_count")
+                .fetch_one(&self.pool)
+                .await
+                .map_err(convert_sqlx_err)?
+                .get(0);
+            if free_pages == 0 {
+                return Ok(());
+            }
+            self.pool
+                .execute(format!("PRAGMA incremental_vacuum({VACUUM_BATCH_PAGES})").as_str())
+                .await
+                .map_err(convert_sqlx_err)?;
+        }
+    }
+
+    fn oldest_retained_seq_num(&self) -> u64 {
+        self.watermarks.oldest_retained_seq_num(None)
+    }
+
+    fn current_seq_num(&self) -> u64 {
+        self.seq_num.load(Ordering::Acquire)
+    }
 }
 
 fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
@@ -598,604 +1317,164 @@ fn convert_sqlx_err(err: sqlx::Error) -> SuiError {
 
 #[cfg(test)]
 mod tests {
+    use super::store_tests::{self, test_queried_event_vs_test_envelope, TestEventStore};
     use super::test_utils;
     use super::*;
-    use flexstr::shared_str;
-    use move_core_types::{account_address::AccountAddress, identifier::Identifier};
-
-    use sui_types::event::{EventEnvelope, TransferType};
-
-    fn test_queried_event_vs_test_envelope(queried: &StoredEvent, orig: &EventEnvelope) {
-        assert_eq!(queried.timestamp, orig.timestamp);
-        assert_eq!(queried.tx_digest, orig.tx_digest);
-        assert_eq!(queried.event_type, shared_str!(orig.event_type()));
-        assert_eq!(queried.package_id, orig.event.package_id());
-        assert_eq!(
-            queried.module_name,
-            orig.event.module_name().map(SharedStr::from)
-        );
-        assert_eq!(queried.object_id, orig.event.object_id());
-        assert_eq!(queried.sender, orig.event.sender());
-        assert_eq!(queried.recipient.as_ref(), orig.event.recipient());
-        assert_eq!(
-            queried.transfer_type().unwrap().as_ref(),
-            orig.event.transfer_type()
-        );
-        assert_eq!(
-            queried.object_version().unwrap().as_ref(),
-            orig.event.object_version()
-        );
-        assert_eq!(
-            queried.move_event_contents.as_deref(),
-            orig.event.move_event_contents()
-        );
-        assert_eq!(queried.amount().unwrap(), orig.event.amount());
-        let move_event_name = orig.event.move_event_name();
-        assert_eq!(queried.move_event_name.as_ref(), move_event_name.as_ref());
+
+    #[async_trait]
+    impl TestEventStore for SqlEventStore {
+        async fn open_for_testing(path: &Path) -> Result<Self, SuiError> {
+            let db = SqlEventStore::new_from_file(&path.join("events.db")).await?;
+            db.initialize().await?;
+            Ok(db)
+        }
     }
 
     #[tokio::test]
     async fn test_eventstore_basic_insert_read() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
-
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
-
-        // Insert some records
-        info!("Inserting records!");
-        let to_insert = vec![
-            test_utils::new_test_newobj_event(1_000_000, 1, None, None, None),
-            test_utils::new_test_publish_event(1_001_000, 2, None),
-            test_utils::new_test_transfer_event(
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_deleteobj_event(1_003_000, 3, None, None),
-            test_utils::new_test_transfer_event(
-                1_004_000,
-                4,
-                1,
-                TransferType::ToAddress,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_move_event(
-                1_005_000,
-                5,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-        ];
-        assert_eq!(db.add_events(&to_insert).await?, 6);
-        info!("Done inserting");
-
-        assert_eq!(db.total_event_count().await?, 6);
-
-        // Query for records in time range, end should be exclusive - should get 2
-        let queried_events = db.event_iterator(1_000_000, 1_002_000, 20).await?;
-        assert_eq!(queried_events.len(), 2);
-        for i in 0..2 {
-            // ASCENDING order
-            test_queried_event_vs_test_envelope(&queried_events[i], &to_insert[i]);
-        }
-
-        Ok(())
+        store_tests::test_basic_insert_read::<SqlEventStore>().await
     }
 
     #[tokio::test]
     async fn test_eventstore_transfers_tx_read() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
-
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
-
-        // Insert some records
-        info!("Inserting records!");
-        let to_insert = vec![
-            test_utils::new_test_newobj_event(1_000_000, 1, None, None, None),
-            test_utils::new_test_publish_event(1_001_000, 2, None),
-            test_utils::new_test_transfer_event(
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_deleteobj_event(1_003_000, 3, None, None),
-            test_utils::new_test_transfer_event(
-                1_004_000,
-                4,
-                1,
-                TransferType::ToAddress,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_move_event(
-                1_005_000,
-                5,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-        ];
-        db.add_events(&to_insert).await?;
-        let target_event = &to_insert[2];
-        info!("Done inserting");
-
-        // Query for transfer event
-        let mut events = db
-            .events_by_transaction(target_event.tx_digest.unwrap(), 10)
-            .await?;
-        assert_eq!(events.len(), 1); // Should be no more events, just that one
-        let transfer_event = events.pop().unwrap();
-
-        test_queried_event_vs_test_envelope(&transfer_event, target_event);
-
-        assert_eq!(transfer_event.fields.len(), 3); // type, obj ver, amount
-
-        Ok(())
+        store_tests::test_transfers_tx_read::<SqlEventStore>().await
     }
 
-    // Test for reads by event type, plus returning events in desc timestamp and limit
     #[tokio::test]
     async fn test_eventstore_query_by_type() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
-
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
-
-        // Insert some records
-        info!("Inserting records!");
-        let to_insert = vec![
-            test_utils::new_test_newobj_event(1_000_000, 1, None, None, None),
-            test_utils::new_test_publish_event(1_001_000, 2, None),
-            test_utils::new_test_transfer_event(
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_deleteobj_event(1_003_000, 3, None, None),
-            test_utils::new_test_transfer_event(
-                1_004_000,
-                4,
-                1,
-                TransferType::ToAddress,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_move_event(
-                1_005_000,
-                5,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-        ];
-        db.add_events(&to_insert).await?;
-        info!("Done inserting");
-
-        let queried_events = db
-            .events_by_type(1_000_000, 1_005_000, EventType::TransferObject, 2)
-            .await?;
-        assert_eq!(queried_events.len(), 2);
-
-        // Desc timestamp order, so the last transfer event should be first
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
-        test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[4]);
-
-        // Query again with limit of 1, it should return only the last transfer event
-        let queried_events = db
-            .events_by_type(1_000_000, 1_005_000, EventType::TransferObject, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 1);
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
-        assert_eq!(queried_events[0].fields.len(), 3);
-
-        // Query with wrong time range, return 0 events
-        let queried_events = db
-            .events_by_type(1_006_000, 1_009_000, EventType::TransferObject, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 0);
-
-        // Query Publish Event
-        let queried_events = db
-            .events_by_type(1_001_000, 1_002_000, EventType::Publish, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 1);
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[1]);
-        assert_eq!(queried_events[0].fields.len(), 0);
-
-        // Query NewObject Event
-        let queried_events = db
-            .events_by_type(1_000_000, 1_002_000, EventType::NewObject, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 1);
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[0]);
-        assert_eq!(queried_events[0].fields.len(), 0);
-
-        // Query DeleteObject Event
-        let queried_events = db
-            .events_by_type(1_003_000, 1_004_000, EventType::DeleteObject, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 1);
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[3]);
-        assert_eq!(queried_events[0].fields.len(), 0);
+        store_tests::test_query_by_type::<SqlEventStore>().await
+    }
 
-        // Query Move Event
-        let queried_events = db
-            .events_by_type(1_004_000, 1_006_000, EventType::MoveEvent, 1)
-            .await?;
-        assert_eq!(queried_events.len(), 1);
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
-        assert_ne!(queried_events[0].fields.len(), 0);
+    #[tokio::test]
+    async fn test_eventstore_pagination() -> Result<(), SuiError> {
+        store_tests::test_pagination::<SqlEventStore>().await
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_eventstore_time_range_pagination() -> Result<(), SuiError> {
+        store_tests::test_time_range_pagination::<SqlEventStore>().await
     }
 
-    // Test for reads by move event
     #[tokio::test]
     async fn test_eventstore_move_events() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
+        store_tests::test_move_events::<SqlEventStore>().await
+    }
 
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
+    #[tokio::test]
+    async fn test_eventstore_query_by_move_event_struct_name() -> Result<(), SuiError> {
+        store_tests::test_query_by_move_event_struct_name::<SqlEventStore>().await
+    }
 
-        // Insert some records
-        info!("Inserting records!");
-        let to_insert = vec![
-            test_utils::new_test_newobj_event(1_000_000, 1, None, None, None),
-            test_utils::new_test_publish_event(1_001_000, 2, None),
-            test_utils::new_test_transfer_event(
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_deleteobj_event(1_003_000, 3, None, None),
-            test_utils::new_test_transfer_event(
-                1_004_000,
-                4,
-                1,
-                TransferType::ToAddress,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_move_event(
-                1_005_000,
-                5,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-            test_utils::new_test_move_event(
-                1_006_000,
-                6,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-        ];
-        db.add_events(&to_insert).await?;
-        info!("Done inserting");
+    #[tokio::test]
+    async fn test_eventstore_query_by_sender_recipient_and_object() -> Result<(), SuiError> {
+        store_tests::test_query_by_sender_recipient_and_object::<SqlEventStore>().await
+    }
 
-        // Query for the Move event and validate basic fields
-        let events = db
-            .events_by_transaction(to_insert[5].tx_digest.unwrap(), 10)
-            .await?;
-        let move_event = &events[0];
-        assert_eq!(events.len(), 1); // Should be no more events, just that one
+    #[tokio::test]
+    async fn test_eventstore_query_by_filter() -> Result<(), SuiError> {
+        store_tests::test_query_by_filter::<SqlEventStore>().await
+    }
 
-        test_queried_event_vs_test_envelope(move_event, &to_insert[5]);
-        assert_eq!(move_event.fields.len(), 2);
+    #[tokio::test]
+    async fn test_eventstore_query_by_move_event_field() -> Result<(), SuiError> {
+        store_tests::test_query_by_move_event_field::<SqlEventStore>().await
+    }
 
-        // Query by module ID
-        let mod_id = ModuleId::new(
-            AccountAddress::from(ObjectID::from_hex_literal("0x3").unwrap()),
-            Identifier::from_str("test_module").unwrap(),
-        );
-        let queried_events = db
-            .events_by_module_id(1_000_000, 1_006_001, &mod_id, 3)
-            .await?;
-        assert_eq!(queried_events.len(), 2);
+    #[tokio::test]
+    async fn test_eventstore_prune() -> Result<(), SuiError> {
+        store_tests::test_prune::<SqlEventStore>().await
+    }
 
-        // results are sorted in DESC order
-        test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
-        test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[6]);
-        assert_eq!(queried_events[0].fields.len(), 2);
-        assert_eq!(queried_events[1].fields.len(), 2);
+    #[tokio::test]
+    async fn test_eventstore_u64_conversion() -> Result<(), SuiError> {
+        store_tests::test_u64_conversion::<SqlEventStore>().await
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_eventstore_seq_num() -> Result<(), SuiError> {
+        store_tests::test_seq_num::<SqlEventStore>().await
     }
 
+    // Fields of events stored before the event_fields table existed are indexed at startup
     #[tokio::test]
-    async fn test_eventstore_query_by_move_event_struct_name() -> Result<(), SuiError> {
+    async fn test_eventstore_backfill_event_fields() -> Result<(), SuiError> {
         telemetry_subscribers::init_for_testing();
 
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = SqlEventStore::open_for_testing(dir.path()).await?;
 
-        // Insert some records
-        info!("Inserting records!");
+        let package = ObjectID::from_hex_literal("0x42").unwrap();
         let to_insert = vec![
-            test_utils::new_test_move_event(
-                1_000_000,
-                1,
-                ObjectID::from_hex_literal("0x42").unwrap(),
-                "query_by_move_event_struct_name",
-                "test_foo",
-            ),
-            test_utils::new_test_move_event(
-                1_001_000,
-                2,
-                ObjectID::from_hex_literal("0x42").unwrap(),
-                "query_by_move_event_struct_name",
-                "test_foo",
-            ),
-            test_utils::new_test_move_event(
-                1_002_000,
-                3,
-                ObjectID::from_hex_literal("0x42").unwrap(),
-                "query_by_move_event_struct_name",
-                "test_bar",
-            ),
+            test_utils::new_test_move_event(1_000_000, 1, 0, package, "module_a", "test_foo"),
+            test_utils::new_test_move_event(1_001_000, 2, 0, package, "module_a", "test_bar"),
         ];
-
-        assert_eq!(db.add_events(&to_insert).await?, 3);
-        info!("Done inserting");
-
+        assert_eq!(db.add_events(&to_insert).await?, 2);
+        let foo_name = to_insert[0].event.move_event_name().unwrap();
+        let creator = to_insert[0]
+            .move_struct_json_value
+            .as_ref()
+            .and_then(|json| json.pointer("/creator"))
+            .unwrap()
+            .clone();
+
+        db.pool
+            .execute("DROP TABLE event_fields")
+            .await
+            .map_err(convert_sqlx_err)?;
+        drop(db);
+        let db = SqlEventStore::open_for_testing(dir.path()).await?;
         let events = db
-            .events_by_move_event_struct_name(
-                1_000_000,
-                1_002_000,
-                "0x2::SUI::test_foo<address, vector<u8>>",
-                10,
-            )
+            .events_by_move_event_field(&foo_name, "/creator", &creator, None, 10, false)
             .await?;
-        assert_eq!(events.len(), 2);
-
+        assert_eq!(events.len(), 1);
         test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
-        test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
-        assert_eq!(events[0].fields.len(), 2);
-        assert_eq!(events[1].fields.len(), 2);
 
         Ok(())
     }
 
+    // Event stores created before events were numbered are migrated at startup
     #[tokio::test]
-    async fn test_eventstore_query_by_sender_recipient_and_object() -> Result<(), SuiError> {
+    async fn test_eventstore_migrate_event_num() -> Result<(), SuiError> {
         telemetry_subscribers::init_for_testing();
 
-        // Initialize store
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
+        let dir = tempfile::TempDir::new().unwrap();
+        let db = SqlEventStore::open_for_testing(dir.path()).await?;
 
-        // Insert some records
-        info!("Inserting records!");
-        let sender = SuiAddress::random_for_testing_only();
-        let recipient = Owner::AddressOwner(SuiAddress::random_for_testing_only());
-        let object_id = ObjectID::random();
+        let package = ObjectID::from_hex_literal("0x42").unwrap();
         let to_insert = vec![
-            test_utils::new_test_transfer_event(
-                // 0, object, sender, recipient
-                1_000_000,
-                1,
-                1,
-                TransferType::Coin,
-                Some(object_id),
-                Some(sender),
-                Some(recipient),
-            ),
-            test_utils::new_test_newobj_event(
-                // 1, object, sender
-                1_001_000,
-                2,
-                Some(object_id),
-                Some(sender),
-                None,
-            ),
-            test_utils::new_test_transfer_event(
-                // 2, recipient
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                Some(recipient),
-            ),
-            test_utils::new_test_newobj_event(
-                // 3, object, recipient
-                1_003_000,
-                4,
-                Some(object_id),
-                None,
-                Some(recipient),
-            ),
-            test_utils::new_test_deleteobj_event(
-                // 4, object, sender
-                1_004_000,
-                5,
-                Some(object_id),
-                Some(sender),
-            ),
-            test_utils::new_test_deleteobj_event(
-                // 5, sender
-                1_005_000,
-                6,
-                None,
-                Some(sender),
-            ),
-            test_utils::new_test_publish_event(
-                // 6, None
-                1_006_000, 7, None,
-            ),
-            test_utils::new_test_publish_event(
-                // 7, sender
-                1_007_000,
-                8,
-                Some(sender),
-            ),
+            test_utils::new_test_move_event(1_000_000, 1, 0, package, "module_a", "test_foo"),
+            test_utils::new_test_move_event(1_000_000, 1, 1, package, "module_a", "test_bar"),
+            test_utils::new_test_move_event(1_001_000, 2, 0, package, "module_a", "test_foo"),
         ];
+        assert_eq!(db.add_events(&to_insert).await?, 3);
 
-        assert_eq!(db.add_events(&to_insert).await?, 8);
-        info!("Done inserting");
-
-        // Query by sender
-        let events = db
-            .events_by_sender(1_000_000, 1_990_000, &sender, 10)
-            .await?;
-        assert_eq!(events.len(), 5);
-
-        test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
-        test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
-        test_queried_event_vs_test_envelope(&events[2], &to_insert[4]);
-        test_queried_event_vs_test_envelope(&events[3], &to_insert[5]);
-        test_queried_event_vs_test_envelope(&events[4], &to_insert[7]);
+        // The schema before events were numbered
+        for statement in [
+            "DROP INDEX event_id_idx",
+            "DROP TABLE event_fields",
+            "ALTER TABLE events DROP COLUMN event_num",
+        ] {
+            db.pool.execute(statement).await.map_err(convert_sqlx_err)?;
+        }
+        drop(db);
 
-        // Query by recipient
-        let events = db
-            .events_by_recipient(1_000_000, 1_990_000, &recipient, 10)
-            .await?;
+        let db = SqlEventStore::open_for_testing(dir.path()).await?;
+        let events = db.all_events(None, 10, false).await?;
         assert_eq!(events.len(), 3);
+        for (event, inserted) in events.iter().zip(&to_insert) {
+            test_queried_event_vs_test_envelope(event, inserted);
+        }
 
-        test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
-        test_queried_event_vs_test_envelope(&events[1], &to_insert[2]);
-        test_queried_event_vs_test_envelope(&events[2], &to_insert[3]);
-
-        // Query by object
-        let events = db
-            .events_by_object(1_000_000, 1_990_000, &object_id, 10)
-            .await?;
-        assert_eq!(events.len(), 4);
-
-        test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
-        test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
-        test_queried_event_vs_test_envelope(&events[2], &to_insert[3]);
-        test_queried_event_vs_test_envelope(&events[3], &to_insert[4]);
-
-        Ok(())
-    }
-
-    // Test we can retrieve u64 object version (aka sequence number) values
-    // stored as string in sqlite
-    #[tokio::test]
-    async fn test_eventstore_u64_conversion() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
-
-        let db = SqlEventStore::new_memory_only_not_prod().await?;
-        db.initialize().await?;
-
-        let to_insert = vec![test_utils::new_test_transfer_event(
-            1_000_000,
-            1,
-            u64::MAX,
-            TransferType::Coin,
-            None,
-            None,
-            None,
-        )];
-        db.add_events(&to_insert).await?;
-
+        // New events can be stored, and the fields of the old ones were indexed
+        let new_event =
+            test_utils::new_test_move_event(1_002_000, 3, 0, package, "module_a", "test_foo");
+        assert_eq!(db.add_events(&[new_event]).await?, 1);
+        let foo_name = to_insert[0].event.move_event_name().unwrap();
+        let name = Value::String("foobar_buz".to_string());
         let events = db
-            .events_by_transaction(to_insert[0].tx_digest.unwrap(), 10)
+            .events_by_move_event_field(&foo_name, "/name", &name, None, 10, false)
             .await?;
-        assert_eq!(events.len(), 1);
-        info!("events[0]: {:?}", events[0]);
-        assert_eq!(
-            events[0].object_version().unwrap().unwrap().value(),
-            u64::MAX
-        );
-        Ok(())
-    }
-
-    // Test Idempotency / Sequence Numbering
-    #[tokio::test]
-    async fn test_eventstore_seq_num() -> Result<(), SuiError> {
-        telemetry_subscribers::init_for_testing();
-
-        // Initialize store
-        let dir = tempfile::TempDir::new().unwrap(); // NOTE this must be its own line so dir isn't dropped
-        let db_file = dir.path().join("events.db");
-        let db = SqlEventStore::new_from_file(&db_file).await?;
-        db.initialize().await?;
-
-        // TODO: these 30 lines are quite duplicated in this file (4 times).
-        // Write in some events, all should succeed
-        let to_insert = vec![
-            test_utils::new_test_newobj_event(1_000_000, 1, None, None, None),
-            test_utils::new_test_publish_event(1_001_000, 2, None),
-            test_utils::new_test_transfer_event(
-                1_002_000,
-                3,
-                1,
-                TransferType::Coin,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_deleteobj_event(1_003_000, 3, None, None),
-            test_utils::new_test_transfer_event(
-                1_004_000,
-                4,
-                1,
-                TransferType::ToAddress,
-                None,
-                None,
-                None,
-            ),
-            test_utils::new_test_move_event(
-                1_005_000,
-                5,
-                ObjectID::from_hex_literal("0x3").unwrap(),
-                "test_module",
-                "test_foo",
-            ),
-        ];
-        assert_eq!(db.add_events(&to_insert[..4]).await?, 4);
-        assert_eq!(db.total_event_count().await?, 4);
-
-        // Write in an older event with older sequence number, should be skipped
-        assert_eq!(db.add_events(&to_insert[1..2]).await?, 0);
-        assert_eq!(db.total_event_count().await?, 4);
-
-        // Drop and reload DB from the same file, test that sequence number was recovered
-        drop(db);
-        let db = SqlEventStore::new_from_file(&db_file).await?;
-        db.initialize().await?;
-        assert_eq!(db.last_seq_num().await?, 3);
-        assert_eq!(db.total_event_count().await?, 4);
-
-        // Try ingesting older event, check still skipped
-        assert_eq!(db.add_events(&to_insert[1..2]).await?, 0);
-        assert_eq!(db.total_event_count().await?, 4);
-
-        // Check writing new events still succeeds
-        assert_eq!(db.add_events(&to_insert[4..]).await?, 2);
-        assert_eq!(db.total_event_count().await?, 6);
+        assert_eq!(events.len(), 3);
 
         Ok(())
     }
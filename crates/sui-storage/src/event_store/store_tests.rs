@@ -0,0 +1,1190 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tests shared by all event store implementations. Each store runs them from its own test module
+//! by implementing `TestEventStore`.
+
+use super::test_utils;
+use super::*;
+use async_trait::async_trait;
+use flexstr::shared_str;
+use move_core_types::account_address::AccountAddress;
+use std::path::Path;
+use sui_types::filter::Filter;
+use tracing::info;
+
+#[async_trait]
+pub trait TestEventStore: EventStore + Send + Sync + Sized {
+    /// Opens a store persisted in the directory `path`, creating it if needed.
+    async fn open_for_testing(path: &Path) -> Result<Self, SuiError>;
+}
+
+async fn event_count(db: &impl EventStore) -> Result<usize, SuiError> {
+    // Descending, as ascending queries without a cursor fail once events are pruned
+    Ok(db
+        .all_events(None, EVENT_STORE_QUERY_MAX_LIMIT, true)
+        .await?
+        .len())
+}
+
+pub fn test_queried_event_vs_test_envelope(queried: &StoredEvent, orig: &EventEnvelope) {
+    assert_eq!(queried.id, orig.id());
+    assert_eq!(queried.timestamp, orig.timestamp);
+    assert_eq!(queried.tx_digest, orig.tx_digest);
+    assert_eq!(queried.event_type, shared_str!(orig.event_type()));
+    assert_eq!(queried.package_id, orig.event.package_id());
+    assert_eq!(
+        queried.module_name,
+        orig.event.module_name().map(SharedStr::from)
+    );
+    assert_eq!(queried.object_id, orig.event.object_id());
+    assert_eq!(queried.sender, orig.event.sender());
+    assert_eq!(queried.recipient.as_ref(), orig.event.recipient());
+    assert_eq!(
+        queried.transfer_type().unwrap().as_ref(),
+        orig.event.transfer_type()
+    );
+    assert_eq!(
+        queried.object_version().unwrap().as_ref(),
+        orig.event.object_version()
+    );
+    assert_eq!(
+        queried.move_event_contents.as_deref(),
+        orig.event.move_event_contents()
+    );
+    assert_eq!(queried.amount().unwrap(), orig.event.amount());
+    let move_event_name = orig.event.move_event_name();
+    assert_eq!(queried.move_event_name.as_ref(), move_event_name.as_ref());
+}
+
+pub async fn test_basic_insert_read<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 6);
+    info!("Done inserting");
+
+    assert_eq!(event_count(&db).await?, 6);
+
+    // Query for records in time range, end should be exclusive - should get 2
+    let queried_events = db
+        .event_iterator(1_000_000, 1_002_000, None, 20, false)
+        .await?;
+    assert_eq!(queried_events.len(), 2);
+    for i in 0..2 {
+        // ASCENDING order
+        test_queried_event_vs_test_envelope(&queried_events[i], &to_insert[i]);
+    }
+
+    Ok(())
+}
+
+pub async fn test_transfers_tx_read<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    db.add_events(&to_insert).await?;
+    let target_event = &to_insert[2];
+    info!("Done inserting");
+
+    // Query for transfer event
+    let mut events = db
+        .events_by_transaction(target_event.tx_digest.unwrap(), None, 10, false)
+        .await?;
+    assert_eq!(events.len(), 1); // Should be no more events, just that one
+    let transfer_event = events.pop().unwrap();
+
+    test_queried_event_vs_test_envelope(&transfer_event, target_event);
+
+    assert_eq!(transfer_event.fields.len(), 3); // type, obj ver, amount
+
+    Ok(())
+}
+
+// Test for reads by event type, plus returning events in desc timestamp and limit
+pub async fn test_query_by_type<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    db.add_events(&to_insert).await?;
+    info!("Done inserting");
+
+    let queried_events = db
+        .events_by_type(EventType::TransferObject, None, 2, false)
+        .await?;
+    assert_eq!(queried_events.len(), 2);
+
+    // Ascending order, so the first transfer event should be first
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
+    test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[4]);
+
+    // Query again with limit of 1, it should return only the first transfer event
+    let queried_events = db
+        .events_by_type(EventType::TransferObject, None, 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[2]);
+    assert_eq!(queried_events[0].fields.len(), 3);
+
+    // Query in descending order, the last transfer event should be first
+    let queried_events = db
+        .events_by_type(EventType::TransferObject, None, 1, true)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[4]);
+
+    // Query with a cursor past the last transfer event, return 0 events
+    let queried_events = db
+        .events_by_type(EventType::TransferObject, Some((5, 0).into()), 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 0);
+
+    // Query Publish Event
+    let queried_events = db
+        .events_by_type(EventType::Publish, None, 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[1]);
+    assert_eq!(queried_events[0].fields.len(), 0);
+
+    // Query NewObject Event
+    let queried_events = db
+        .events_by_type(EventType::NewObject, None, 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[0]);
+    assert_eq!(queried_events[0].fields.len(), 0);
+
+    // Query DeleteObject Event
+    let queried_events = db
+        .events_by_type(EventType::DeleteObject, None, 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[3]);
+    assert_eq!(queried_events[0].fields.len(), 0);
+
+    // Query Move Event
+    let queried_events = db
+        .events_by_type(EventType::MoveEvent, None, 1, false)
+        .await?;
+    assert_eq!(queried_events.len(), 1);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+    assert_ne!(queried_events[0].fields.len(), 0);
+
+    Ok(())
+}
+
+// Test paging through events with cursors, in both directions
+pub async fn test_pagination<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_newobj_event(1_002_000, 3, 0, None, None, None),
+        test_utils::new_test_deleteobj_event(1_002_000, 3, 1, None, None),
+        test_utils::new_test_newobj_event(1_003_000, 4, 0, None, None, None),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 5);
+
+    // Page forward two events at a time; the first event of the next page is the cursor
+    let page = db.all_events(None, 2, false).await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[1]);
+
+    let page = db.all_events(Some((3, 0).into()), 2, false).await?;
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].id(), to_insert[2].id());
+    assert_eq!(page[1].id(), to_insert[3].id());
+
+    // Cursor in the middle of a transaction's events
+    let page = db.all_events(Some((3, 1).into()), 10, false).await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[3]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[4]);
+
+    // New events do not shift existing pages
+    db.add_events(&[test_utils::new_test_publish_event(1_004_000, 5, 0, None)])
+        .await?;
+    let page = db.all_events(Some((3, 0).into()), 2, false).await?;
+    assert_eq!(page[0].id(), to_insert[2].id());
+    assert_eq!(page[1].id(), to_insert[3].id());
+
+    // Page backward
+    let page = db.all_events(None, 2, true).await?;
+    assert_eq!(page.len(), 2);
+    assert_eq!(page[0].id(), EventID::from((5, 0)));
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[4]);
+
+    let page = db.all_events(Some((3, 1).into()), 10, true).await?;
+    assert_eq!(page.len(), 4);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[3]);
+    test_queried_event_vs_test_envelope(&page[3], &to_insert[0]);
+
+    Ok(())
+}
+
+pub async fn test_time_range_pagination<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_newobj_event(1_002_000, 3, 0, None, None, None),
+        test_utils::new_test_deleteobj_event(1_002_000, 3, 1, None, None),
+        test_utils::new_test_newobj_event(1_003_000, 4, 0, None, None, None),
+        test_utils::new_test_publish_event(1_004_000, 5, 0, None),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 6);
+
+    // Page forward through [1_001_000, 1_004_000), the limit ending the page early
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, None, 2, false)
+        .await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[2]);
+
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, Some((3, 1).into()), 2, false)
+        .await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[3]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[4]);
+
+    // Page backward through the same range
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, None, 2, true)
+        .await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[4]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[3]);
+
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, Some((3, 0).into()), 10, true)
+        .await?;
+    assert_eq!(page.len(), 2);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[2]);
+    test_queried_event_vs_test_envelope(&page[1], &to_insert[1]);
+
+    // A cursor outside of the range does not extend it
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, Some((1, 0).into()), 10, false)
+        .await?;
+    assert_eq!(page.len(), 4);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[1]);
+    let page = db
+        .event_iterator(1_001_000, 1_004_000, Some((5, 0).into()), 10, true)
+        .await?;
+    assert_eq!(page.len(), 4);
+    test_queried_event_vs_test_envelope(&page[0], &to_insert[4]);
+
+    Ok(())
+}
+
+// Test for reads by move event
+pub async fn test_move_events<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+        test_utils::new_test_move_event(
+            1_006_000,
+            6,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    db.add_events(&to_insert).await?;
+    info!("Done inserting");
+
+    // Query for the Move event and validate basic fields
+    let events = db
+        .events_by_transaction(to_insert[5].tx_digest.unwrap(), None, 10, false)
+        .await?;
+    let move_event = &events[0];
+    assert_eq!(events.len(), 1); // Should be no more events, just that one
+
+    test_queried_event_vs_test_envelope(move_event, &to_insert[5]);
+    assert_eq!(move_event.fields.len(), 2);
+
+    // Query by module ID
+    let mod_id = ModuleId::new(
+        AccountAddress::from(ObjectID::from_hex_literal("0x3").unwrap()),
+        Identifier::from_str("test_module").unwrap(),
+    );
+    let queried_events = db.events_by_module_id(&mod_id, None, 3, false).await?;
+    assert_eq!(queried_events.len(), 2);
+
+    // results are sorted in ASC order
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[5]);
+    test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[6]);
+    assert_eq!(queried_events[0].fields.len(), 2);
+    assert_eq!(queried_events[1].fields.len(), 2);
+
+    // and in DESC order when asked for
+    let queried_events = db.events_by_module_id(&mod_id, None, 3, true).await?;
+    assert_eq!(queried_events.len(), 2);
+    test_queried_event_vs_test_envelope(&queried_events[0], &to_insert[6]);
+    test_queried_event_vs_test_envelope(&queried_events[1], &to_insert[5]);
+
+    Ok(())
+}
+
+pub async fn test_query_by_move_event_struct_name<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let to_insert = vec![
+        test_utils::new_test_move_event(
+            1_000_000,
+            1,
+            0,
+            ObjectID::from_hex_literal("0x42").unwrap(),
+            "query_by_move_event_struct_name",
+            "test_foo",
+        ),
+        test_utils::new_test_move_event(
+            1_001_000,
+            2,
+            0,
+            ObjectID::from_hex_literal("0x42").unwrap(),
+            "query_by_move_event_struct_name",
+            "test_foo",
+        ),
+        test_utils::new_test_move_event(
+            1_002_000,
+            3,
+            0,
+            ObjectID::from_hex_literal("0x42").unwrap(),
+            "query_by_move_event_struct_name",
+            "test_bar",
+        ),
+    ];
+
+    assert_eq!(db.add_events(&to_insert).await?, 3);
+    info!("Done inserting");
+
+    let events = db
+        .events_by_move_event_struct_name(
+            "0x2::SUI::test_foo<address, vector<u8>>",
+            None,
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events.len(), 2);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
+    assert_eq!(events[0].fields.len(), 2);
+    assert_eq!(events[1].fields.len(), 2);
+
+    Ok(())
+}
+
+pub async fn test_query_by_sender_recipient_and_object<S: TestEventStore>() -> Result<(), SuiError>
+{
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let sender = SuiAddress::random_for_testing_only();
+    let recipient = Owner::AddressOwner(SuiAddress::random_for_testing_only());
+    let object_id = ObjectID::random();
+    let to_insert = vec![
+        test_utils::new_test_transfer_event(
+            // 0, object, sender, recipient
+            1_000_000,
+            1,
+            0,
+            1,
+            TransferType::Coin,
+            Some(object_id),
+            Some(sender),
+            Some(recipient),
+        ),
+        test_utils::new_test_newobj_event(
+            // 1, object, sender
+            1_001_000,
+            2,
+            0,
+            Some(object_id),
+            Some(sender),
+            None,
+        ),
+        test_utils::new_test_transfer_event(
+            // 2, recipient
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            Some(recipient),
+        ),
+        test_utils::new_test_newobj_event(
+            // 3, object, recipient
+            1_003_000,
+            4,
+            0,
+            Some(object_id),
+            None,
+            Some(recipient),
+        ),
+        test_utils::new_test_deleteobj_event(
+            // 4, object, sender
+            1_004_000,
+            5,
+            0,
+            Some(object_id),
+            Some(sender),
+        ),
+        test_utils::new_test_deleteobj_event(
+            // 5, sender
+            1_005_000,
+            6,
+            0,
+            None,
+            Some(sender),
+        ),
+        test_utils::new_test_publish_event(
+            // 6, None
+            1_006_000, 7, 0, None,
+        ),
+        test_utils::new_test_publish_event(
+            // 7, sender
+            1_007_000,
+            8,
+            0,
+            Some(sender),
+        ),
+    ];
+
+    assert_eq!(db.add_events(&to_insert).await?, 8);
+    info!("Done inserting");
+
+    // Query by sender
+    let events = db.events_by_sender(&sender, None, 10, false).await?;
+    assert_eq!(events.len(), 5);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&events[2], &to_insert[4]);
+    test_queried_event_vs_test_envelope(&events[3], &to_insert[5]);
+    test_queried_event_vs_test_envelope(&events[4], &to_insert[7]);
+
+    // Query by recipient
+    let events = db.events_by_recipient(&recipient, None, 10, false).await?;
+    assert_eq!(events.len(), 3);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[2]);
+    test_queried_event_vs_test_envelope(&events[2], &to_insert[3]);
+
+    // Query by object
+    let events = db.events_by_object(&object_id, None, 10, false).await?;
+    assert_eq!(events.len(), 4);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&events[2], &to_insert[3]);
+    test_queried_event_vs_test_envelope(&events[3], &to_insert[4]);
+
+    // Query by sender within a time range, in ascending time
+    let filter = EventFilter::SenderAddress(sender);
+    let events = db
+        .events_in_time_range(&filter, 1_001_000, 1_007_000, 10)
+        .await?;
+    assert_eq!(events.len(), 3);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[4]);
+    test_queried_event_vs_test_envelope(&events[2], &to_insert[5]);
+
+    let events = db
+        .events_in_time_range(&filter, 1_001_000, 1_007_000, 2)
+        .await?;
+    assert_eq!(events.len(), 2);
+
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[4]);
+
+    // Query all events within a time range without events
+    let events = db
+        .events_in_time_range(&EventFilter::MatchAll(vec![]), 1_007_001, 1_008_000, 10)
+        .await?;
+    assert!(events.is_empty());
+
+    Ok(())
+}
+
+pub async fn test_query_by_filter<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // Insert some records
+    info!("Inserting records!");
+    let sender = SuiAddress::random_for_testing_only();
+    let package_a = ObjectID::from_hex_literal("0x42").unwrap();
+    let package_b = ObjectID::from_hex_literal("0x43").unwrap();
+    let to_insert = vec![
+        test_utils::new_test_move_event(1_000_000, 1, 0, package_a, "module_a", "test_foo"),
+        test_utils::new_test_move_event(1_001_000, 1, 1, package_a, "module_a", "test_bar"),
+        test_utils::new_test_move_event(1_002_000, 2, 0, package_b, "module_b", "test_foo"),
+        test_utils::new_test_transfer_event(
+            1_003_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            Some(sender),
+            None,
+        ),
+        test_utils::new_test_newobj_event(1_004_000, 4, 0, None, Some(sender), None),
+        test_utils::new_test_newobj_event(1_005_000, 5, 0, None, None, None),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 6);
+    info!("Done inserting");
+
+    let foo_type = match &to_insert[0].event {
+        Event::MoveEvent { type_, .. } => type_.clone(),
+        _ => unreachable!(),
+    };
+    let bar_type = match &to_insert[1].event {
+        Event::MoveEvent { type_, .. } => type_.clone(),
+        _ => unreachable!(),
+    };
+    let name = to_insert[2]
+        .move_struct_json_value
+        .as_ref()
+        .and_then(|json| json.pointer("/name/0"))
+        .unwrap()
+        .clone();
+    let name_array = to_insert[2]
+        .move_struct_json_value
+        .as_ref()
+        .and_then(|json| json.pointer("/name"))
+        .unwrap()
+        .clone();
+
+    // Each filter returns the events it matches in memory, plus how many we expect
+    let filters = vec![
+        (
+            EventFilter::MatchAll(vec![
+                EventFilter::Package(package_a),
+                EventFilter::MoveEventType(foo_type.clone()),
+            ]),
+            1,
+        ),
+        (
+            EventFilter::MatchAll(vec![
+                EventFilter::Package(package_a),
+                EventFilter::Module(Identifier::new("module_a").unwrap()),
+            ]),
+            2,
+        ),
+        (
+            EventFilter::MatchAny(vec![
+                EventFilter::MoveEventType(bar_type),
+                EventFilter::Module(Identifier::new("module_b").unwrap()),
+            ]),
+            2,
+        ),
+        (
+            EventFilter::SenderAddress(sender).and(EventFilter::EventType(EventType::NewObject)),
+            1,
+        ),
+        (
+            EventFilter::MatchAll(vec![
+                EventFilter::MoveEventType(foo_type.clone()),
+                EventFilter::MatchAny(vec![
+                    EventFilter::Package(package_b),
+                    EventFilter::EventType(EventType::TransferObject),
+                ]),
+            ]),
+            1,
+        ),
+        (
+            EventFilter::MoveEventField {
+                path: "/name/0".to_string(),
+                value: name.clone(),
+            },
+            3,
+        ),
+        (
+            EventFilter::MatchAll(vec![
+                EventFilter::MoveEventType(foo_type),
+                EventFilter::MoveEventField {
+                    path: "/name/0".to_string(),
+                    value: name,
+                },
+            ]),
+            2,
+        ),
+        (
+            EventFilter::MoveEventField {
+                path: "/name".to_string(),
+                value: name_array,
+            },
+            3,
+        ),
+        (EventFilter::MatchAll(vec![]), 6),
+        (EventFilter::MatchAny(vec![]), 0),
+    ];
+    for (filter, expected_len) in filters {
+        let expected: Vec<_> = to_insert.iter().filter(|e| filter.matches(e)).collect();
+        assert_eq!(expected.len(), expected_len, "{:?}", filter);
+
+        let events = db.events_by_filter(&filter, None, 10, false).await?;
+        assert_eq!(events.len(), expected_len, "{:?}", filter);
+        for (queried, orig) in events.iter().zip(expected.iter()) {
+            test_queried_event_vs_test_envelope(queried, orig);
+        }
+
+        let events = db.events_by_filter(&filter, None, 10, true).await?;
+        assert_eq!(events.len(), expected_len, "{:?}", filter);
+        for (queried, orig) in events.iter().zip(expected.iter().rev()) {
+            test_queried_event_vs_test_envelope(queried, orig);
+        }
+    }
+
+    // Filters can be paged through with a cursor too
+    let filter = EventFilter::MatchAny(vec![
+        EventFilter::Package(package_a),
+        EventFilter::SenderAddress(sender),
+    ]);
+    let events = db.events_by_filter(&filter, None, 2, false).await?;
+    assert_eq!(events.len(), 2);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[1]);
+    let events = db
+        .events_by_filter(&filter, Some(to_insert[3].id()), 2, false)
+        .await?;
+    assert_eq!(events.len(), 2);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[3]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[4]);
+
+    // Malformed JSON pointers are rejected
+    let filter = EventFilter::MoveEventField {
+        path: "name".to_string(),
+        value: json!("foo"),
+    };
+    assert!(matches!(
+        db.events_by_filter(&filter, None, 10, false).await,
+        Err(SuiError::InvalidEventFilter(_))
+    ));
+
+    Ok(())
+}
+
+pub async fn test_query_by_move_event_field<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    let package = ObjectID::from_hex_literal("0x42").unwrap();
+    let to_insert = vec![
+        test_utils::new_test_move_event(1_000_000, 1, 0, package, "module_a", "test_foo"),
+        test_utils::new_test_move_event(1_001_000, 2, 0, package, "module_a", "test_bar"),
+        test_utils::new_test_move_event(1_002_000, 3, 0, package, "module_a", "test_foo"),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 3);
+
+    let foo_name = to_insert[0].event.move_event_name().unwrap();
+    let field = |event: &EventEnvelope, path: &str| {
+        event
+            .move_struct_json_value
+            .as_ref()
+            .and_then(|json| json.pointer(path))
+            .unwrap()
+            .clone()
+    };
+
+    // Fields are matched within the given event type only
+    let creator = field(&to_insert[0], "/creator");
+    let events = db
+        .events_by_move_event_field(&foo_name, "/creator", &creator, None, 10, false)
+        .await?;
+    assert_eq!(events.len(), 1);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+    let bar_name = to_insert[1].event.move_event_name().unwrap();
+    let events = db
+        .events_by_move_event_field(&bar_name, "/creator", &creator, None, 10, false)
+        .await?;
+    assert!(events.is_empty());
+
+    // Nested fields are flattened, and results are paginated by event ID
+    let name_char = field(&to_insert[0], "/name/0");
+    let events = db
+        .events_by_move_event_field(&foo_name, "/name/0", &name_char, None, 10, true)
+        .await?;
+    assert_eq!(events.len(), 2);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[2]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[0]);
+    let events = db
+        .events_by_move_event_field(
+            &foo_name,
+            "/name/0",
+            &name_char,
+            Some(to_insert[0].id()),
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events.len(), 2);
+
+    // Fields stay indexed after a restart
+    drop(db);
+    let db = S::open_for_testing(dir.path()).await?;
+    let events = db
+        .events_by_move_event_field(&foo_name, "/creator", &creator, None, 10, false)
+        .await?;
+    assert_eq!(events.len(), 1);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[0]);
+
+    assert!(matches!(
+        db.events_by_move_event_field(&foo_name, "creator", &creator, None, 10, false)
+            .await,
+        Err(SuiError::InvalidEventFilter(_))
+    ));
+
+    Ok(())
+}
+
+pub async fn test_prune<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    assert_eq!(db.add_events(&to_insert).await?, 6);
+    assert_eq!(db.oldest_retained_seq_num(), 0);
+
+    // Pruning one event type only fails queries of that type below the pruned events
+    let policy = RetentionPolicy {
+        min_timestamp_ms_by_type: vec![(EventType::TransferObject, 1_003_000)],
+        ..Default::default()
+    };
+    assert_eq!(db.prune(&policy).await?, 1);
+    assert_eq!(event_count(&db).await?, 5);
+    assert!(matches!(
+        db.events_by_type(
+            EventType::TransferObject,
+            Some(to_insert[2].id()),
+            10,
+            false
+        )
+        .await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 3,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    assert!(matches!(
+        db.events_by_type(EventType::TransferObject, None, 10, false)
+            .await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 0,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    let events = db
+        .events_by_type(
+            EventType::TransferObject,
+            Some(to_insert[4].id()),
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events.len(), 1);
+    let events = db
+        .events_by_type(EventType::NewObject, Some(to_insert[0].id()), 10, false)
+        .await?;
+    assert_eq!(events.len(), 1);
+    // Queries of all types skip the pruned events of that type
+    let events = db.all_events(Some(to_insert[1].id()), 10, false).await?;
+    assert_eq!(events.len(), 4);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[1]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[3]);
+    assert_eq!(db.oldest_retained_seq_num(), 4);
+
+    // Only the newest events are kept, whole transactions are pruned at a time
+    let policy = RetentionPolicy {
+        max_event_count: Some(2),
+        ..Default::default()
+    };
+    assert_eq!(db.prune(&policy).await?, 3);
+    assert_eq!(event_count(&db).await?, 2);
+    // Ascending queries without a cursor and time ranges reaching the pruned events fail
+    assert!(matches!(
+        db.all_events(None, 10, false).await,
+        Err(SuiError::EventsPruned {
+            cursor_seq_num: 0,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    assert!(matches!(
+        db.event_iterator(1_000_000, 1_010_000, None, 10, true)
+            .await,
+        Err(SuiError::EventTimeRangePruned {
+            start_time: 1_000_000,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    assert!(matches!(
+        db.events_in_time_range(&EventFilter::MatchAll(vec![]), 1_003_500, 1_010_000, 10)
+            .await,
+        Err(SuiError::EventTimeRangePruned {
+            start_time: 1_003_500,
+            oldest_retained_seq_num: 4
+        })
+    ));
+    // Time ranges from the oldest event left are complete
+    let events = db
+        .event_iterator(1_004_000, 1_010_000, None, 10, false)
+        .await?;
+    assert_eq!(events.len(), 2);
+    let events = db
+        .events_in_time_range(&EventFilter::MatchAll(vec![]), 1_004_000, 1_010_000, 10)
+        .await?;
+    assert_eq!(events.len(), 2);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[4]);
+    test_queried_event_vs_test_envelope(&events[1], &to_insert[5]);
+    // Already pruned events are not pruned again
+    assert_eq!(db.prune(&policy).await?, 0);
+
+    let policy = RetentionPolicy {
+        min_timestamp_ms: Some(1_005_000),
+        ..Default::default()
+    };
+    assert_eq!(db.prune(&policy).await?, 1);
+    assert_eq!(db.oldest_retained_seq_num(), 5);
+    db.vacuum().await?;
+    let events = db.all_events(Some(to_insert[5].id()), 10, false).await?;
+    assert_eq!(events.len(), 1);
+    test_queried_event_vs_test_envelope(&events[0], &to_insert[5]);
+
+    // Pruned sequence numbers are recovered on restart
+    drop(db);
+    let db = S::open_for_testing(dir.path()).await?;
+    assert_eq!(db.oldest_retained_seq_num(), 5);
+    assert!(db
+        .all_events(Some(to_insert[4].id()), 10, false)
+        .await
+        .is_err());
+
+    Ok(())
+}
+
+// Test we can retrieve u64 object version (aka sequence number) values
+// stored as string in sqlite
+pub async fn test_u64_conversion<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let db = S::open_for_testing(dir.path()).await?;
+
+    let to_insert = vec![test_utils::new_test_transfer_event(
+        1_000_000,
+        1,
+        0,
+        u64::MAX,
+        TransferType::Coin,
+        None,
+        None,
+        None,
+    )];
+    db.add_events(&to_insert).await?;
+
+    let events = db
+        .events_by_transaction(to_insert[0].tx_digest.unwrap(), None, 10, false)
+        .await?;
+    assert_eq!(events.len(), 1);
+    info!("events[0]: {:?}", events[0]);
+    assert_eq!(
+        events[0].object_version().unwrap().unwrap().value(),
+        u64::MAX
+    );
+    Ok(())
+}
+
+// Test Idempotency / Sequence Numbering
+pub async fn test_seq_num<S: TestEventStore>() -> Result<(), SuiError> {
+    telemetry_subscribers::init_for_testing();
+
+    // Initialize store
+    let dir = tempfile::TempDir::new().unwrap(); // NOTE this must be its own line so dir isn't dropped
+    let db = S::open_for_testing(dir.path()).await?;
+
+    // TODO: these 30 lines are quite duplicated in this file (4 times).
+    // Write in some events, all should succeed
+    let to_insert = vec![
+        test_utils::new_test_newobj_event(1_000_000, 1, 0, None, None, None),
+        test_utils::new_test_publish_event(1_001_000, 2, 0, None),
+        test_utils::new_test_transfer_event(
+            1_002_000,
+            3,
+            0,
+            1,
+            TransferType::Coin,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_deleteobj_event(1_003_000, 3, 1, None, None),
+        test_utils::new_test_transfer_event(
+            1_004_000,
+            4,
+            0,
+            1,
+            TransferType::ToAddress,
+            None,
+            None,
+            None,
+        ),
+        test_utils::new_test_move_event(
+            1_005_000,
+            5,
+            0,
+            ObjectID::from_hex_literal("0x3").unwrap(),
+            "test_module",
+            "test_foo",
+        ),
+    ];
+    assert_eq!(db.add_events(&to_insert[..4]).await?, 4);
+    assert_eq!(event_count(&db).await?, 4);
+
+    // Write in an older event with older sequence number, should be skipped
+    assert_eq!(db.add_events(&to_insert[1..2]).await?, 0);
+    assert_eq!(event_count(&db).await?, 4);
+
+    // Drop and reload DB from the same file, test that sequence number was recovered
+    drop(db);
+    let db = S::open_for_testing(dir.path()).await?;
+    assert_eq!(db.current_seq_num(), 3);
+    assert_eq!(event_count(&db).await?, 4);
+
+    // Try ingesting older event, check still skipped
+    assert_eq!(db.add_events(&to_insert[1..2]).await?, 0);
+    assert_eq!(event_count(&db).await?, 4);
+
+    // Events of the current sequence number already stored are ignored
+    assert_eq!(db.add_events(&to_insert[2..4]).await?, 0);
+    assert_eq!(event_count(&db).await?, 4);
+
+    // Check writing new events still succeeds
+    assert_eq!(db.add_events(&to_insert[4..]).await?, 2);
+    assert_eq!(event_count(&db).await?, 6);
+
+    Ok(())
+}
@@ -48,12 +48,14 @@ impl TestEvent {
 pub fn new_test_publish_event(
     timestamp: u64,
     seq_num: u64,
+    event_num: u64,
     sender: Option<SuiAddress>,
 ) -> EventEnvelope {
     EventEnvelope::new(
         timestamp,
         None,
         seq_num,
+        event_num,
         Event::Publish {
             sender: sender.unwrap_or_else(SuiAddress::random_for_testing_only),
             package_id: ObjectID::random(),
@@ -65,6 +67,7 @@ pub fn new_test_publish_event(
 pub fn new_test_newobj_event(
     timestamp: u64,
     seq_num: u64,
+    event_num: u64,
     object_id: Option<ObjectID>,
     sender: Option<SuiAddress>,
     recipient: Option<Owner>,
@@ -73,6 +76,7 @@ pub fn new_test_newobj_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        event_num,
         Event::NewObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -88,6 +92,7 @@ pub fn new_test_newobj_event(
 pub fn new_test_deleteobj_event(
     timestamp: u64,
     seq_num: u64,
+    event_num: u64,
     object_id: Option<ObjectID>,
     sender: Option<SuiAddress>,
 ) -> EventEnvelope {
@@ -95,6 +100,7 @@ pub fn new_test_deleteobj_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        event_num,
         Event::DeleteObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -108,6 +114,7 @@ pub fn new_test_deleteobj_event(
 pub fn new_test_transfer_event(
     timestamp: u64,
     seq_num: u64,
+    event_num: u64,
     object_version: u64,
     type_: TransferType,
     object_id: Option<ObjectID>,
@@ -118,6 +125,7 @@ pub fn new_test_transfer_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        event_num,
         Event::TransferObject {
             package_id: ObjectID::random(),
             transaction_module: Identifier::new("module").unwrap(),
@@ -136,6 +144,7 @@ pub fn new_test_transfer_event(
 pub fn new_test_move_event(
     timestamp: u64,
     seq_num: u64,
+    event_num: u64,
     package_id: ObjectID,
     module_name: &str,
     event_struct_name: &'static str,
@@ -161,6 +170,7 @@ pub fn new_test_move_event(
         timestamp,
         Some(TransactionDigest::random()),
         seq_num,
+        event_num,
         move_event,
         Some(json),
     )
@@ -5,21 +5,34 @@
 //! The main user of this data is the explorer.
 
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::StructTag;
 use rocksdb::Options;
-use serde::{de::DeserializeOwned, Serialize};
-use typed_store::rocks::DBMap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use typed_store::rocks::{DBBatch, DBMap};
 use typed_store::traits::Map;
 use typed_store::traits::TypedStoreDebug;
 use typed_store_derive::DBMapUtils;
 
-use sui_types::base_types::ObjectRef;
 use sui_types::base_types::{ObjectID, SuiAddress, TransactionDigest};
+use sui_types::base_types::{ObjectInfo, ObjectRef, SequenceNumber};
 use sui_types::batch::TxSequenceNumber;
 use sui_types::error::SuiResult;
 use sui_types::object::Owner;
 
+use crate::db_stats::{db_stats, ColumnFamilyStats};
 use crate::default_db_options;
 
+const OBJECT_TYPE_INDEXES_BACKFILL_ADDR: u64 = 0;
+
+/// How far the objects that were live when the type indexes were introduced are indexed, in
+/// object ID order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectTypeIndexesBackfill {
+    /// The live objects are indexed up to this object ID, excluded.
+    Until(ObjectID),
+    Done,
+}
+
 #[derive(DBMapUtils)]
 pub struct IndexStore {
     /// Index from sui address to transactions initiated by that address.
@@ -53,6 +66,23 @@ pub struct IndexStore {
     /// Index from transaction digest to sequence number.
     #[default_options_override_fn = "transactions_seq_table_default_config"]
     transactions_seq: DBMap<TransactionDigest, TxSequenceNumber>,
+
+    /// Index from owner and Move struct type to the live objects of that type with that owner.
+    #[default_options_override_fn = "objects_by_owner_and_type_table_default_config"]
+    objects_by_owner_and_type: DBMap<(Owner, StructTag, ObjectID), ObjectInfo>,
+
+    /// Index from Move struct type to the live objects of that type.
+    #[default_options_override_fn = "objects_by_type_table_default_config"]
+    objects_by_type: DBMap<(StructTag, ObjectID), ObjectInfo>,
+
+    /// The owner, type and version under which each object is in the two indexes above, to
+    /// find its entries when the object changes. Packages are not indexed by type.
+    #[default_options_override_fn = "indexed_object_types_table_default_config"]
+    indexed_object_types: DBMap<ObjectID, (Owner, StructTag, SequenceNumber)>,
+
+    /// Single entry with the progress of the indexing of the objects that were live when the type
+    /// indexes were introduced.
+    object_type_indexes_backfill: DBMap<u64, ObjectTypeIndexesBackfill>,
 }
 
 // These functions are used to initialize the DB tables
@@ -77,8 +107,22 @@ fn transactions_by_move_function_table_default_config() -> Options {
 fn timestamps_table_default_config() -> Options {
     default_db_options(None, Some(1_000_000)).1
 }
+fn objects_by_owner_and_type_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn objects_by_type_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).0
+}
+fn indexed_object_types_table_default_config() -> Options {
+    default_db_options(None, Some(1_000_000)).1
+}
 
 impl IndexStore {
+    /// Returns the statistics of the tables of the store.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        db_stats(&self.transactions_from_addr)
+    }
+
     pub fn index_tx(
         &self,
         sender: SuiAddress,
@@ -139,6 +183,136 @@ impl IndexStore {
         Ok(())
     }
 
+    /// Updates the type indexes with the objects `written` at a new version, each with its Move
+    /// struct type, and removes the `removed` (deleted or wrapped) objects from them.
+    /// An object is left untouched if it is indexed at a version at least as high, so that
+    /// indexing transactions again, or out of order, is harmless.
+    pub fn index_object_types(
+        &self,
+        written: impl Iterator<Item = (ObjectInfo, StructTag)>,
+        removed: impl Iterator<Item = ObjectRef>,
+    ) -> SuiResult {
+        self.object_types_batch(written, removed)?.write()?;
+        Ok(())
+    }
+
+    /// Indexes the live objects `written` like [`Self::index_object_types`], and saves the
+    /// `progress` of the backfill along with them.
+    pub fn backfill_object_types(
+        &self,
+        written: impl Iterator<Item = (ObjectInfo, StructTag)>,
+        progress: ObjectTypeIndexesBackfill,
+    ) -> SuiResult {
+        self.object_types_batch(written, std::iter::empty())?
+            .insert_batch(
+                &self.object_type_indexes_backfill,
+                std::iter::once((OBJECT_TYPE_INDEXES_BACKFILL_ADDR, progress)),
+            )?
+            .write()?;
+        Ok(())
+    }
+
+    fn object_types_batch(
+        &self,
+        written: impl Iterator<Item = (ObjectInfo, StructTag)>,
+        removed: impl Iterator<Item = ObjectRef>,
+    ) -> SuiResult<DBBatch> {
+        let mut batch = self.indexed_object_types.batch();
+        for (info, type_) in written {
+            let object_id = info.object_id;
+            if let Some((owner, old_type, version)) = self.indexed_object_types.get(&object_id)? {
+                if version >= info.version {
+                    continue;
+                }
+                batch = self.remove_object_type_entries(batch, object_id, owner, old_type)?;
+            }
+            batch = batch.insert_batch(
+                &self.objects_by_owner_and_type,
+                std::iter::once(((info.owner, type_.clone(), object_id), info.clone())),
+            )?;
+            batch = batch.insert_batch(
+                &self.objects_by_type,
+                std::iter::once(((type_.clone(), object_id), info.clone())),
+            )?;
+            batch = batch.insert_batch(
+                &self.indexed_object_types,
+                std::iter::once((object_id, (info.owner, type_, info.version))),
+            )?;
+        }
+        for (object_id, version, _) in removed {
+            if let Some((owner, type_, indexed_version)) =
+                self.indexed_object_types.get(&object_id)?
+            {
+                if indexed_version >= version {
+                    continue;
+                }
+                batch = self.remove_object_type_entries(batch, object_id, owner, type_)?;
+                batch =
+                    batch.delete_batch(&self.indexed_object_types, std::iter::once(object_id))?;
+            }
+        }
+        Ok(batch)
+    }
+
+    fn remove_object_type_entries(
+        &self,
+        batch: DBBatch,
+        object_id: ObjectID,
+        owner: Owner,
+        type_: StructTag,
+    ) -> SuiResult<DBBatch> {
+        let batch = batch.delete_batch(
+            &self.objects_by_owner_and_type,
+            std::iter::once((owner, type_.clone(), object_id)),
+        )?;
+        Ok(batch.delete_batch(&self.objects_by_type, std::iter::once((type_, object_id)))?)
+    }
+
+    /// How far the objects that were live when the type indexes were introduced are indexed, or
+    /// None if their indexing has not started.
+    pub fn object_type_indexes_backfill(&self) -> SuiResult<Option<ObjectTypeIndexesBackfill>> {
+        Ok(self
+            .object_type_indexes_backfill
+            .get(&OBJECT_TYPE_INDEXES_BACKFILL_ADDR)?)
+    }
+
+    /// Returns the live objects with Move struct type `type_` owned by `owner`, ordered by object
+    /// ID, starting at object ID `cursor` if given.
+    pub fn get_objects_by_owner_and_type(
+        &self,
+        owner: Owner,
+        type_: &StructTag,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        Ok(self
+            .objects_by_owner_and_type
+            .iter()
+            .skip_to(&(owner, type_.clone(), cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((o, t, _), _)| *o == owner && t == type_)
+            .take(limit)
+            .map(|(_, info)| info)
+            .collect())
+    }
+
+    /// Returns the live objects with Move struct type `type_`, ordered by object ID, starting at
+    /// object ID `cursor` if given.
+    pub fn get_objects_by_type(
+        &self,
+        type_: &StructTag,
+        cursor: Option<ObjectID>,
+        limit: usize,
+    ) -> SuiResult<Vec<ObjectInfo>> {
+        Ok(self
+            .objects_by_type
+            .iter()
+            .skip_to(&(type_.clone(), cursor.unwrap_or(ObjectID::ZERO)))?
+            .take_while(|((t, _), _)| t == type_)
+            .take(limit)
+            .map(|(_, info)| info)
+            .collect())
+    }
+
     /// Returns unix timestamp for a transaction if it exists
     pub fn get_timestamp_ms(
         &self,
@@ -294,3 +468,185 @@ impl IndexStore {
         Ok(self.transactions_seq.get(digest)?)
     }
 }
+
+/// Timestamps of executed transactions, read from the index store of a node or from a read-only
+/// handle of it opened by tools, which must not write to the store of a running node.
+pub trait TransactionTimestamps {
+    fn get_timestamp_ms(&self, transaction_digest: &TransactionDigest) -> SuiResult<Option<u64>>;
+}
+
+impl TransactionTimestamps for IndexStore {
+    fn get_timestamp_ms(&self, transaction_digest: &TransactionDigest) -> SuiResult<Option<u64>> {
+        IndexStore::get_timestamp_ms(self, transaction_digest)
+    }
+}
+
+impl TransactionTimestamps for IndexStoreReadOnly {
+    fn get_timestamp_ms(&self, transaction_digest: &TransactionDigest) -> SuiResult<Option<u64>> {
+        Ok(self.timestamps.get(transaction_digest)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sui_types::base_types::{dbg_addr, ObjectDigest};
+    use sui_types::gas_coin::GasCoin;
+    use sui_types::SUI_FRAMEWORK_ADDRESS;
+
+    fn object_info(
+        object_id: ObjectID,
+        version: u64,
+        owner: Owner,
+        type_: &StructTag,
+    ) -> ObjectInfo {
+        ObjectInfo {
+            object_id,
+            version: SequenceNumber::from(version),
+            digest: ObjectDigest::random(),
+            type_: type_.to_string(),
+            owner,
+            previous_transaction: TransactionDigest::random(),
+        }
+    }
+
+    #[test]
+    fn test_object_type_indexes() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let store =
+            IndexStore::open_tables_read_write(working_dir.path().join("indexes"), None, None);
+
+        let nft_type = StructTag {
+            address: SUI_FRAMEWORK_ADDRESS,
+            module: Identifier::new("devnet_nft").unwrap(),
+            name: Identifier::new("DevNetNFT").unwrap(),
+            type_params: vec![],
+        };
+        let gas_type = GasCoin::type_();
+        let (alice, bob) = (
+            Owner::AddressOwner(dbg_addr(1)),
+            Owner::AddressOwner(dbg_addr(2)),
+        );
+        let mut nft_ids: Vec<_> = (0..3).map(|_| ObjectID::random()).collect();
+        nft_ids.sort();
+        let gas_id = ObjectID::random();
+
+        let nfts: Vec<_> = nft_ids
+            .iter()
+            .map(|id| object_info(*id, 1, alice, &nft_type))
+            .collect();
+        let gas = object_info(gas_id, 1, alice, &gas_type);
+        store
+            .index_object_types(
+                nfts.iter()
+                    .map(|info| (info.clone(), nft_type.clone()))
+                    .chain(std::iter::once((gas.clone(), gas_type.clone()))),
+                std::iter::empty(),
+            )
+            .unwrap();
+
+        assert_eq!(
+            store
+                .get_objects_by_owner_and_type(alice, &nft_type, None, 10)
+                .unwrap(),
+            nfts
+        );
+        assert_eq!(
+            store
+                .get_objects_by_owner_and_type(alice, &gas_type, None, 10)
+                .unwrap(),
+            vec![gas.clone()]
+        );
+        // Pages start at the cursor
+        assert_eq!(
+            store
+                .get_objects_by_type(&nft_type, Some(nft_ids[1]), 1)
+                .unwrap(),
+            vec![nfts[1].clone()]
+        );
+
+        // Transfer the first NFT to bob and delete the second one
+        let transferred = object_info(nft_ids[0], 2, bob, &nft_type);
+        store
+            .index_object_types(
+                std::iter::once((transferred.clone(), nft_type.clone())),
+                std::iter::once((
+                    nft_ids[1],
+                    SequenceNumber::from(2),
+                    ObjectDigest::OBJECT_DIGEST_DELETED,
+                )),
+            )
+            .unwrap();
+        assert_eq!(
+            store
+                .get_objects_by_owner_and_type(alice, &nft_type, None, 10)
+                .unwrap(),
+            vec![nfts[2].clone()]
+        );
+        assert_eq!(
+            store
+                .get_objects_by_owner_and_type(bob, &nft_type, None, 10)
+                .unwrap(),
+            vec![transferred.clone()]
+        );
+        assert_eq!(
+            store.get_objects_by_type(&nft_type, None, 10).unwrap(),
+            vec![transferred.clone(), nfts[2].clone()]
+        );
+
+        // Indexing an older version again changes nothing
+        store
+            .index_object_types(
+                std::iter::once((nfts[0].clone(), nft_type.clone())),
+                std::iter::empty(),
+            )
+            .unwrap();
+        assert_eq!(
+            store
+                .get_objects_by_owner_and_type(bob, &nft_type, None, 10)
+                .unwrap(),
+            vec![transferred]
+        );
+    }
+
+    #[test]
+    fn test_backfill_object_types() {
+        let working_dir = tempfile::tempdir().unwrap();
+        let store =
+            IndexStore::open_tables_read_write(working_dir.path().join("indexes"), None, None);
+        assert_eq!(store.object_type_indexes_backfill().unwrap(), None);
+
+        let gas_type = GasCoin::type_();
+        let owner = Owner::AddressOwner(dbg_addr(1));
+        let mut gas_ids: Vec<_> = (0..2).map(|_| ObjectID::random()).collect();
+        gas_ids.sort();
+        let gas: Vec<_> = gas_ids
+            .iter()
+            .map(|id| object_info(*id, 1, owner, &gas_type))
+            .collect();
+
+        // The progress is saved with each chunk of indexed objects
+        for (i, progress) in [
+            ObjectTypeIndexesBackfill::Until(gas_ids[1]),
+            ObjectTypeIndexesBackfill::Done,
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            store
+                .backfill_object_types(
+                    std::iter::once((gas[i].clone(), gas_type.clone())),
+                    progress,
+                )
+                .unwrap();
+            assert_eq!(
+                store.object_type_indexes_backfill().unwrap(),
+                Some(progress)
+            );
+            assert_eq!(
+                store.get_objects_by_type(&gas_type, None, 10).unwrap(),
+                gas[..=i].to_vec()
+            );
+        }
+    }
+}
@@ -5,8 +5,9 @@ pub mod lock_service;
 pub use lock_service::LockService;
 
 pub mod indexes;
-pub use indexes::IndexStore;
+pub use indexes::{IndexStore, IndexStoreReadOnly, TransactionTimestamps};
 
+pub mod db_stats;
 pub mod event_store;
 pub mod mutex_table;
 pub mod node_sync_store;
@@ -14,7 +15,9 @@ pub mod write_ahead_log;
 
 use rocksdb::Options;
 use std::future::Future;
-use typed_store::rocks::default_rocksdb_options;
+use std::path::Path;
+use sui_types::error::SuiResult;
+use typed_store::rocks::{default_rocksdb_options, DBMap, TypedStoreError};
 
 /// Given a provided `db_options`, add a few default options.
 /// Returns the default option and the point lookup option.
@@ -46,6 +49,15 @@ pub fn default_db_options(
     (options, point_lookup)
 }
 
+/// Creates a RocksDB checkpoint of the database holding `table` in the directory `path`, which
+/// must not exist yet. Table files are hard-linked when `path` is on the same filesystem as the
+/// database, so this is cheap and can be done while the database is written to.
+pub fn checkpoint_db<K, V>(table: &DBMap<K, V>, path: &Path) -> SuiResult {
+    rocksdb::checkpoint::Checkpoint::new(&*table.rocksdb)
+        .and_then(|checkpoint| checkpoint.create_checkpoint(path))
+        .map_err(|e| TypedStoreError::RocksDBError(e.to_string()).into())
+}
+
 // Used to exec futures that send data to/from other threads. In the simulator, this becomes a
 // blocking call, which removes the non-determinism that would otherwise be caused by the
 // timing of the reply from the other thread.
@@ -18,7 +18,7 @@
 use futures::channel::oneshot;
 use rocksdb::Options;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::thread::JoinHandle;
 use tokio::sync::mpsc::{channel, Receiver, Sender};
@@ -34,7 +34,8 @@ use sui_types::committee::EpochId;
 use sui_types::error::{SuiError, SuiResult};
 use sui_types::fp_ensure;
 
-use crate::{block_on_future_in_sim, default_db_options};
+use crate::db_stats::{db_stats, ColumnFamilyStats};
+use crate::{block_on_future_in_sim, checkpoint_db, default_db_options};
 
 /// Commands to send to the LockService (for mutating lock state)
 // TODO: use smallvec as an optimization
@@ -438,6 +439,7 @@ pub struct LockService {
 }
 
 struct LockServiceInner {
+    tables: LockServiceImpl,
     sender: Option<Sender<LockServiceCommands>>,
     query_sender: Option<Sender<LockServiceQueries>>,
     run_command_loop: Option<JoinHandle<()>>,
@@ -497,12 +499,14 @@ impl LockService {
         });
 
         let (q_sender, q_receiver) = channel(LOCKSERVICE_QUEUE_LEN);
+        let inner3 = inner_service.clone();
         let run_queries_loop = std::thread::spawn(move || {
-            inner_service.run_queries_loop(q_receiver);
+            inner3.run_queries_loop(q_receiver);
         });
 
         Ok(Self {
             inner: Arc::new(LockServiceInner {
+                tables: inner_service,
                 sender: Some(sender),
                 query_sender: Some(q_sender),
                 run_command_loop: Some(run_command_loop),
@@ -511,6 +515,16 @@ impl LockService {
         })
     }
 
+    /// Creates a RocksDB checkpoint of the lock database in the directory `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        checkpoint_db(&self.inner.tables.transaction_lock, path)
+    }
+
+    /// Returns the statistics of the tables of the lock database.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        db_stats(&self.inner.tables.transaction_lock)
+    }
+
     /// Acquires a lock for a transaction on the given objects if they have all been initialized previously
     /// to None state.  It is also OK if they have been set to the same transaction.
     /// The locks are all set to the given transaction digest.
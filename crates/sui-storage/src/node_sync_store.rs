@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::BTreeSet;
+use std::path::Path;
 
 use sui_types::{
     base_types::{
@@ -21,6 +22,9 @@ use typed_store_derive::DBMapUtils;
 
 use tracing::trace;
 
+use crate::checkpoint_db;
+use crate::db_stats::{db_stats, ColumnFamilyStats};
+
 #[cfg(test)]
 use std::sync::Arc;
 
@@ -62,6 +66,16 @@ impl NodeSyncStore {
         Arc::new(NodeSyncStore::open_tables_read_write(db_path, None, None))
     }
 
+    /// Creates a RocksDB checkpoint of the store in the directory `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        checkpoint_db(&self.pending_certs, path)
+    }
+
+    /// Returns the statistics of the tables of the store.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        db_stats(&self.pending_certs)
+    }
+
     pub fn store_cert(&self, epoch_id: EpochId, cert: &CertifiedTransaction) -> SuiResult {
         Ok(self
             .pending_certs
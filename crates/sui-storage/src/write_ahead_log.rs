@@ -7,10 +7,12 @@
 
 use async_trait::async_trait;
 
+use crate::checkpoint_db;
+use crate::db_stats::{db_stats, ColumnFamilyStats};
 use crate::mutex_table::{LockGuard, MutexTable};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt::Debug;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use sui_types::base_types::TransactionDigest;
 use typed_store::traits::TypedStoreDebug;
@@ -207,6 +209,16 @@ where
         }
     }
 
+    /// Creates a RocksDB checkpoint of the log in the directory `path`.
+    pub fn checkpoint_db(&self, path: &Path) -> SuiResult {
+        checkpoint_db(&self.tables.log, path)
+    }
+
+    /// Returns the statistics of the tables of the log.
+    pub fn db_stats(&self) -> SuiResult<Vec<ColumnFamilyStats>> {
+        db_stats(&self.tables.log)
+    }
+
     fn commit_tx(&self, tx: &TransactionDigest, is_commit: bool) -> SuiResult {
         if is_commit {
             debug!(digest = ?tx, "committing tx");
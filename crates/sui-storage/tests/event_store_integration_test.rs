@@ -14,24 +14,26 @@ async fn test_stored_event_to_sui_event() -> Result<(), anyhow::Error> {
     let db = SqlEventStore::new_memory_only_not_prod().await?;
     db.initialize().await.map_err(anyhow::Error::from)?;
 
-    let new_obj = test_utils::new_test_newobj_event(1_666_000, 1, None, None, None);
+    let new_obj = test_utils::new_test_newobj_event(1_666_000, 1, 0, None, None, None);
     insert_and_fetch_by_tx_digest_then_compare(new_obj, &db).await?;
 
     let move_ = test_utils::new_test_move_event(
         1_666_001,
         2,
+        0,
         ObjectID::from_hex_literal("0x3").unwrap(),
         "a_module",
         "whatever",
     );
     insert_and_fetch_by_tx_digest_then_compare(move_, &db).await?;
 
-    let delete_obj = test_utils::new_test_deleteobj_event(1_666_002, 3, None, None);
+    let delete_obj = test_utils::new_test_deleteobj_event(1_666_002, 3, 0, None, None);
     insert_and_fetch_by_tx_digest_then_compare(delete_obj, &db).await?;
 
     let transfer_obj = test_utils::new_test_transfer_event(
         1_666_003,
         4,
+        0,
         1,
         TransferType::ToAddress,
         None,
@@ -40,10 +42,10 @@ async fn test_stored_event_to_sui_event() -> Result<(), anyhow::Error> {
     );
     insert_and_fetch_by_tx_digest_then_compare(transfer_obj, &db).await?;
 
-    let publish = test_utils::new_test_publish_event(1_001_000, 5, None);
+    let publish = test_utils::new_test_publish_event(1_001_000, 5, 0, None);
     assert_eq!(db.add_events(&vec![publish.clone()]).await?, 1);
     let mut queried_events = db
-        .events_by_type(1_001_000, 1_002_000, EventType::Publish, 1)
+        .events_by_type(EventType::Publish, None, 1, false)
         .await?;
     assert_eq!(queried_events.len(), 1);
     let sui_event: SuiEventEnvelope = queried_events.swap_remove(0).try_into()?;
@@ -61,7 +63,7 @@ async fn insert_and_fetch_by_tx_digest_then_compare(
     let tx_digest = event_envelope.tx_digest.unwrap();
     assert_eq!(db.add_events(&vec![event_envelope.clone()]).await?, 1);
 
-    let mut events = db.events_by_transaction(tx_digest, 10).await?;
+    let mut events = db.events_by_transaction(tx_digest, None, 10, false).await?;
     assert_eq!(events.len(), 1);
     let stored_event = events.pop().unwrap();
     let sui_event: SuiEventEnvelope = stored_event.try_into()?;
@@ -5,11 +5,16 @@ use anyhow::Result;
 use futures::future::join_all;
 use std::cmp::min;
 use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
 use std::sync::Arc;
 use sui_config::genesis::Genesis;
+use sui_config::node::EventStoreBackend;
 use sui_network::default_mysten_network_config;
+use sui_tool::db_tool::db_restore::restore_db_backup;
 use sui_tool::db_tool::{execute_db_tool_command, print_db_all_tables, DbToolCommand};
+use sui_tool::export::{export_ledger, ExportFormat};
 
 use sui_core::authority_client::{
     AuthorityAPI, NetworkAuthorityClient, NetworkAuthorityClientMetrics,
@@ -20,7 +25,22 @@ use anyhow::anyhow;
 use futures::stream::StreamExt;
 
 use clap::*;
-use sui_core::authority::MAX_ITEMS_LIMIT;
+use sui_core::authority::{AuthorityStore, MAX_ITEMS_LIMIT};
+use sui_core::authority_aggregator::AuthorityAggregatorBuilder;
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_core::effects_divergence::{
+    check_checkpoint, check_sequence_range, check_transaction, DivergenceReport,
+};
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_core::event_handler::EventHandler;
+use sui_core::event_store_rebuilder::rebuild_event_store;
+use sui_core::ledger_export::LedgerExporter;
+use sui_core::state_snapshot::{
+    export_state_snapshot, read_state_snapshot_header, verify_state_snapshot_header,
+};
+use sui_core::transaction_replay::{replay_transaction, TransactionReplay};
+use sui_storage::event_store::{EventStoreType, RocksEventStore, SqlEventStore};
+use sui_storage::IndexStore;
 use sui_types::messages_checkpoint::{
     CheckpointRequest, CheckpointResponse, CheckpointSequenceNumber,
 };
@@ -137,6 +157,135 @@ pub enum ToolCommand {
         )]
         sequence_number: Option<CheckpointSequenceNumber>,
     },
+
+    /// Rebuild the event store of a node from the effects in its database, resuming from the
+    /// last transaction in the event store. The node must be stopped.
+    /// To rebuild an event store that was enabled late from the first transaction, delete it
+    /// first.
+    #[clap(name = "rebuild-event-store")]
+    RebuildEventStore {
+        /// Path of the node DB, the `db-path` of the node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Path of the event store, `events.db` or `events` in the node DB by default
+        #[clap(long = "event-store-path")]
+        event_store_path: Option<PathBuf>,
+        /// Storage engine of the event store, `sqlite` or `rocksdb`
+        #[clap(long = "event-store-backend", default_value = "sqlite")]
+        event_store_backend: EventStoreBackend,
+    },
+
+    /// Export and inspect state snapshots, which bootstrap new nodes without replaying the
+    /// history of the chain.
+    #[clap(name = "snapshot")]
+    Snapshot {
+        #[clap(subcommand)]
+        cmd: SnapshotCommand,
+    },
+
+    /// Restore a backup created with `POST /db-backup` on the admin interface of a node into the
+    /// DB of a stopped node. The authority, committee, checkpoint and node sync stores of the DB
+    /// are moved aside to `<store>.old` and replaced by copies of the backup. Indexes and event
+    /// stores are not part of backups: delete them and rebuild the event store with
+    /// `rebuild-event-store` before restarting the node.
+    #[clap(name = "restore-db-backup")]
+    RestoreDbBackup {
+        /// Path of the backup, as reported by the admin interface
+        #[clap(long = "backup-path")]
+        backup_path: PathBuf,
+        /// Path of the node DB, the `db-path` of the node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+    },
+
+    /// Re-execute a certified transaction in-process, on the versions of the objects it was
+    /// executed on, and compare the effects with the certified effects. The certificate and the
+    /// objects are read from the DB of a stopped node if `--db-path` is given, from the validators
+    /// of the genesis otherwise.
+    #[clap(name = "replay")]
+    Replay {
+        /// Digest of the transaction to replay
+        #[clap(long = "tx")]
+        tx: TransactionDigest,
+        /// Path of the node DB, the `db-path` of the node config
+        #[clap(long = "db-path", conflicts_with = "genesis")]
+        db_path: Option<PathBuf>,
+        #[clap(long = "genesis", required_unless_present = "db_path")]
+        genesis: Option<PathBuf>,
+        /// Write the trace of the Move instructions executed by the Move VM to this file. Only
+        /// debug builds of sui-tool can trace the Move VM, release builds reject this option.
+        #[clap(long = "move-vm-trace")]
+        move_vm_trace: Option<PathBuf>,
+        /// Print the certificate and both effects as JSON
+        #[clap(long = "json")]
+        json: bool,
+    },
+
+    /// Query every validator of the genesis committee for the effects of the same transactions
+    /// and report the validators whose signed effects disagree with the others or are missing,
+    /// with the first differing field of the effects. The transactions are either one digest,
+    /// the sequence range `[start, end)` of the batches of the validators, or the transactions
+    /// of a certified checkpoint, whose effects digests are then the reference.
+    #[clap(name = "check-effects")]
+    CheckEffects {
+        #[clap(long = "genesis")]
+        genesis: PathBuf,
+        #[clap(long, conflicts_with_all = &["start", "checkpoint"])]
+        digest: Option<TransactionDigest>,
+        #[clap(long, requires = "end", conflicts_with = "checkpoint")]
+        start: Option<TxSequenceNumber>,
+        #[clap(long, requires = "start")]
+        end: Option<TxSequenceNumber>,
+        #[clap(long)]
+        checkpoint: Option<CheckpointSequenceNumber>,
+    },
+
+    /// Export the transactions, object changes and events of the DB of a stopped node to JSON
+    /// Lines or Parquet files under `--output`, in the JSON format of the RPC API. Running the
+    /// command again on the same output resumes an interrupted export.
+    #[clap(name = "export")]
+    Export {
+        /// Path of the node DB, the `db-path` of the node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Directory to write the files to
+        #[clap(long = "output")]
+        output: PathBuf,
+        /// Format of the files, `jsonl` or `parquet`
+        #[clap(long = "format", default_value = "jsonl")]
+        format: ExportFormat,
+        /// Sequence number of the first transaction to export, 0 by default
+        #[clap(long)]
+        start: Option<TxSequenceNumber>,
+        /// Sequence number to stop the export at (exclusive), the last executed transaction by
+        /// default
+        #[clap(long)]
+        end: Option<TxSequenceNumber>,
+    },
+}
+
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub enum SnapshotCommand {
+    /// Export the live object set of a node at a certified checkpoint. The node must be stopped.
+    Export {
+        /// Path of the node DB, the `db-path` of the node config
+        #[clap(long = "db-path")]
+        db_path: PathBuf,
+        /// Path of the snapshot file to write
+        #[clap(long = "output")]
+        output: PathBuf,
+        /// Checkpoint to take the snapshot at, the latest certified checkpoint by default
+        #[clap(long)]
+        checkpoint: Option<CheckpointSequenceNumber>,
+    },
+    /// Print the checkpoint and committee of a snapshot, and verify them if a genesis is given.
+    Inspect {
+        #[clap(long = "snapshot")]
+        snapshot: PathBuf,
+        #[clap(long = "genesis")]
+        genesis: Option<PathBuf>,
+    },
 }
 
 fn make_clients(genesis: &Genesis) -> Result<BTreeMap<AuthorityName, NetworkAuthorityClient>> {
@@ -381,6 +530,36 @@ async fn get_object(
     ret
 }
 
+fn print_transaction_replay(replay: &TransactionReplay) {
+    // The gas cost summary of the effects, the Move VM does not break the gas down further.
+    let gas = |effects: &TransactionEffects| {
+        format!(
+            "computation {}, storage {}, rebate {}",
+            effects.gas_used.computation_cost,
+            effects.gas_used.storage_cost,
+            effects.gas_used.storage_rebate
+        )
+    };
+    println!("Transaction: {:?}", replay.certificate.digest());
+    println!("Certified status: {:?}", replay.certified_effects.status);
+    println!("Replayed status: {:?}", replay.replayed_effects.status);
+    if let Some(error) = &replay.execution_error {
+        println!("Replayed execution error: {error}");
+    }
+    println!("Certified gas summary: {}", gas(&replay.certified_effects));
+    println!("Replayed gas summary: {}", gas(&replay.replayed_effects));
+    for package in &replay.packages {
+        println!("Loaded package: {:?}", package);
+    }
+    if replay.effects_match() {
+        println!("The replayed effects match the certified effects");
+    } else {
+        println!("Certified effects: {:#?}", replay.certified_effects);
+        println!("Replayed effects: {:#?}", replay.replayed_effects);
+        println!("Differences: {}", replay.differences.join(", "));
+    }
+}
+
 async fn handle_batch(client: &dyn AuthorityAPI, req: &BatchInfoRequest) {
     let mut streamx = Box::pin(client.handle_batch_stream(req.clone()).await.unwrap());
 
@@ -549,6 +728,207 @@ impl ToolCommand {
                     }
                 }
             }
+            ToolCommand::RebuildEventStore {
+                db_path,
+                event_store_path,
+                event_store_backend,
+            } => {
+                let store = Arc::new(AuthorityStore::open(&db_path.join("store"), None));
+                let index_path = db_path.join("indexes");
+                let indexes = index_path
+                    .exists()
+                    .then(|| IndexStore::get_read_only_handle(index_path, None, None));
+                let event_store_path =
+                    event_store_path.unwrap_or_else(|| event_store_backend.default_path(&db_path));
+                let event_store = match event_store_backend {
+                    EventStoreBackend::Sqlite => {
+                        let db = SqlEventStore::new_from_file(&event_store_path).await?;
+                        db.initialize().await?;
+                        EventStoreType::SqlEventStore(db)
+                    }
+                    EventStoreBackend::Rocksdb => {
+                        EventStoreType::RocksEventStore(RocksEventStore::open(&event_store_path)?)
+                    }
+                };
+                let event_handler = EventHandler::new(store.clone(), Arc::new(event_store));
+
+                let progress =
+                    rebuild_event_store(&store, indexes.as_ref(), &event_handler, |progress| {
+                        println!(
+                            "Replayed transactions up to {} of {}: {} events",
+                            progress.next_seq, progress.end_seq, progress.events
+                        )
+                    })
+                    .await?;
+                println!(
+                    "Rebuilt {:?}: replayed {} transactions and {} events",
+                    event_store_path, progress.transactions, progress.events
+                );
+                if progress.missing_timestamps > 0 {
+                    println!(
+                        "{} transactions had no timestamp, their events have the timestamp of the previous transaction",
+                        progress.missing_timestamps
+                    );
+                }
+            }
+            ToolCommand::Snapshot {
+                cmd:
+                    SnapshotCommand::Export {
+                        db_path,
+                        output,
+                        checkpoint,
+                    },
+            } => {
+                let store = AuthorityStore::open(&db_path.join("store"), None);
+                let checkpoints = CheckpointStoreTables::open_tables_read_write(
+                    db_path.join("checkpoints"),
+                    None,
+                    None,
+                );
+                let committees =
+                    CommitteeStore::open_tables_read_write(db_path.join("epochs"), None, None);
+                let writer = BufWriter::new(File::create(&output)?);
+                let summary =
+                    export_state_snapshot(&store, &checkpoints, &committees, checkpoint, writer)?;
+                println!(
+                    "Exported {} objects and the effects of {} transactions at checkpoint {} of epoch {} to {:?}",
+                    summary.objects, summary.effects, summary.checkpoint, summary.epoch, output
+                );
+            }
+            ToolCommand::Snapshot {
+                cmd: SnapshotCommand::Inspect { snapshot, genesis },
+            } => {
+                let header = read_state_snapshot_header(BufReader::new(File::open(snapshot)?))?;
+                println!("Checkpoint: {}\n", header.checkpoint);
+                println!("Committee: {}", header.committee);
+                if let Some(genesis) = genesis {
+                    let genesis = Genesis::load(genesis)?;
+                    verify_state_snapshot_header(&header, &genesis.committee()?)?;
+                    println!("The snapshot checkpoint is certified by the genesis committee");
+                }
+            }
+            ToolCommand::Replay {
+                tx,
+                db_path,
+                genesis,
+                move_vm_trace,
+                json,
+            } => {
+                if let Some(trace) = move_vm_trace {
+                    if !cfg!(debug_assertions) {
+                        return Err(anyhow!(
+                            "Release builds do not trace the Move VM, build sui-tool without --release"
+                        ));
+                    }
+                    // Read by the Move VM when it executes its first instruction.
+                    std::env::set_var("MOVE_VM_TRACE", trace);
+                }
+                let replay = match (db_path, genesis) {
+                    (Some(db_path), _) => {
+                        let store = AuthorityStore::open(&db_path.join("store"), None);
+                        replay_transaction(&store, &tx).await?
+                    }
+                    (None, Some(genesis)) => {
+                        let genesis = Genesis::load(genesis)?;
+                        let (aggregator, _) =
+                            AuthorityAggregatorBuilder::from_genesis(&genesis).build()?;
+                        replay_transaction(&aggregator, &tx).await?
+                    }
+                    (None, None) => return Err(anyhow!("Either --db-path or --genesis is needed")),
+                };
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&replay)?);
+                } else {
+                    print_transaction_replay(&replay);
+                }
+                if !replay.effects_match() {
+                    return Err(anyhow!(
+                        "The replayed effects differ from the certified effects in {}",
+                        replay.differences.join(", ")
+                    ));
+                }
+            }
+            ToolCommand::RestoreDbBackup {
+                backup_path,
+                db_path,
+            } => {
+                let moved = restore_db_backup(&backup_path, &db_path)?;
+                println!("Restored {:?} into {:?}", backup_path, db_path);
+                for old in moved {
+                    println!("Moved the previous store to {:?}", old);
+                }
+            }
+            ToolCommand::Export {
+                db_path,
+                output,
+                format,
+                start,
+                end,
+            } => {
+                let store = Arc::new(AuthorityStore::open(&db_path.join("store"), None));
+                let index_path = db_path.join("indexes");
+                let indexes = index_path
+                    .exists()
+                    .then(|| IndexStore::open_tables_read_write(index_path, None, None));
+                let checkpoint_path = db_path.join("checkpoints");
+                let checkpoints = checkpoint_path.exists().then(|| {
+                    CheckpointStoreTables::open_tables_read_write(checkpoint_path, None, None)
+                });
+                let exporter = LedgerExporter::new(store, indexes, checkpoints);
+                let summary = export_ledger(&exporter, &output, format, start, end, |chunk| {
+                    println!(
+                        "Exported transactions {} to {}: {} transactions",
+                        chunk.start,
+                        chunk.end,
+                        chunk.transactions.len()
+                    )
+                })?;
+                println!(
+                    "Exported transactions {} to {} to {:?}: {} transactions, {} object changes and {} events",
+                    summary.start,
+                    summary.end,
+                    output,
+                    summary.transactions,
+                    summary.object_changes,
+                    summary.events
+                );
+            }
+            ToolCommand::CheckEffects {
+                genesis,
+                digest,
+                start,
+                end,
+                checkpoint,
+            } => {
+                let genesis = Genesis::load(genesis)?;
+                let (aggregator, _) = AuthorityAggregatorBuilder::from_genesis(&genesis).build()?;
+                let report = match (digest, start, end, checkpoint) {
+                    (Some(digest), _, _, _) => {
+                        let mut report = DivergenceReport::default();
+                        report.push(check_transaction(&aggregator, digest).await);
+                        report
+                    }
+                    (None, Some(start), Some(end), _) => {
+                        check_sequence_range(&aggregator, start, end).await
+                    }
+                    (None, _, _, Some(checkpoint)) => {
+                        check_checkpoint(&aggregator, checkpoint).await?
+                    }
+                    _ => {
+                        return Err(anyhow!(
+                            "One of --digest, --start and --end, or --checkpoint is needed"
+                        ))
+                    }
+                };
+                println!("{}", serde_json::to_string_pretty(&report)?);
+                if report.has_forks() {
+                    return Err(anyhow!(
+                        "Validators signed conflicting effects for {} of {} transactions",
+                        report.forks,
+                        report.transactions
+                    ));
+                }
+            }
         };
         Ok(())
     }
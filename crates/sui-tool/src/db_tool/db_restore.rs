@@ -0,0 +1,56 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sui_core::db_backup::{CHECKPOINTS_DIR, EPOCHS_DIR, NODE_SYNC_DIR, STORE_DIR};
+
+/// The stores of a node DB that are part of a backup.
+const BACKUP_DIRS: [&str; 4] = [STORE_DIR, EPOCHS_DIR, CHECKPOINTS_DIR, NODE_SYNC_DIR];
+
+/// Restores a backup created through the admin interface of a node into the DB of a stopped
+/// node. The stores of the node DB are moved aside to `<store>.old` first, and returned.
+pub fn restore_db_backup(backup_path: &Path, db_path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    for dir in BACKUP_DIRS {
+        if !backup_path.join(dir).is_dir() {
+            bail!(
+                "{:?} is not a backup, it has no {dir} directory",
+                backup_path
+            );
+        }
+        let old = db_path.join(format!("{dir}.old"));
+        if old.exists() {
+            bail!("{:?} already exists, remove it before restoring", old);
+        }
+    }
+
+    let mut moved = Vec::new();
+    for dir in BACKUP_DIRS {
+        let current = db_path.join(dir);
+        if current.exists() {
+            let old = db_path.join(format!("{dir}.old"));
+            fs::rename(&current, &old)?;
+            moved.push(old);
+        }
+        copy_dir(&backup_path.join(dir), &current)
+            .map_err(|e| anyhow!("Failed to copy {dir} from the backup: {e}"))?;
+    }
+    Ok(moved)
+}
+
+/// Copies the files rather than hard-linking them, so that the backup stays intact once the
+/// node writes to the restored DB.
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,40 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use rocksdb::{DBWithThreadMode, MultiThreaded};
+use std::path::{Path, PathBuf};
+use sui_storage::db_stats::{column_family_stats, ColumnFamilyStats};
+use sui_storage::default_db_options;
+
+/// Returns the statistics of the tables of every RocksDB database found under `path`, which can
+/// be a single database or the whole DB of a node.
+pub fn db_stats(path: &Path) -> anyhow::Result<Vec<(PathBuf, Vec<ColumnFamilyStats>)>> {
+    let mut stats = Vec::new();
+    collect_db_stats(path, &mut stats)?;
+    Ok(stats)
+}
+
+fn collect_db_stats(
+    path: &Path,
+    stats: &mut Vec<(PathBuf, Vec<ColumnFamilyStats>)>,
+) -> anyhow::Result<()> {
+    // Every RocksDB database has a CURRENT file pointing at its manifest
+    if path.join("CURRENT").is_file() {
+        let options = default_db_options(None, None).0;
+        let names = DBWithThreadMode::<MultiThreaded>::list_cf(&options, path)?;
+        let db = DBWithThreadMode::<MultiThreaded>::open_cf_for_read_only(
+            &options, path, &names, false,
+        )?;
+        stats.push((path.to_path_buf(), column_family_stats(&db)?));
+        return Ok(());
+    }
+    let mut dirs: Vec<_> = std::fs::read_dir(path)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    for dir in dirs {
+        collect_db_stats(&dir, stats)?;
+    }
+    Ok(())
+}
@@ -2,16 +2,34 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use self::db_dump::{dump_table, list_tables, StoreName};
+use self::db_stats::db_stats;
+use anyhow::bail;
 use clap::Parser;
 use std::path::PathBuf;
+use sui_config::genesis::Genesis;
+use sui_core::authority::AuthorityStore;
+use sui_core::checkpoint_verifier::{verify_checkpoint_chain, ChainRoot};
+use sui_core::checkpoints::CheckpointStoreTables;
+use sui_core::db_checker::check_db;
+use sui_core::epoch::committee_store::CommitteeStore;
+use sui_types::base_types::{decode_bytes_hex, encode_bytes_hex};
+use sui_types::messages_checkpoint::CheckpointSequenceNumber;
 
 pub mod db_dump;
+pub mod db_restore;
+pub mod db_stats;
 
 #[derive(Parser)]
 #[clap(rename_all = "kebab-case")]
 pub enum DbToolCommand {
     ListTables,
     Dump(Dump),
+    Check(Check),
+    VerifyCheckpoints(VerifyCheckpoints),
+    /// Print the estimated number of keys, live SST size, memtable size and pending compaction
+    /// bytes of every table of the databases under `--db-path`, which can be a single database or
+    /// the DB of a node.
+    Stats,
 }
 
 #[derive(Parser)]
@@ -31,6 +49,42 @@ pub struct Dump {
     page_number: usize,
 }
 
+/// Check the consistency of the authority and checkpoint stores of a stopped node, and print a
+/// JSON report of the inconsistencies found. `--db-path` is the node DB, the `db-path` of the
+/// node config.
+/// On a node bootstrapped from a state snapshot, the transactions of the snapshot checkpoint are
+/// reported as unknown, since snapshots do not hold them.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct Check {
+    /// The maximum number of inconsistencies to report
+    #[clap(long = "max-inconsistencies", default_value = "1000")]
+    max_inconsistencies: usize,
+}
+
+/// Verify that the checkpoints of a stopped node form a chain certified by the committee of every
+/// epoch, from the genesis or from a trusted checkpoint to the last stored checkpoint, and print
+/// a JSON report with the first failure. `--db-path` is the node DB, the `db-path` of the node
+/// config.
+#[derive(Parser)]
+#[clap(rename_all = "kebab-case")]
+pub struct VerifyCheckpoints {
+    /// The genesis whose committee certifies the first epoch
+    #[clap(long = "genesis", required_unless_present = "trusted_checkpoint")]
+    genesis: Option<PathBuf>,
+    /// The sequence number of a checkpoint known to be valid, to start from instead of the
+    /// genesis, e.g. the last checkpoint of a previous verification
+    #[clap(
+        long = "trusted-checkpoint",
+        requires = "trusted_digest",
+        conflicts_with = "genesis"
+    )]
+    trusted_checkpoint: Option<CheckpointSequenceNumber>,
+    /// The hex digest of the trusted checkpoint
+    #[clap(long = "trusted-digest")]
+    trusted_digest: Option<String>,
+}
+
 pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::Result<()> {
     match cmd {
         DbToolCommand::ListTables => print_db_all_tables(db_path),
@@ -41,6 +95,9 @@ pub fn execute_db_tool_command(db_path: PathBuf, cmd: DbToolCommand) -> anyhow::
             d.page_size,
             d.page_number,
         ),
+        DbToolCommand::Check(c) => check_db_consistency(db_path, c.max_inconsistencies),
+        DbToolCommand::VerifyCheckpoints(v) => verify_checkpoints(db_path, v),
+        DbToolCommand::Stats => print_db_stats(db_path),
     }
 }
 
@@ -49,6 +106,71 @@ pub fn print_db_all_tables(db_path: PathBuf) -> anyhow::Result<()> {
     Ok(())
 }
 
+pub fn check_db_consistency(db_path: PathBuf, max_inconsistencies: usize) -> anyhow::Result<()> {
+    let store = AuthorityStore::open(&db_path.join("store"), None);
+    let checkpoints =
+        CheckpointStoreTables::open_tables_read_write(db_path.join("checkpoints"), None, None);
+    let report = check_db(&store, &checkpoints, max_inconsistencies)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !report.is_consistent() {
+        bail!(
+            "Found {} inconsistencies in {:?}",
+            report.inconsistency_count,
+            db_path
+        );
+    }
+    Ok(())
+}
+
+pub fn verify_checkpoints(db_path: PathBuf, cmd: VerifyCheckpoints) -> anyhow::Result<()> {
+    let root = match (cmd.genesis, cmd.trusted_checkpoint, cmd.trusted_digest) {
+        (_, Some(sequence_number), Some(digest)) => ChainRoot::Trusted {
+            sequence_number,
+            digest: decode_bytes_hex(&digest)?,
+        },
+        (Some(genesis), _, _) => ChainRoot::Genesis(Genesis::load(genesis)?.committee()?),
+        _ => bail!("Either --genesis or --trusted-checkpoint and --trusted-digest are needed"),
+    };
+    let checkpoints =
+        CheckpointStoreTables::open_tables_read_write(db_path.join("checkpoints"), None, None);
+    let committees = CommitteeStore::open_tables_read_write(db_path.join("epochs"), None, None);
+    let report = verify_checkpoint_chain(&checkpoints, &committees, &root)?;
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if let Some(last) = &report.last_verified {
+        println!(
+            "Last verified checkpoint: {} of epoch {}, digest 0x{}",
+            last.sequence_number,
+            last.epoch,
+            encode_bytes_hex(&last.digest)
+        );
+    }
+    if let Some(failure) = &report.failure {
+        bail!("Invalid checkpoint chain in {:?}: {:?}", db_path, failure);
+    }
+    Ok(())
+}
+
+pub fn print_db_stats(db_path: PathBuf) -> anyhow::Result<()> {
+    println!(
+        "{:<40} {:>16} {:>16} {:>16} {:>16}",
+        "table", "estimated keys", "live sst bytes", "memtable bytes", "pending compaction"
+    );
+    for (path, tables) in db_stats(&db_path)? {
+        let db = path.strip_prefix(&db_path).unwrap_or(&path);
+        for table in tables {
+            println!(
+                "{:<40} {:>16} {:>16} {:>16} {:>16}",
+                db.join(&table.name).display(),
+                table.estimated_keys,
+                table.live_sst_size,
+                table.memtable_size,
+                table.pending_compaction_bytes
+            );
+        }
+    }
+    Ok(())
+}
+
 pub fn print_all_entries(
     store: StoreName,
     path: PathBuf,
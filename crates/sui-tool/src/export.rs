@@ -0,0 +1,341 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Export of the ledger of a node DB to JSON Lines or Parquet files, one file per chunk of
+//! transactions in each of the `transactions`, `object_changes` and `events` directories.
+//! The export can be interrupted and resumed: `progress.json` records the first transaction not
+//! exported yet, and chunk files are only complete once renamed.
+
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::bail;
+use arrow::array::{ArrayRef, StringArray, UInt64Array};
+use arrow::datatypes::{DataType, Field, Schema};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::ArrowWriter;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use sui_core::ledger_export::{LedgerChunk, LedgerExporter};
+use sui_types::batch::TxSequenceNumber;
+
+#[cfg(test)]
+#[path = "unit_tests/export_tests.rs"]
+mod export_tests;
+
+/// Number of transactions exported to each file.
+pub const EXPORT_CHUNK_SIZE: u64 = 10_000;
+
+const PROGRESS_FILE: &str = "progress.json";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// One JSON record per line
+    Jsonl,
+    /// One Parquet column per scalar field of the records, nested fields as JSON strings
+    Parquet,
+}
+
+impl ExportFormat {
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Parquet => "parquet",
+        }
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "jsonl" => Ok(ExportFormat::Jsonl),
+            "parquet" => Ok(ExportFormat::Parquet),
+            _ => bail!("Unknown export format {s}, expected jsonl or parquet"),
+        }
+    }
+}
+
+/// The ledger an export reads its chunks from, the one of a node DB read by a [`LedgerExporter`].
+pub trait LedgerSource {
+    /// The sequence number where an export of the whole ledger stops.
+    fn next_sequence_number(&self) -> anyhow::Result<TxSequenceNumber>;
+
+    /// Reads the records of the transactions at sequence numbers `[start, end)`.
+    fn export_range(
+        &self,
+        start: TxSequenceNumber,
+        end: TxSequenceNumber,
+    ) -> anyhow::Result<LedgerChunk>;
+}
+
+impl LedgerSource for LedgerExporter {
+    fn next_sequence_number(&self) -> anyhow::Result<TxSequenceNumber> {
+        Ok(LedgerExporter::next_sequence_number(self)?)
+    }
+
+    fn export_range(
+        &self,
+        start: TxSequenceNumber,
+        end: TxSequenceNumber,
+    ) -> anyhow::Result<LedgerChunk> {
+        LedgerExporter::export_range(self, start, end)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportProgress {
+    format: ExportFormat,
+    next_seq: TxSequenceNumber,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ExportSummary {
+    pub start: TxSequenceNumber,
+    pub end: TxSequenceNumber,
+    pub transactions: u64,
+    pub object_changes: u64,
+    pub events: u64,
+}
+
+#[derive(Clone, Copy)]
+enum ColumnType {
+    UInt64,
+    /// Strings, and other JSON values serialized to strings
+    Utf8,
+}
+
+/// A Parquet column, read from the JSON record at `pointer`.
+struct Column {
+    name: &'static str,
+    pointer: &'static str,
+    column_type: ColumnType,
+}
+
+const fn column(name: &'static str, pointer: &'static str, column_type: ColumnType) -> Column {
+    Column {
+        name,
+        pointer,
+        column_type,
+    }
+}
+
+const TRANSACTION_COLUMNS: &[Column] = &[
+    column("seq", "/seq", ColumnType::UInt64),
+    column("transactionDigest", "/transactionDigest", ColumnType::Utf8),
+    column("effectsDigest", "/effectsDigest", ColumnType::Utf8),
+    column("checkpoint", "/checkpoint", ColumnType::UInt64),
+    column("timestampMs", "/timestampMs", ColumnType::UInt64),
+    column("sender", "/sender", ColumnType::Utf8),
+    column("status", "/status/status", ColumnType::Utf8),
+    column("error", "/status/error", ColumnType::Utf8),
+    column(
+        "computationCost",
+        "/gasUsed/computationCost",
+        ColumnType::UInt64,
+    ),
+    column("storageCost", "/gasUsed/storageCost", ColumnType::UInt64),
+    column(
+        "storageRebate",
+        "/gasUsed/storageRebate",
+        ColumnType::UInt64,
+    ),
+    column("gasBudget", "/data/gasBudget", ColumnType::UInt64),
+    column("transactions", "/data/transactions", ColumnType::Utf8),
+    column("dependencies", "/dependencies", ColumnType::Utf8),
+];
+
+const OBJECT_CHANGE_COLUMNS: &[Column] = &[
+    column("seq", "/seq", ColumnType::UInt64),
+    column("transactionDigest", "/transactionDigest", ColumnType::Utf8),
+    column("timestampMs", "/timestampMs", ColumnType::UInt64),
+    column("kind", "/kind", ColumnType::Utf8),
+    column("objectId", "/objectId", ColumnType::Utf8),
+    column("version", "/version", ColumnType::UInt64),
+    column("digest", "/digest", ColumnType::Utf8),
+    column("owner", "/owner", ColumnType::Utf8),
+];
+
+const EVENT_COLUMNS: &[Column] = &[
+    column("txSeq", "/id/txSeq", ColumnType::UInt64),
+    column("eventSeq", "/id/eventSeq", ColumnType::UInt64),
+    column("txDigest", "/txDigest", ColumnType::Utf8),
+    column("timestamp", "/timestamp", ColumnType::UInt64),
+    column("event", "/event", ColumnType::Utf8),
+];
+
+/// Exports the transactions at sequence numbers `[start, end)`, `end` being the next transaction
+/// to execute by default, to `output`. If `output` holds the progress of a previous export, the
+/// export resumes from it instead of `start`.
+pub fn export_ledger(
+    exporter: &impl LedgerSource,
+    output: &Path,
+    format: ExportFormat,
+    start: Option<TxSequenceNumber>,
+    end: Option<TxSequenceNumber>,
+    mut on_chunk: impl FnMut(&LedgerChunk),
+) -> anyhow::Result<ExportSummary> {
+    for dir in ["transactions", "object_changes", "events"] {
+        fs::create_dir_all(output.join(dir))?;
+    }
+    let progress_path = output.join(PROGRESS_FILE);
+    let start = if progress_path.exists() {
+        let progress: ExportProgress = serde_json::from_reader(File::open(&progress_path)?)?;
+        if progress.format != format {
+            bail!(
+                "{:?} holds a {:?} export, which cannot be resumed as {:?}",
+                output,
+                progress.format,
+                format
+            );
+        }
+        if start.map_or(false, |start| start != progress.next_seq) {
+            bail!(
+                "The export in {:?} resumes from {}, remove {:?} to start over",
+                output,
+                progress.next_seq,
+                progress_path
+            );
+        }
+        progress.next_seq
+    } else {
+        start.unwrap_or(0)
+    };
+    let end = match end {
+        Some(end) => end,
+        None => exporter.next_sequence_number()?,
+    };
+
+    let mut summary = ExportSummary {
+        start,
+        end,
+        ..Default::default()
+    };
+    let mut next_seq = start;
+    while next_seq < end {
+        let chunk_end = end.min(next_seq.saturating_add(EXPORT_CHUNK_SIZE));
+        let chunk = exporter.export_range(next_seq, chunk_end)?;
+        let file_name = format!("{:020}-{:020}.{}", next_seq, chunk_end, format.extension());
+        write_records(
+            &output.join("transactions").join(&file_name),
+            &chunk.transactions,
+            TRANSACTION_COLUMNS,
+            format,
+        )?;
+        write_records(
+            &output.join("object_changes").join(&file_name),
+            &chunk.object_changes,
+            OBJECT_CHANGE_COLUMNS,
+            format,
+        )?;
+        write_records(
+            &output.join("events").join(&file_name),
+            &chunk.events,
+            EVENT_COLUMNS,
+            format,
+        )?;
+        next_seq = chunk_end;
+        write_atomically(&progress_path, |file| {
+            Ok(serde_json::to_writer_pretty(
+                file,
+                &ExportProgress { format, next_seq },
+            )?)
+        })?;
+        summary.transactions += chunk.transactions.len() as u64;
+        summary.object_changes += chunk.object_changes.len() as u64;
+        summary.events += chunk.events.len() as u64;
+        on_chunk(&chunk);
+    }
+    Ok(summary)
+}
+
+/// Writes the records of a chunk to `path`, unless there are none.
+fn write_records<T: Serialize>(
+    path: &Path,
+    records: &[T],
+    columns: &[Column],
+    format: ExportFormat,
+) -> anyhow::Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    write_atomically(path, |file| match format {
+        ExportFormat::Jsonl => {
+            let mut writer = BufWriter::new(file);
+            for record in records {
+                serde_json::to_writer(&mut writer, record)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+            Ok(())
+        }
+        ExportFormat::Parquet => write_parquet(file, records, columns),
+    })
+}
+
+fn write_parquet<T: Serialize>(
+    file: File,
+    records: &[T],
+    columns: &[Column],
+) -> anyhow::Result<()> {
+    let records = records
+        .iter()
+        .map(serde_json::to_value)
+        .collect::<Result<Vec<_>, _>>()?;
+    let schema = Arc::new(Schema::new(
+        columns
+            .iter()
+            .map(|column| {
+                let data_type = match column.column_type {
+                    ColumnType::UInt64 => DataType::UInt64,
+                    ColumnType::Utf8 => DataType::Utf8,
+                };
+                Field::new(column.name, data_type, true)
+            })
+            .collect(),
+    ));
+    let arrays = columns
+        .iter()
+        .map(|column| -> ArrayRef {
+            let values = records.iter().map(|record| record.pointer(column.pointer));
+            match column.column_type {
+                ColumnType::UInt64 => Arc::new(
+                    values
+                        .map(|value| value.and_then(Value::as_u64))
+                        .collect::<UInt64Array>(),
+                ),
+                ColumnType::Utf8 => Arc::new(
+                    values
+                        .map(|value| match value {
+                            None | Some(Value::Null) => None,
+                            Some(Value::String(value)) => Some(value.clone()),
+                            Some(value) => Some(value.to_string()),
+                        })
+                        .collect::<StringArray>(),
+                ),
+            }
+        })
+        .collect();
+    let batch = RecordBatch::try_new(schema.clone(), arrays)?;
+    let mut writer = ArrowWriter::try_new(file, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Writes a file next to `path` and renames it to `path` once complete, so that `path` is never
+/// partially written.
+fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(File) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let tmp_path = path.with_extension("tmp");
+    write(File::create(&tmp_path)?)?;
+    fs::rename(&tmp_path, path)?;
+    Ok(())
+}
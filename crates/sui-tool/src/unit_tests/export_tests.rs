@@ -0,0 +1,222 @@
+// Copyright (c) Mysten Labs, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+use std::io::{BufRead, BufReader};
+
+use arrow::array::Array;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde_json::json;
+use sui_core::ledger_export::{ObjectChangeRecord, TransactionRecord};
+use sui_types::base_types::{
+    dbg_addr, ObjectDigest, ObjectID, SequenceNumber, TransactionDigest, TransactionEffectsDigest,
+};
+use sui_types::object::Owner;
+
+use super::*;
+
+/// A ledger with one transaction, which creates an object, in every chunk of an export. Reading
+/// the transactions from `fail_from` fails, like an interrupted export.
+struct TestLedger {
+    end: TxSequenceNumber,
+    fail_from: Option<TxSequenceNumber>,
+}
+
+impl LedgerSource for TestLedger {
+    fn next_sequence_number(&self) -> anyhow::Result<TxSequenceNumber> {
+        Ok(self.end)
+    }
+
+    fn export_range(
+        &self,
+        start: TxSequenceNumber,
+        end: TxSequenceNumber,
+    ) -> anyhow::Result<LedgerChunk> {
+        if self.fail_from.map_or(false, |fail_from| end > fail_from) {
+            bail!("Interrupted");
+        }
+        let seqs: Vec<_> = (start..end)
+            .filter(|seq| seq % EXPORT_CHUNK_SIZE == 5)
+            .collect();
+        Ok(LedgerChunk {
+            start,
+            end,
+            transactions: seqs.iter().map(|seq| transaction(*seq)).collect(),
+            object_changes: seqs.iter().map(|seq| object_change(*seq)).collect(),
+            events: vec![],
+        })
+    }
+}
+
+fn object_ref_json() -> Value {
+    json!({
+        "objectId": ObjectID::random(),
+        "version": SequenceNumber::from(1),
+        "digest": ObjectDigest::random(),
+    })
+}
+
+fn transaction(seq: TxSequenceNumber) -> TransactionRecord {
+    serde_json::from_value(json!({
+        "seq": seq,
+        "transactionDigest": TransactionDigest::random(),
+        "effectsDigest": TransactionEffectsDigest::random(),
+        "checkpoint": null,
+        "timestampMs": seq * 1000,
+        "sender": dbg_addr(1),
+        "status": { "status": "success" },
+        "gasUsed": { "computationCost": 10, "storageCost": 20, "storageRebate": 5 },
+        "data": {
+            "transactions": [],
+            "sender": dbg_addr(1),
+            "gasPayment": object_ref_json(),
+            "gasBudget": 1000,
+        },
+        "dependencies": [],
+    }))
+    .unwrap()
+}
+
+fn object_change(seq: TxSequenceNumber) -> ObjectChangeRecord {
+    let mut record = object_ref_json();
+    record["seq"] = json!(seq);
+    record["transactionDigest"] = json!(TransactionDigest::random());
+    record["timestampMs"] = json!(seq * 1000);
+    record["kind"] = json!("created");
+    record["owner"] = json!(Owner::AddressOwner(dbg_addr(2)));
+    serde_json::from_value(record).unwrap()
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    names.sort();
+    names
+}
+
+fn read_progress(output: &Path) -> ExportProgress {
+    serde_json::from_reader(File::open(output.join(PROGRESS_FILE)).unwrap()).unwrap()
+}
+
+#[test]
+fn test_resume_interrupted_export() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path();
+    let end = 3 * EXPORT_CHUNK_SIZE;
+
+    // The export is interrupted in its third chunk
+    let interrupted = TestLedger {
+        end,
+        fail_from: Some(2 * EXPORT_CHUNK_SIZE),
+    };
+    assert!(export_ledger(
+        &interrupted,
+        output,
+        ExportFormat::Jsonl,
+        None,
+        None,
+        |_| {}
+    )
+    .is_err());
+    let progress = read_progress(output);
+    assert_eq!(progress.format, ExportFormat::Jsonl);
+    assert_eq!(progress.next_seq, 2 * EXPORT_CHUNK_SIZE);
+    assert_eq!(file_names(&output.join("transactions")).len(), 2);
+
+    // It only resumes in the same format, from where it stopped
+    let ledger = TestLedger {
+        end,
+        fail_from: None,
+    };
+    assert!(export_ledger(&ledger, output, ExportFormat::Parquet, None, None, |_| {}).is_err());
+    assert!(export_ledger(&ledger, output, ExportFormat::Jsonl, Some(0), None, |_| {}).is_err());
+
+    let summary = export_ledger(&ledger, output, ExportFormat::Jsonl, None, None, |_| {}).unwrap();
+    assert_eq!(summary.start, 2 * EXPORT_CHUNK_SIZE);
+    assert_eq!(summary.end, end);
+    assert_eq!(summary.transactions, 1);
+    assert_eq!(summary.object_changes, 1);
+    assert_eq!(read_progress(output).next_seq, end);
+
+    // Every chunk is exported once, and no partial file is left
+    for records in ["transactions", "object_changes"] {
+        assert_eq!(
+            file_names(&output.join(records)),
+            (0..3)
+                .map(|i| format!(
+                    "{:020}-{:020}.jsonl",
+                    i * EXPORT_CHUNK_SIZE,
+                    (i + 1) * EXPORT_CHUNK_SIZE
+                ))
+                .collect::<Vec<_>>()
+        );
+    }
+    let mut seqs = Vec::new();
+    for name in file_names(&output.join("transactions")) {
+        let file = File::open(output.join("transactions").join(name)).unwrap();
+        for line in BufReader::new(file).lines() {
+            let record: TransactionRecord = serde_json::from_str(&line.unwrap()).unwrap();
+            seqs.push(record.seq);
+        }
+    }
+    assert_eq!(
+        seqs,
+        vec![5, EXPORT_CHUNK_SIZE + 5, 2 * EXPORT_CHUNK_SIZE + 5]
+    );
+    assert!(!output.join("events").read_dir().unwrap().any(|_| true));
+
+    // A completed export has nothing left to export
+    let summary = export_ledger(&ledger, output, ExportFormat::Jsonl, None, None, |_| {}).unwrap();
+    assert_eq!(summary.transactions, 0);
+}
+
+#[test]
+fn test_export_to_parquet() {
+    let dir = tempfile::tempdir().unwrap();
+    let output = dir.path();
+    let ledger = TestLedger {
+        end: EXPORT_CHUNK_SIZE,
+        fail_from: None,
+    };
+    export_ledger(&ledger, output, ExportFormat::Parquet, None, None, |_| {}).unwrap();
+
+    let path = output
+        .join("transactions")
+        .join(format!("{:020}-{:020}.parquet", 0, EXPORT_CHUNK_SIZE));
+    let batches = ParquetRecordBatchReaderBuilder::try_new(File::open(path).unwrap())
+        .unwrap()
+        .build()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(batches.len(), 1);
+    let batch = &batches[0];
+    assert_eq!(batch.num_rows(), 1);
+    let schema = batch.schema();
+    let column = |name: &str| batch.column(schema.index_of(name).unwrap()).clone();
+    let u64_value = |name: &str| {
+        let column = column(name);
+        column
+            .as_any()
+            .downcast_ref::<UInt64Array>()
+            .unwrap()
+            .value(0)
+    };
+    let string_value = |name: &str| {
+        let column = column(name);
+        let strings = column.as_any().downcast_ref::<StringArray>().unwrap();
+        (!strings.is_null(0)).then(|| strings.value(0).to_string())
+    };
+
+    // Scalar fields, including nested ones, are columns, other fields are JSON strings
+    assert_eq!(u64_value("seq"), 5);
+    assert_eq!(u64_value("timestampMs"), 5000);
+    assert_eq!(u64_value("computationCost"), 10);
+    assert_eq!(u64_value("gasBudget"), 1000);
+    assert!(string_value("transactionDigest").is_some());
+    assert_eq!(string_value("status"), Some("success".to_string()));
+    assert_eq!(string_value("error"), None);
+    assert_eq!(string_value("transactions"), Some("[]".to_string()));
+    assert!(column("checkpoint").is_null(0));
+}
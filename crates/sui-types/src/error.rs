@@ -309,6 +309,15 @@ pub enum SuiError {
         asked_version: SequenceNumber,
         latest_version: SequenceNumber,
     },
+    #[error(
+        "Version {:?} of object {:?} has been pruned from the object store",
+        version,
+        object_id
+    )]
+    ObjectVersionPruned {
+        object_id: ObjectID,
+        version: SequenceNumber,
+    },
     #[error("Object deleted at reference {:?}.", object_ref)]
     ObjectDeleted { object_ref: ObjectRef },
     #[error("Object ID did not have the expected type")]
@@ -346,6 +355,8 @@ pub enum SuiError {
     StorageMissingFieldError(String),
     #[error("Corrupted fields/data in storage error: {0}")]
     StorageCorruptedFieldError(String),
+    #[error("Invalid state snapshot: {0}")]
+    InvalidStateSnapshot(String),
 
     #[error("Batch error: cannot send transaction to batch.")]
     BatchErrorSender,
@@ -454,6 +465,20 @@ pub enum SuiError {
 
     #[error("Failed to get supermajority's consensus on committee information for minimal epoch: {minimal_epoch}")]
     FailedToGetAgreedCommitteeFromMajority { minimal_epoch: EpochId },
+
+    #[error("Invalid event filter: {0}")]
+    InvalidEventFilter(String),
+
+    #[error("Events before sequence number {oldest_retained_seq_num} have been pruned from the event store, cannot query from sequence number {cursor_seq_num}")]
+    EventsPruned {
+        cursor_seq_num: u64,
+        oldest_retained_seq_num: u64,
+    },
+    #[error("Events before sequence number {oldest_retained_seq_num} have been pruned from the event store, cannot query from timestamp {start_time}")]
+    EventTimeRangePruned {
+        start_time: u64,
+        oldest_retained_seq_num: u64,
+    },
 }
 
 pub type SuiResult<T = ()> = Result<T, SuiError>;
@@ -3,6 +3,7 @@
 
 use std::str::FromStr;
 
+use crate::batch::TxSequenceNumber;
 use crate::error::SuiError;
 use crate::object::MoveObject;
 use crate::object::ObjectFormatOptions;
@@ -37,6 +38,8 @@ pub struct EventEnvelope {
     pub tx_digest: Option<TransactionDigest>,
     /// Sequence number, must be nondecreasing for event ingestion idempotency
     pub seq_num: u64,
+    /// Consecutive per-tx counter assigned to this event.
+    pub event_num: u64,
     /// Specific event type
     pub event: Event,
     /// json value for MoveStruct (for MoveEvent only)
@@ -48,6 +51,7 @@ impl EventEnvelope {
         timestamp: u64,
         tx_digest: Option<TransactionDigest>,
         seq_num: u64,
+        event_num: u64,
         event: Event,
         move_struct_json_value: Option<Value>,
     ) -> Self {
@@ -55,6 +59,7 @@ impl EventEnvelope {
             timestamp,
             tx_digest,
             seq_num,
+            event_num,
             event,
             move_struct_json_value,
         }
@@ -63,6 +68,31 @@ impl EventEnvelope {
     pub fn event_type(&self) -> &'static str {
         self.event.variant_name()
     }
+
+    pub fn id(&self) -> EventID {
+        EventID {
+            tx_seq: self.seq_num,
+            event_seq: self.event_num,
+        }
+    }
+}
+
+/// Unique ID of a Sui Event, the ID is a combination of tx sequence number and event sequence number.
+/// The ID is local to this particular fullnode and can differ from the ID of the same event on
+/// other fullnodes.
+#[derive(
+    Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct EventID {
+    pub tx_seq: TxSequenceNumber,
+    pub event_seq: u64,
+}
+
+impl From<(TxSequenceNumber, u64)> for EventID {
+    fn from((tx_seq, event_seq): (TxSequenceNumber, u64)) -> Self {
+        Self { tx_seq, event_seq }
+    }
 }
 
 #[derive(
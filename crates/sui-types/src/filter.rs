@@ -6,6 +6,7 @@ use move_core_types::language_storage::StructTag;
 use serde_json::Value;
 
 use crate::base_types::SuiAddress;
+use crate::batch::TxSequenceNumber;
 use crate::event::EventType;
 use crate::event::{Event, EventEnvelope};
 use crate::messages::TxCertAndSignedEffects;
@@ -89,6 +90,12 @@ impl Filter<TxCertAndSignedEffects> for TransactionFilter {
     }
 }
 
+impl Filter<(TxSequenceNumber, TxCertAndSignedEffects)> for TransactionFilter {
+    fn matches(&self, (_seq, item): &(TxSequenceNumber, TxCertAndSignedEffects)) -> bool {
+        Filter::<TxCertAndSignedEffects>::matches(self, item)
+    }
+}
+
 pub trait Filter<T> {
     fn matches(&self, item: &T) -> bool;
 }
@@ -662,6 +662,8 @@ pub enum PastObjectRead {
     VersionFound(ObjectRef, Object, Option<MoveStructLayout>),
     /// The object exists but not found with this version
     VersionNotFound(ObjectID, SequenceNumber),
+    /// The object existed with this version, but it was superseded and pruned from the store
+    VersionPruned(ObjectID, SequenceNumber),
     /// The asked object version is higher than the latest
     VersionTooHigh {
         object_id: ObjectID,
@@ -680,6 +682,9 @@ impl PastObjectRead {
             Self::VersionNotFound(object_id, version) => {
                 Err(SuiError::ObjectVersionNotFound { object_id, version })
             }
+            Self::VersionPruned(object_id, version) => {
+                Err(SuiError::ObjectVersionPruned { object_id, version })
+            }
             Self::VersionTooHigh {
                 object_id,
                 asked_version,
@@ -712,6 +717,13 @@ impl Display for PastObjectRead {
                     object_id, version
                 )
             }
+            Self::VersionPruned(object_id, version) => {
+                write!(
+                    f,
+                    "PastObjectRead::VersionPruned ({:?}, asked sequence number {:?})",
+                    object_id, version
+                )
+            }
             Self::VersionTooHigh {
                 object_id,
                 asked_version,
@@ -1,10 +1,13 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
-use crate::base_types::SuiAddress;
+use crate::base_types::{SuiAddress, TransactionDigest};
+use crate::event::EventType;
+use crate::object::Owner;
 use crate::ObjectID;
 use schemars::JsonSchema;
 use serde::Deserialize;
 use serde::Serialize;
+use serde_json::Value;
 
 #[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
 pub enum TransactionQuery {
@@ -33,3 +36,42 @@ pub enum Ordering {
     // Descending order (latest transaction first), transactions are causal ordered.
     Descending,
 }
+
+#[derive(Clone, Debug, JsonSchema, Serialize, Deserialize)]
+pub enum EventQuery {
+    // Return all events.
+    All,
+    // Return events emitted by the given transaction.
+    Transaction(TransactionDigest),
+    // Return events emitted in a specified Move module.
+    MoveModule {
+        // the Move package ID
+        package: ObjectID,
+        // the module name
+        module: String,
+    },
+    // Return events with the given move event struct name, e.g. `0x2::devnet_nft::MintNFTEvent`.
+    MoveEvent(String),
+    // Return events with the given move event struct name whose field at `path`, a JSON pointer
+    // such as `/creator`, equals `value`.
+    MoveEventField {
+        struct_name: String,
+        path: String,
+        value: Value,
+    },
+    // Return events of the given event type.
+    EventType(EventType),
+    // Return events associated with the given sender.
+    Sender(SuiAddress),
+    // Return events associated with the given recipient.
+    Recipient(Owner),
+    // Return events associated with the given object.
+    Object(ObjectID),
+    // Return events emitted in [start_time, end_time) interval.
+    TimeRange {
+        // left endpoint of time interval, milliseconds since epoch, inclusive
+        start_time: u64,
+        // right endpoint of time interval, milliseconds since epoch, exclusive
+        end_time: u64,
+    },
+}
@@ -39,7 +39,7 @@ use sui_types::{
     parse_sui_type_tag, SUI_FRAMEWORK_ADDRESS,
 };
 
-use crate::config::{Config, PersistedConfig, SuiClientConfig};
+use crate::config::{Config, PersistedConfig, SuiClientConfig, SuiEnv};
 
 pub const EXAMPLE_NFT_NAME: &str = "Example NFT";
 pub const EXAMPLE_NFT_DESCRIPTION: &str = "An NFT created by the Sui Command Line Tool";
@@ -63,6 +63,31 @@ pub enum SuiClientCommands {
         /// The pubsub Websocket server URL
         #[clap(long, value_hint = ValueHint::Url)]
         ws: Option<String>,
+        /// The alias of an environment of the client config, which sets the RPC and Websocket
+        /// server URLs and the active address last used in it.
+        #[clap(long, conflicts_with_all = &["rpc", "ws"])]
+        env: Option<String>,
+    },
+
+    /// List the environments of the client config
+    #[clap(name = "envs")]
+    Envs,
+
+    /// Add a new environment to the client config
+    #[clap(name = "new-env")]
+    NewEnv {
+        /// The name used to switch to the environment
+        #[clap(long)]
+        alias: String,
+        /// The RPC server URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        rpc: String,
+        /// The pubsub Websocket server URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        ws: Option<String>,
+        /// The faucet URL of the environment
+        #[clap(long, value_hint = ValueHint::Url)]
+        faucet: Option<String>,
     },
 
     /// Default address used for commands when none specified
@@ -558,23 +583,62 @@ impl SuiClientCommands {
 
                 SuiClientCommandResult::MergeCoin(response)
             }
-            SuiClientCommands::Switch { address, rpc, ws } => {
+            SuiClientCommands::Switch {
+                address,
+                rpc,
+                ws,
+                env,
+            } => {
+                if let Some(env) = &env {
+                    context.config.switch_env(env)?;
+                }
+
                 if let Some(addr) = address {
                     if !context.config.keystore.addresses().contains(&addr) {
                         return Err(anyhow!("Address {} not managed by wallet", addr));
                     }
-                    context.config.active_address = Some(addr);
+                    context.config.set_active_address(addr);
                 }
 
                 Self::switch_server(&mut context.config, &rpc, &ws)?;
 
-                if Option::is_none(&address) && Option::is_none(&rpc) && Option::is_none(&ws) {
+                if Option::is_none(&address)
+                    && Option::is_none(&rpc)
+                    && Option::is_none(&ws)
+                    && Option::is_none(&env)
+                {
                     return Err(anyhow!(
-                        "No address or RPC url specified. Please Specify one."
+                        "No address, environment or RPC url specified. Please Specify one."
                     ));
                 }
                 context.config.save()?;
-                SuiClientCommandResult::Switch(SwitchResponse { address, rpc, ws })
+                SuiClientCommandResult::Switch(SwitchResponse {
+                    address,
+                    rpc,
+                    ws,
+                    env,
+                })
+            }
+            SuiClientCommands::Envs => SuiClientCommandResult::Envs(
+                context.config.envs.clone(),
+                context.config.active_env.clone(),
+            ),
+            SuiClientCommands::NewEnv {
+                alias,
+                rpc,
+                ws,
+                faucet,
+            } => {
+                let env = SuiEnv {
+                    alias,
+                    rpc,
+                    ws,
+                    faucet,
+                    active_address: None,
+                };
+                context.config.add_env(env.clone())?;
+                context.config.save()?;
+                SuiClientCommandResult::NewEnv(env)
             }
             SuiClientCommands::ActiveAddress => {
                 SuiClientCommandResult::ActiveAddress(context.active_address().ok())
@@ -624,6 +688,11 @@ impl SuiClientCommands {
         rpc: &Option<String>,
         ws: &Option<String>,
     ) -> Result<(), anyhow::Error> {
+        // The servers are no longer the ones of the active environment.
+        if rpc.is_some() || ws.is_some() {
+            config.leave_env();
+        }
+
         if let Some(rpc) = rpc {
             let ws = match &config.client_type {
                 ClientType::RPC(_, Some(ws)) => Some(ws.clone()),
@@ -909,6 +978,35 @@ impl Display for SuiClientCommandResult {
             SuiClientCommandResult::Switch(response) => {
                 write!(writer, "{}", response)?;
             }
+            SuiClientCommandResult::Envs(envs, active_env) => {
+                writeln!(
+                    writer,
+                    " {0: ^10} | {1: ^40} | {2: ^40} | {3: ^40} | {4: ^6}",
+                    "Alias", "RPC URL", "WS URL", "Faucet URL", "Active"
+                )?;
+                writeln!(writer, "{}", ["-"; 150].join(""))?;
+                for env in envs {
+                    let active = if active_env.as_ref() == Some(&env.alias) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    writeln!(
+                        writer,
+                        " {0: ^10} | {1: ^40} | {2: ^40} | {3: ^40} | {4: ^6}",
+                        env.alias,
+                        env.rpc,
+                        env.ws.as_deref().unwrap_or(""),
+                        env.faucet.as_deref().unwrap_or(""),
+                        active
+                    )?;
+                }
+                writeln!(writer, "Showing {} results.", envs.len())?;
+            }
+            SuiClientCommandResult::NewEnv(env) => {
+                writeln!(writer, "Added new environment [{}]", env.alias)?;
+                writeln!(writer, "{}", env)?;
+            }
             SuiClientCommandResult::ActiveAddress(response) => {
                 match response {
                     Some(r) => write!(writer, "{}", r)?,
@@ -1046,6 +1144,8 @@ pub enum SuiClientCommandResult {
     SplitCoin(SuiTransactionResponse),
     MergeCoin(SuiTransactionResponse),
     Switch(SwitchResponse),
+    Envs(Vec<SuiEnv>, Option<String>),
+    NewEnv(SuiEnv),
     ActiveAddress(Option<SuiAddress>),
     CreateExampleNFT(GetObjectDataResponse),
 }
@@ -1056,11 +1156,16 @@ pub struct SwitchResponse {
     pub address: Option<SuiAddress>,
     pub rpc: Option<String>,
     pub ws: Option<String>,
+    /// Active environment
+    pub env: Option<String>,
 }
 
 impl Display for SwitchResponse {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut writer = String::new();
+        if let Some(env) = &self.env {
+            writeln!(writer, "Active environment switched to [{}]", env)?;
+        }
         if let Some(addr) = self.address {
             writeln!(writer, "Active address switched to {}", addr)?;
         }
@@ -2,6 +2,7 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::anyhow;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::fmt::{Display, Formatter, Write};
@@ -21,6 +22,68 @@ pub struct SuiClientConfig {
     pub keystore: Keystore,
     pub client_type: ClientType,
     pub active_address: Option<SuiAddress>,
+    /// Named networks the client can switch between.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub envs: Vec<SuiEnv>,
+    /// The alias of the environment `client_type` connects to, `None` if the client was
+    /// configured otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_env: Option<String>,
+}
+
+impl SuiClientConfig {
+    pub fn get_env(&self, alias: &str) -> Option<&SuiEnv> {
+        self.envs.iter().find(|env| env.alias == alias)
+    }
+
+    pub fn add_env(&mut self, env: SuiEnv) -> Result<(), anyhow::Error> {
+        if self.get_env(&env.alias).is_some() {
+            return Err(anyhow!("Environment {} already exists", env.alias));
+        }
+        self.envs.push(env);
+        Ok(())
+    }
+
+    /// Connects the client to the environment `alias`, with the active address last used in it
+    /// if it is still managed by the keystore. The active address of the environment left is
+    /// remembered.
+    pub fn switch_env(&mut self, alias: &str) -> Result<(), anyhow::Error> {
+        let env = self
+            .get_env(alias)
+            .cloned()
+            .ok_or_else(|| anyhow!("Environment {} not found", alias))?;
+        self.leave_env();
+        self.client_type = env.client_type();
+        if let Some(address) = env.active_address {
+            if self.keystore.addresses().contains(&address) {
+                self.active_address = Some(address);
+            }
+        }
+        self.active_env = Some(env.alias);
+        Ok(())
+    }
+
+    /// Records the active address in the active environment, and leaves it, before the client
+    /// is connected elsewhere.
+    pub fn leave_env(&mut self) {
+        self.remember_active_address();
+        self.active_env = None;
+    }
+
+    /// Sets the active address, remembered by the active environment.
+    pub fn set_active_address(&mut self, address: SuiAddress) {
+        self.active_address = Some(address);
+        self.remember_active_address();
+    }
+
+    fn remember_active_address(&mut self) {
+        let active_address = self.active_address;
+        if let Some(alias) = &self.active_env {
+            if let Some(env) = self.envs.iter_mut().find(|env| &env.alias == alias) {
+                env.active_address = active_address;
+            }
+        }
+    }
 }
 
 impl Config for SuiClientConfig {}
@@ -39,8 +102,52 @@ impl Display for SuiClientConfig {
             Some(r) => writeln!(writer, "{}", r)?,
             None => writeln!(writer, "None")?,
         };
+        writeln!(
+            writer,
+            "Active environment : {}",
+            self.active_env.as_deref().unwrap_or("None")
+        )?;
         writeln!(writer, "{}", self.keystore)?;
         write!(writer, "{}", self.client_type)?;
         write!(f, "{}", writer)
     }
 }
+
+/// A named network, e.g. devnet or a local network.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SuiEnv {
+    pub alias: String,
+    pub rpc: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ws: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub faucet: Option<String>,
+    /// The active address when the environment was last used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_address: Option<SuiAddress>,
+}
+
+impl SuiEnv {
+    pub fn client_type(&self) -> ClientType {
+        ClientType::RPC(self.rpc.clone(), self.ws.clone())
+    }
+}
+
+impl Display for SuiEnv {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut writer = String::new();
+        writeln!(writer, "Alias : {}", self.alias)?;
+        writeln!(writer, "HTTP RPC URL : {}", self.rpc)?;
+        writeln!(
+            writer,
+            "WS RPC URL : {}",
+            self.ws.as_deref().unwrap_or("None")
+        )?;
+        write!(
+            writer,
+            "Faucet URL : {}",
+            self.faucet.as_deref().unwrap_or("None")
+        )?;
+        write!(f, "{}", writer)
+    }
+}
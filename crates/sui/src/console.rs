@@ -164,6 +164,7 @@ async fn handle_command(
     if matches!(
         result,
         SuiClientCommandResult::Switch(SwitchResponse { rpc: Some(_), .. })
+            | SuiClientCommandResult::Switch(SwitchResponse { env: Some(_), .. })
     ) {
         println!("RPC server switch completed, please restart Sui console.");
         return Ok(true);
@@ -285,6 +285,8 @@ impl SuiCommand {
                     keystore: Keystore::from(keystore),
                     client_type: ClientType::Embedded(wallet_gateway_config),
                     active_address,
+                    envs: vec![],
+                    active_env: None,
                 };
 
                 wallet_config.save(&client_path)?;
@@ -325,9 +327,12 @@ impl SuiCommand {
                 prompt_if_no_config(&config_path).await?;
 
                 // Server switch need to happen before context creation, or else it might fail due to previously misconfigured url.
-                if let Some(SuiClientCommands::Switch { rpc, ws, .. }) = &cmd {
+                if let Some(SuiClientCommands::Switch { rpc, ws, env, .. }) = &cmd {
                     let config: SuiClientConfig = PersistedConfig::read(&config_path)?;
                     let mut config = config.persisted(&config_path);
+                    if let Some(env) = env {
+                        config.switch_env(env)?;
+                    }
                     SuiClientCommands::switch_server(&mut config, rpc, ws)?;
                     // This will init the client to check if the urls are correct and reachable
                     config.client_type.init().await?;
@@ -338,7 +343,11 @@ impl SuiCommand {
 
                 if let Some(cmd) = cmd {
                     // Do not sync if command is a gateway switch, as the current gateway might be unreachable and causes sync to panic.
-                    if !matches!(cmd, SuiClientCommands::Switch { rpc: Some(_), .. }) {
+                    if !matches!(
+                        cmd,
+                        SuiClientCommands::Switch { rpc: Some(_), .. }
+                            | SuiClientCommands::Switch { env: Some(_), .. }
+                    ) {
                         sync_accounts(&mut context).await?;
                     }
                     cmd.execute(&mut context).await?.print(!json);
@@ -422,6 +431,8 @@ async fn prompt_if_no_config(wallet_conf_path: &Path) -> Result<(), anyhow::Erro
                 keystore,
                 client_type: client,
                 active_address: Some(new_address),
+                envs: vec![],
+                active_env: None,
             }
             .persisted(wallet_conf_path)
             .save()?;
@@ -141,6 +141,8 @@ async fn test_addresses_command() -> Result<(), anyhow::Error> {
             ..Default::default()
         }),
         active_address: None,
+        envs: vec![],
+        active_env: None,
     };
     let wallet_conf_path = working_dir.join(SUI_CLIENT_CONFIG);
     let wallet_config = wallet_config.persisted(&wallet_conf_path);
@@ -719,6 +721,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
         address: Some(addr2),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -731,7 +734,8 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(addr2),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None
             })
         )
     );
@@ -758,6 +762,7 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
         address: Some(new_addr),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -769,7 +774,8 @@ async fn test_switch_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(new_addr),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None
             })
         )
     );
@@ -839,6 +845,7 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
         address: Some(addr2),
         rpc: None,
         ws: None,
+        env: None,
     }
     .execute(context)
     .await?;
@@ -849,13 +856,140 @@ async fn test_active_address_command() -> Result<(), anyhow::Error> {
             SuiClientCommandResult::Switch(SwitchResponse {
                 address: Some(addr2),
                 rpc: None,
-                ws: None
+                ws: None,
+                env: None
             })
         )
     );
     Ok(())
 }
 
+#[sim_test]
+async fn test_switch_env_command() -> Result<(), anyhow::Error> {
+    let mut cluster = init_cluster_builder_env_aware().build().await?;
+    let addr1 = cluster.get_address_0();
+    let addr2 = cluster.get_address_1();
+    let context = cluster.wallet_mut();
+    context.config.active_address = Some(addr1);
+
+    for (alias, rpc) in [
+        ("local", "http://127.0.0.1:9000"),
+        ("dev", "http://127.0.0.1:9001"),
+    ] {
+        let resp = SuiClientCommands::NewEnv {
+            alias: alias.to_string(),
+            rpc: rpc.to_string(),
+            ws: None,
+            faucet: Some(format!("{rpc}/gas")),
+        }
+        .execute(context)
+        .await?;
+        assert!(matches!(resp, SuiClientCommandResult::NewEnv(env) if env.alias == alias));
+    }
+    // Aliases are unique
+    assert!(SuiClientCommands::NewEnv {
+        alias: "local".to_string(),
+        rpc: "http://127.0.0.1:9002".to_string(),
+        ws: None,
+        faucet: None,
+    }
+    .execute(context)
+    .await
+    .is_err());
+
+    // Switching to an environment keeps the active address if it has none yet
+    let resp = SuiClientCommands::Switch {
+        address: None,
+        rpc: None,
+        ws: None,
+        env: Some("local".to_string()),
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(
+        format!("{resp}"),
+        format!(
+            "{}",
+            SuiClientCommandResult::Switch(SwitchResponse {
+                address: None,
+                rpc: None,
+                ws: None,
+                env: Some("local".to_string())
+            })
+        )
+    );
+    assert!(matches!(
+        &context.config.client_type,
+        ClientType::RPC(rpc, None) if rpc == "http://127.0.0.1:9000"
+    ));
+    assert_eq!(context.active_address()?, addr1);
+    SuiClientCommands::Switch {
+        address: Some(addr2),
+        rpc: None,
+        ws: None,
+        env: None,
+    }
+    .execute(context)
+    .await?;
+
+    SuiClientCommands::Switch {
+        address: Some(addr1),
+        rpc: None,
+        ws: None,
+        env: Some("dev".to_string()),
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.active_address()?, addr1);
+
+    // The active address of an environment is restored when switching back to it
+    SuiClientCommands::Switch {
+        address: None,
+        rpc: None,
+        ws: None,
+        env: Some("local".to_string()),
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.active_address()?, addr2);
+
+    let resp = SuiClientCommands::Envs.execute(context).await?;
+    let (envs, active_env) = if let SuiClientCommandResult::Envs(envs, active_env) = resp {
+        (envs, active_env)
+    } else {
+        panic!("Command failed")
+    };
+    assert_eq!(active_env.as_deref(), Some("local"));
+    assert_eq!(
+        envs.iter()
+            .map(|env| (env.alias.as_str(), env.active_address))
+            .collect::<Vec<_>>(),
+        vec![("local", Some(addr2)), ("dev", Some(addr1))]
+    );
+
+    // Switching to an RPC server leaves the environment
+    SuiClientCommands::Switch {
+        address: None,
+        rpc: Some("http://127.0.0.1:9003".to_string()),
+        ws: None,
+        env: None,
+    }
+    .execute(context)
+    .await?;
+    assert_eq!(context.config.active_env, None);
+
+    assert!(SuiClientCommands::Switch {
+        address: None,
+        rpc: None,
+        ws: None,
+        env: Some("test".to_string()),
+    }
+    .execute(context)
+    .await
+    .is_err());
+    Ok(())
+}
+
 fn get_gas_value(o: &SuiParsedObject) -> u64 {
     GasCoin::try_from(o).unwrap().value()
 }
@@ -1,15 +1,11 @@
 // Copyright (c) Mysten Labs, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use std::str::FromStr;
 use std::{collections::BTreeMap, sync::Arc};
 
 use futures::future;
 use jsonrpsee::core::client::{ClientT, Subscription, SubscriptionClientT};
 use jsonrpsee::rpc_params;
-use move_core_types::account_address::AccountAddress;
-use move_core_types::identifier::Identifier;
-use move_core_types::language_storage::ModuleId;
 use move_core_types::value::MoveStructLayout;
 use prometheus::Registry;
 use tokio::sync::Mutex;
@@ -17,21 +13,27 @@ use tokio::time::timeout;
 use tokio::time::{sleep, Duration};
 
 use sui::client_commands::{SuiClientCommandResult, SuiClientCommands};
+use sui_config::node::DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE;
 use sui_json_rpc_types::{
-    SuiEvent, SuiEventEnvelope, SuiEventFilter, SuiExecuteTransactionResponse, SuiExecutionStatus,
-    SuiMoveStruct, SuiMoveValue, SuiTransactionFilter, SuiTransactionResponse,
+    Balance, CoinPage, EventPage, GetObjectDataResponse, MultiGetResult, SuiCheckpointContents,
+    SuiCheckpointSummary, SuiEvent, SuiEventEnvelope, SuiEventFilter,
+    SuiExecuteTransactionResponse, SuiExecutionStatus, SuiMoveStruct, SuiMoveValue,
+    SuiTransactionFilter, SuiTransactionResponse, SuiTransactionStreamResponse,
 };
 use sui_macros::*;
 use sui_node::SuiNode;
 use sui_sdk::crypto::AccountKeystore;
 use sui_types::base_types::{ObjectRef, SequenceNumber};
-use sui_types::event::TransferType;
+use sui_types::event::{EventID, TransferType};
+use sui_types::gas_coin::{GasCoin, GAS};
 use sui_types::messages::{
     ExecuteTransactionRequest, ExecuteTransactionRequestType, ExecuteTransactionResponse,
 };
+use sui_types::messages_checkpoint::{CheckpointContents, CheckpointSequenceNumber};
 use sui_types::object::{Object, ObjectRead, Owner, PastObjectRead};
-use sui_types::query::TransactionQuery;
+use sui_types::query::{EventQuery, Ordering, TransactionQuery};
 use sui_types::sui_framework_address_concat_string;
+use sui_types::sui_serde::Base64;
 use sui_types::{
     base_types::{ObjectID, SuiAddress, TransactionDigest},
     messages::TransactionInfoRequest,
@@ -255,7 +257,15 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query all events
     let all_events = node
         .state()
-        .get_events_by_timerange(ts.unwrap() - HOUR_MS, ts.unwrap() + HOUR_MS, 10)
+        .get_events(
+            EventQuery::TimeRange {
+                start_time: ts.unwrap() - HOUR_MS,
+                end_time: ts.unwrap() + HOUR_MS,
+            },
+            None,
+            10,
+            false,
+        )
         .await?;
     assert_eq!(all_events.len(), 1);
     assert_eq!(all_events[0].event, expected_event);
@@ -264,14 +274,17 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by sender
     let events_by_sender = node
         .state()
-        .get_events_by_sender(&sender, ts.unwrap() - HOUR_MS, ts.unwrap() + HOUR_MS, 10)
+        .get_events(EventQuery::Sender(sender), None, 10, false)
         .await?;
     assert_eq!(events_by_sender.len(), 1);
     assert_eq!(events_by_sender[0].event, expected_event);
     assert_eq!(events_by_sender[0].tx_digest.unwrap(), digest);
 
     // query by tx digest
-    let events_by_tx = node.state().get_events_by_transaction(digest, 10).await?;
+    let events_by_tx = node
+        .state()
+        .get_events(EventQuery::Transaction(digest), None, 10, false)
+        .await?;
     assert_eq!(events_by_tx.len(), 1);
     assert_eq!(events_by_tx[0].event, expected_event);
     assert_eq!(events_by_tx[0].tx_digest.unwrap(), digest);
@@ -279,11 +292,11 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by recipient
     let events_by_recipient = node
         .state()
-        .get_events_by_recipient(
-            &Owner::AddressOwner(receiver),
-            ts.unwrap() - HOUR_MS,
-            ts.unwrap() + HOUR_MS,
+        .get_events(
+            EventQuery::Recipient(Owner::AddressOwner(receiver)),
+            None,
             10,
+            false,
         )
         .await?;
     assert_eq!(events_by_recipient.len(), 1);
@@ -293,26 +306,24 @@ async fn test_full_node_indexes() -> Result<(), anyhow::Error> {
     // query by object
     let events_by_object = node
         .state()
-        .get_events_by_object(
-            &transferred_object,
-            ts.unwrap() - HOUR_MS,
-            ts.unwrap() + HOUR_MS,
-            10,
-        )
+        .get_events(EventQuery::Object(transferred_object), None, 10, false)
         .await?;
     assert_eq!(events_by_object.len(), 1);
     assert_eq!(events_by_object[0].event, expected_event);
     assert_eq!(events_by_object[0].tx_digest.unwrap(), digest);
 
     // query by transaction module
-    // Query by module ID
-    let mod_id = ModuleId::new(
-        AccountAddress::from(ObjectID::from_hex_literal("0x2").unwrap()),
-        Identifier::from_str("native").unwrap(),
-    );
     let events_by_module = node
         .state()
-        .get_events_by_transaction_module(&mod_id, ts.unwrap() - HOUR_MS, ts.unwrap() + HOUR_MS, 10)
+        .get_events(
+            EventQuery::MoveModule {
+                package: ObjectID::from_hex_literal("0x2").unwrap(),
+                module: "native".to_string(),
+            },
+            None,
+            10,
+            false,
+        )
         .await?;
     assert_eq!(events_by_module.len(), 1);
     assert_eq!(events_by_module[0].event, expected_event);
@@ -450,7 +461,7 @@ async fn test_full_node_transaction_streaming_basic() -> Result<(), anyhow::Erro
     let ws_client = fullnode.ws_client.as_ref().unwrap();
     let node = fullnode.sui_node;
 
-    let mut sub: Subscription<SuiTransactionResponse> = ws_client
+    let mut sub: Subscription<SuiTransactionStreamResponse> = ws_client
         .subscribe(
             "sui_subscribeTransaction",
             rpc_params![SuiTransactionFilter::Any],
@@ -466,10 +477,12 @@ async fn test_full_node_transaction_streaming_basic() -> Result<(), anyhow::Erro
     wait_for_all_txes(digests.clone(), node.state().clone()).await;
 
     // Wait for streaming
+    let mut seqs = Vec::with_capacity(3);
     for digest in digests.iter().take(3) {
         match timeout(Duration::from_secs(3), sub.next()).await {
             Ok(Some(Ok(resp))) => {
-                assert_eq!(&resp.certificate.transaction_digest, digest);
+                assert_eq!(&resp.response.certificate.transaction_digest, digest);
+                seqs.push(resp.seq);
             }
             other => panic!(
                 "Failed to get Ok item from transaction streaming, but {:?}",
@@ -487,6 +500,49 @@ async fn test_full_node_transaction_streaming_basic() -> Result<(), anyhow::Erro
         ),
     }
 
+    // A subscription started from the second tx replays the executed txs, then goes live
+    let mut sub: Subscription<SuiTransactionStreamResponse> = ws_client
+        .subscribe(
+            "sui_subscribeTransaction",
+            rpc_params![SuiTransactionFilter::Any, seqs[1]],
+            "sui_unsubscribeTransaction",
+        )
+        .await
+        .unwrap();
+    for (digest, seq) in digests.iter().zip(seqs.iter()).skip(1) {
+        match timeout(Duration::from_secs(3), sub.next()).await {
+            Ok(Some(Ok(resp))) => {
+                assert_eq!(&resp.response.certificate.transaction_digest, digest);
+                assert_eq!(&resp.seq, seq);
+            }
+            other => panic!(
+                "Failed to get Ok item from transaction replay, but {:?}",
+                other
+            ),
+        };
+    }
+    let (_, _, _, digest) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+    match timeout(Duration::from_secs(3), sub.next()).await {
+        Ok(Some(Ok(resp))) => {
+            assert_eq!(resp.response.certificate.transaction_digest, digest);
+            assert!(resp.seq > seqs[2]);
+        }
+        other => panic!(
+            "Failed to get Ok item from transaction streaming, but {:?}",
+            other
+        ),
+    };
+
+    // No duplicates
+    match timeout(Duration::from_secs(3), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new txs are coming in. Got {:?}",
+            other
+        ),
+    }
+
     // Node Config without websocket_address does not create a transaction streamer
     let full_node = start_a_fullnode_with_handle(&test_cluster.swarm, None, None, true).await?;
     assert!(full_node.sui_node.state().transaction_streamer.is_none());
@@ -550,8 +606,6 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
         other => panic!("Failed to get SuiEvent, but {:?}", other),
     };
 
-    let ts = node.state().get_timestamp_ms(&digest).await?;
-
     let expected_event = SuiEvent::MoveEvent {
         package_id: ObjectID::from_hex_literal("0x2").unwrap(),
         transaction_module: "devnet_nft".into(),
@@ -564,17 +618,34 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
     // Query by move event struct name
     let events_by_sender = node
         .state()
-        .get_events_by_move_event_struct_name(
-            &struct_tag_str,
-            ts.unwrap() - HOUR_MS,
-            ts.unwrap() + HOUR_MS,
+        .get_events(
+            EventQuery::MoveEvent(struct_tag_str.clone()),
+            None,
             10,
+            false,
         )
         .await?;
     assert_eq!(events_by_sender.len(), 1);
     assert_eq!(events_by_sender[0].event, expected_event);
     assert_eq!(events_by_sender[0].tx_digest.unwrap(), digest);
 
+    // Query by move event field
+    let events_by_creator = node
+        .state()
+        .get_events(
+            EventQuery::MoveEventField {
+                struct_name: struct_tag_str.clone(),
+                path: "/creator".to_string(),
+                value: serde_json::to_value(sender)?,
+            },
+            None,
+            10,
+            false,
+        )
+        .await?;
+    assert_eq!(events_by_creator.len(), 1);
+    assert_eq!(events_by_creator[0].event, expected_event);
+
     // No more
     match timeout(Duration::from_secs(5), sub.next()).await {
         Err(_) => (),
@@ -584,6 +655,47 @@ async fn test_full_node_sub_and_query_move_event_ok() -> Result<(), anyhow::Erro
         ),
     }
 
+    // A subscription resumed from a cursor replays the stored event before going live
+    let mut sub: Subscription<SuiEventEnvelope> = ws_client
+        .subscribe(
+            "sui_subscribeEvent",
+            rpc_params![
+                SuiEventFilter::MoveEventType(struct_tag_str),
+                EventID::from((0, 0))
+            ],
+            "sui_unsubscribeEvent",
+        )
+        .await
+        .unwrap();
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => {
+            assert_eq!(envelope.id, events_by_sender[0].id);
+            assert_eq!(envelope.tx_digest, Some(digest));
+            assert_eq!(envelope.event, expected_event);
+        }
+        other => panic!("Failed to get replayed SuiEvent, but {:?}", other),
+    };
+
+    // Live events follow the replayed ones
+    let (_, _, digest) = create_devnet_nft(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Ok(Some(Ok(envelope))) => {
+            assert!(envelope.id > events_by_sender[0].id);
+            assert_eq!(envelope.tx_digest, Some(digest));
+        }
+        other => panic!("Failed to get live SuiEvent, but {:?}", other),
+    };
+
+    // No duplicates
+    match timeout(Duration::from_secs(5), sub.next()).await {
+        Err(_) => (),
+        other => panic!(
+            "Expect to time out because no new events are coming in. Got {:?}",
+            other
+        ),
+    }
+
     Ok(())
 }
 
@@ -630,67 +742,84 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
     };
 
     // query by sender
-    let params = rpc_params![sender, 10, ts.unwrap() - HOUR_MS, ts.unwrap() + HOUR_MS];
-    let events_by_sender: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsBySender", params)
+    let params = rpc_params![
+        EventQuery::Sender(sender),
+        None::<u64>,
+        10,
+        Ordering::Ascending
+    ];
+    let events_by_sender: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_sender = events_by_sender.data;
     assert_eq!(events_by_sender.len(), 1);
     assert_eq!(events_by_sender[0].event, expected_event);
     assert_eq!(events_by_sender[0].tx_digest.unwrap(), digest);
 
     // query by tx digest
-    let params = rpc_params![digest, 10];
-    let events_by_tx: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByTransaction", params)
+    let params = rpc_params![
+        EventQuery::Transaction(digest),
+        None::<u64>,
+        10,
+        Ordering::Ascending
+    ];
+    let events_by_tx: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_tx = events_by_tx.data;
     assert_eq!(events_by_tx.len(), 1);
     assert_eq!(events_by_tx[0].event, expected_event);
     assert_eq!(events_by_tx[0].tx_digest.unwrap(), digest);
 
     // query by recipient
     let params = rpc_params![
-        Owner::AddressOwner(receiver),
+        EventQuery::Recipient(Owner::AddressOwner(receiver)),
+        None::<u64>,
         10,
-        ts.unwrap() - HOUR_MS,
-        ts.unwrap() + HOUR_MS
+        Ordering::Ascending
     ];
-    let events_by_recipient: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByRecipient", params)
+    let events_by_recipient: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_recipient = events_by_recipient.data;
     assert_eq!(events_by_recipient.len(), 1);
     assert_eq!(events_by_recipient[0].event, expected_event);
     assert_eq!(events_by_recipient[0].tx_digest.unwrap(), digest);
 
     // query by object
     let params = rpc_params![
-        transferred_object,
+        EventQuery::Object(transferred_object),
+        None::<u64>,
         10,
-        ts.unwrap() - HOUR_MS,
-        ts.unwrap() + HOUR_MS
+        Ordering::Ascending
     ];
-    let events_by_object: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByObject", params)
+    let events_by_object: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_object = events_by_object.data;
     assert_eq!(events_by_object.len(), 1);
     assert_eq!(events_by_object[0].event, expected_event);
     assert_eq!(events_by_object[0].tx_digest.unwrap(), digest);
 
     // query by transaction module
     let params = rpc_params![
-        ObjectID::from_hex_literal("0x2").unwrap(),
-        "native",
+        EventQuery::MoveModule {
+            package: ObjectID::from_hex_literal("0x2").unwrap(),
+            module: "native".to_string(),
+        },
+        None::<u64>,
         10,
-        ts.unwrap() - HOUR_MS,
-        ts.unwrap() + HOUR_MS
+        Ordering::Ascending
     ];
-    let events_by_module: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByModule", params)
+    let events_by_module: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_module = events_by_module.data;
     assert_eq!(events_by_module.len(), 1);
     assert_eq!(events_by_module[0].event, expected_event);
     assert_eq!(events_by_module[0].tx_digest.unwrap(), digest);
@@ -703,24 +832,34 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
 
     // query by move event struct name
     let params = rpc_params![
-        struct_tag_str,
+        EventQuery::MoveEvent(struct_tag_str),
+        None::<u64>,
         10,
-        ts2.unwrap() - HOUR_MS,
-        ts2.unwrap() + HOUR_MS
+        Ordering::Ascending
     ];
-    let events_by_sender: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByMoveEventStructName", params)
+    let events_by_sender: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let events_by_sender = events_by_sender.data;
     assert_eq!(events_by_sender.len(), 1);
     assert_eq!(events_by_sender[0].tx_digest.unwrap(), digest2);
 
     // query all transactions
-    let params = rpc_params![10, ts.unwrap() - HOUR_MS, ts2.unwrap() + HOUR_MS];
-    let all_events: Vec<SuiEventEnvelope> = jsonrpc_client
-        .request("sui_getEventsByTimeRange", params)
+    let params = rpc_params![
+        EventQuery::TimeRange {
+            start_time: ts.unwrap() - HOUR_MS,
+            end_time: ts2.unwrap() + HOUR_MS
+        },
+        None::<u64>,
+        10,
+        Ordering::Ascending
+    ];
+    let all_events: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
         .await
         .unwrap();
+    let all_events = all_events.data;
     // The first txn emits TransferObject
     // The second txn emits MoveEvent and NewObject
     assert_eq!(all_events.len(), 3);
@@ -731,6 +870,242 @@ async fn test_full_node_event_read_api_ok() -> Result<(), anyhow::Error> {
     // Sorted in ascending time
     assert_eq!(tx_digests, vec![digest, digest2, digest2]);
 
+    // page through the same events one at a time, latest first
+    let query = EventQuery::TimeRange {
+        start_time: ts.unwrap() - HOUR_MS,
+        end_time: ts2.unwrap() + HOUR_MS,
+    };
+    let params = rpc_params![query.clone(), None::<u64>, 1, Ordering::Descending];
+    let page: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 1);
+    assert_eq!(page.data[0].id, all_events[2].id);
+    assert_eq!(page.next_cursor, Some(all_events[1].id));
+
+    let params = rpc_params![query, page.next_cursor, 2, Ordering::Descending];
+    let page: EventPage = jsonrpc_client
+        .request("sui_getEvents", params)
+        .await
+        .unwrap();
+    assert_eq!(page.data.len(), 2);
+    assert_eq!(page.data[0].id, all_events[1].id);
+    assert_eq!(page.data[1].id, all_events[0].id);
+    assert_eq!(page.next_cursor, None);
+
+    // the deprecated methods forward to the same queries, in descending time
+    let params = rpc_params![sender, 10, 0, ts2.unwrap() + HOUR_MS];
+    let events: Vec<SuiEventEnvelope> = jsonrpc_client
+        .request("sui_getEventsBySender", params)
+        .await
+        .unwrap();
+    assert_eq!(
+        events.iter().map(|event| event.id).collect::<Vec<_>>(),
+        vec![all_events[2].id, all_events[1].id, all_events[0].id]
+    );
+    let params = rpc_params![sender, 10, 0, ts.unwrap() + 1];
+    let events: Vec<SuiEventEnvelope> = jsonrpc_client
+        .request("sui_getEventsBySender", params)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].tx_digest.unwrap(), digest);
+    let params = rpc_params![digest2, 10];
+    let events: Vec<SuiEventEnvelope> = jsonrpc_client
+        .request("sui_getEventsByTransaction", params)
+        .await
+        .unwrap();
+    assert_eq!(events.len(), 2);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_get_balance_ok() -> Result<(), anyhow::Error> {
+    let test_cluster = init_cluster_builder_env_aware().build().await?;
+    let sender = test_cluster.get_address_0();
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let jsonrpc_client = &test_cluster.fullnode_handle.as_ref().unwrap().rpc_client;
+
+    let gas_coins = node
+        .state()
+        .get_owned_coins(sender, Some(&GAS::type_()), None, None)
+        .await?;
+    assert!(!gas_coins.is_empty());
+    let expected_total: u128 = gas_coins
+        .iter()
+        .map(|coin| GasCoin::try_from(coin).unwrap().value() as u128)
+        .sum();
+
+    // coin type defaults to SUI
+    let balance: Balance = jsonrpc_client
+        .request("sui_getBalance", rpc_params![sender])
+        .await?;
+    assert_eq!(balance.coin_type, GAS::type_().to_string());
+    assert_eq!(balance.coin_object_count, gas_coins.len());
+    assert_eq!(balance.total_balance, expected_total);
+
+    let balances: Vec<Balance> = jsonrpc_client
+        .request("sui_getAllBalances", rpc_params![sender])
+        .await?;
+    assert_eq!(balances, vec![balance]);
+
+    // no coins of this type are owned
+    let balance: Balance = jsonrpc_client
+        .request(
+            "sui_getBalance",
+            rpc_params![sender, "0x2::devnet_nft::DevNetNFT"],
+        )
+        .await?;
+    assert_eq!(balance.coin_object_count, 0);
+    assert_eq!(balance.total_balance, 0);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_get_coins_ok() -> Result<(), anyhow::Error> {
+    let test_cluster = init_cluster_builder_env_aware().build().await?;
+    let sender = test_cluster.get_address_0();
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let jsonrpc_client = &test_cluster.fullnode_handle.as_ref().unwrap().rpc_client;
+
+    let gas_coins = node
+        .state()
+        .get_owned_coins(sender, Some(&GAS::type_()), None, None)
+        .await?;
+    assert!(gas_coins.len() > 2);
+
+    // Page through the coins two at a time
+    let mut coins = Vec::new();
+    let mut cursor = None;
+    loop {
+        let page: CoinPage = jsonrpc_client
+            .request(
+                "sui_getCoins",
+                rpc_params![sender, GAS::type_().to_string(), cursor, 2],
+            )
+            .await?;
+        assert!(page.data.len() <= 2);
+        coins.extend(page.data);
+        cursor = page.next_cursor;
+        if cursor.is_none() {
+            break;
+        }
+    }
+
+    assert_eq!(coins.len(), gas_coins.len());
+    for (coin, object) in coins.iter().zip(&gas_coins) {
+        assert_eq!(coin.object_ref(), object.compute_object_reference());
+        assert_eq!(coin.balance, GasCoin::try_from(object).unwrap().value());
+        assert_eq!(coin.coin_type, GAS::type_().to_string());
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_checkpoint_read_api_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+    let jsonrpc_client = &test_cluster.fullnode_handle.as_ref().unwrap().rpc_client;
+
+    transfer_coin(context).await?;
+
+    // Wait for the full node to sync a certified checkpoint
+    let latest: CheckpointSequenceNumber = timeout(Duration::from_secs(60), async {
+        loop {
+            if let Ok(seq) = jsonrpc_client
+                .request("sui_getLatestCheckpointSequenceNumber", rpc_params![])
+                .await
+            {
+                break seq;
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await?;
+
+    let summary: SuiCheckpointSummary = jsonrpc_client
+        .request("sui_getCheckpointSummary", rpc_params![latest])
+        .await?;
+    assert_eq!(summary.sequence_number, latest);
+
+    let contents: SuiCheckpointContents = jsonrpc_client
+        .request("sui_getCheckpointContents", rpc_params![latest])
+        .await?;
+    assert_eq!(contents.sequence_number, latest);
+    let digest = CheckpointContents::new_with_causally_ordered_transactions(
+        contents.transactions.into_iter(),
+    )
+    .digest();
+    assert_eq!(summary.content_digest, Base64::from_bytes(&digest));
+
+    // Summaries are chained by digest
+    if latest > 0 {
+        let previous: SuiCheckpointSummary = jsonrpc_client
+            .request("sui_getCheckpointSummary", rpc_params![latest - 1])
+            .await?;
+        assert_eq!(summary.previous_digest, Some(previous.digest));
+    }
+
+    let result: Result<SuiCheckpointSummary, _> = jsonrpc_client
+        .request("sui_getCheckpointSummary", rpc_params![latest + 1000])
+        .await;
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_full_node_multi_get_ok() -> Result<(), anyhow::Error> {
+    let mut test_cluster = init_cluster_builder_env_aware().build().await?;
+    let context = &mut test_cluster.wallet;
+    let node = &test_cluster.fullnode_handle.as_ref().unwrap().sui_node;
+    let jsonrpc_client = &test_cluster.fullnode_handle.as_ref().unwrap().rpc_client;
+
+    let (transferred_object, _, _, digest) = transfer_coin(context).await?;
+    wait_for_tx(digest, node.state().clone()).await;
+
+    // Items of a batch fail independently of each other
+    let missing_object = ObjectID::random();
+    let objects: Vec<MultiGetResult<GetObjectDataResponse>> = jsonrpc_client
+        .request(
+            "sui_multiGetObjects",
+            rpc_params![vec![transferred_object, missing_object]],
+        )
+        .await?;
+    assert_eq!(objects.len(), 2);
+    assert!(matches!(
+        &objects[0],
+        MultiGetResult::Ok(GetObjectDataResponse::Exists(object)) if object.id() == transferred_object
+    ));
+    assert!(matches!(
+        &objects[1],
+        MultiGetResult::Ok(GetObjectDataResponse::NotExists(id)) if *id == missing_object
+    ));
+
+    let transactions: Vec<MultiGetResult<SuiTransactionResponse>> = jsonrpc_client
+        .request(
+            "sui_multiGetTransactions",
+            rpc_params![vec![digest, TransactionDigest::random()]],
+        )
+        .await?;
+    assert_eq!(transactions.len(), 2);
+    assert!(matches!(
+        &transactions[0],
+        MultiGetResult::Ok(tx) if tx.certificate.transaction_digest == digest
+    ));
+    assert!(matches!(&transactions[1], MultiGetResult::Error(_)));
+
+    // Batches over the configured maximum are rejected as a whole
+    let digests = vec![digest; DEFAULT_JSON_RPC_MULTI_GET_MAX_BATCH_SIZE + 1];
+    let result: Result<Vec<MultiGetResult<SuiTransactionResponse>>, _> = jsonrpc_client
+        .request("sui_multiGetTransactions", rpc_params![digests])
+        .await;
+    assert!(result.is_err());
+
     Ok(())
 }
 
@@ -294,6 +294,8 @@ impl TestClusterBuilder {
                 ..Default::default()
             }),
             active_address,
+            envs: vec![],
+            active_env: None,
         }
         .save(&wallet_path)?;
 